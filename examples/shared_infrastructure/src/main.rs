@@ -38,7 +38,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Creating shared infrastructure...");
     let infra_config = InfrastructureConfig::new(rpc_url, swqos_configs, commitment);
     let infrastructure = Arc::new(TradingInfrastructure::new(infra_config).await);
-    println!("Infrastructure created with {} SWQOS clients", infrastructure.swqos_clients.len());
+    println!("Infrastructure created with {} SWQOS clients", infrastructure.swqos_clients().len());
 
     // Step 2: Create multiple TradingClients sharing the same infrastructure (fast)
     let wallet_keys = vec![
@@ -64,7 +64,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("\nCreated {} clients sharing 1 infrastructure instance", clients.len());
     println!("  - 1 RPC client (shared)");
-    println!("  - {} SWQOS clients (shared)", infrastructure.swqos_clients.len());
+    println!("  - {} SWQOS clients (shared)", infrastructure.swqos_clients().len());
 
     // All clients can now trade concurrently using shared resources
     // Example: clients[0].buy(buy_params).await?;
@@ -236,6 +236,18 @@ impl GasFeeStrategy {
         });
     }
 
+    /// 返回独立副本，`trade_type` 的所有策略 cu_limit 至少为 `cu_limit`（不影响原策略）。
+    /// Detached copy with every `trade_type` strategy's cu_limit raised to at least `cu_limit`
+    pub fn with_min_cu_limit(&self, trade_type: TradeType, cu_limit: u32) -> Self {
+        let mut map = (**self.strategies.load()).clone();
+        for ((_, t_type, _), value) in map.iter_mut() {
+            if *t_type == trade_type {
+                value.cu_limit = value.cu_limit.max(cu_limit);
+            }
+        }
+        Self { strategies: Arc::new(ArcSwap::from_pointee(map)) }
+    }
+
     /// 移除指定(SwqosType,TradeType)的策略。
     /// Remove strategy for specified (SwqosType,TradeType)
     pub fn del_all(&self, swqos_type: SwqosType, trade_type: TradeType) {
@@ -23,3 +23,141 @@ pub fn initialize_account3(
     ];
     Ok(Instruction { program_id: *token_program_id, accounts, data })
 }
+
+/// Size of the base mint, shared with SPL Token.
+pub const MINT_LEN: usize = 82;
+/// Extensions follow the account type byte, placed after the size of a base token account.
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// Extension type discriminators (`ExtensionType`) of the mint extensions read by the SDK.
+pub mod extension {
+    pub const TRANSFER_FEE_CONFIG: u16 = 1;
+    pub const DEFAULT_ACCOUNT_STATE: u16 = 6;
+    pub const NON_TRANSFERABLE: u16 = 9;
+    pub const PERMANENT_DELEGATE: u16 = 12;
+    pub const TRANSFER_HOOK: u16 = 14;
+}
+
+/// TLV extensions of a Token-2022 mint account as `(type, value)`; empty for a plain mint.
+pub fn mint_extensions(data: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut extensions = Vec::new();
+    if data.get(ACCOUNT_TYPE_OFFSET) != Some(&ACCOUNT_TYPE_MINT) {
+        return extensions;
+    }
+    let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+    while let Some(header) = data.get(offset..offset + 4) {
+        let ext_type = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        if ext_type == 0 {
+            break;
+        }
+        let Some(value) = data.get(offset + 4..offset + 4 + len) else { break };
+        extensions.push((ext_type, value));
+        offset += 4 + len;
+    }
+    extensions
+}
+
+/// Transfer fee of one epoch range.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferFee {
+    /// First epoch the fee applies to.
+    pub epoch: u64,
+    pub maximum_fee: u64,
+    pub transfer_fee_basis_points: u16,
+}
+
+impl TransferFee {
+    /// Fee withheld when transferring `pre_fee_amount` (rounded up, capped at `maximum_fee`).
+    pub fn calculate_fee(&self, pre_fee_amount: u64) -> u64 {
+        let basis_points = self.transfer_fee_basis_points as u128;
+        if basis_points == 0 || pre_fee_amount == 0 {
+            return 0;
+        }
+        let fee = (pre_fee_amount as u128 * basis_points).div_ceil(10_000);
+        fee.min(self.maximum_fee as u128) as u64
+    }
+
+    /// Amount the receiver gets when `pre_fee_amount` is transferred.
+    pub fn calculate_post_fee_amount(&self, pre_fee_amount: u64) -> u64 {
+        pre_fee_amount.saturating_sub(self.calculate_fee(pre_fee_amount))
+    }
+
+    fn parse(data: &[u8]) -> Self {
+        Self {
+            epoch: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            maximum_fee: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+            transfer_fee_basis_points: u16::from_le_bytes([data[16], data[17]]),
+        }
+    }
+}
+
+/// `TransferFeeConfig` extension: the fee in force and the one scheduled to replace it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferFeeConfig {
+    pub older_transfer_fee: TransferFee,
+    pub newer_transfer_fee: TransferFee,
+}
+
+impl TransferFeeConfig {
+    /// Parse the extension value: two authorities (64) + withheld amount (8), then the older and
+    /// newer fees (18 each).
+    pub fn parse(value: &[u8]) -> Option<Self> {
+        let fees = value.get(72..108)?;
+        Some(Self {
+            older_transfer_fee: TransferFee::parse(&fees[..18]),
+            newer_transfer_fee: TransferFee::parse(&fees[18..]),
+        })
+    }
+
+    /// Transfer fee config of a mint account, if it has one.
+    pub fn from_mint(data: &[u8]) -> Option<Self> {
+        mint_extensions(data)
+            .into_iter()
+            .find(|(ext_type, _)| *ext_type == extension::TRANSFER_FEE_CONFIG)
+            .and_then(|(_, value)| Self::parse(value))
+    }
+
+    /// Fee in force at `epoch`.
+    pub fn epoch_fee(&self, epoch: u64) -> &TransferFee {
+        if epoch >= self.newer_transfer_fee.epoch {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fee(epoch: u64, maximum_fee: u64, transfer_fee_basis_points: u16) -> TransferFee {
+        TransferFee { epoch, maximum_fee, transfer_fee_basis_points }
+    }
+
+    #[test]
+    fn fee_rounds_up_and_is_capped() {
+        let one_percent = fee(0, 1_000, 100);
+        assert_eq!(one_percent.calculate_fee(10_000), 100);
+        // 1% of 10_001 is 100.01, rounded up.
+        assert_eq!(one_percent.calculate_fee(10_001), 101);
+        assert_eq!(one_percent.calculate_fee(1), 1);
+        assert_eq!(one_percent.calculate_fee(0), 0);
+        assert_eq!(one_percent.calculate_fee(1_000_000), 1_000);
+        assert_eq!(one_percent.calculate_post_fee_amount(1_000_000), 999_000);
+        assert_eq!(fee(0, 1_000, 0).calculate_fee(1_000_000), 0);
+    }
+
+    #[test]
+    fn newer_fee_applies_from_its_epoch() {
+        let config = TransferFeeConfig {
+            older_transfer_fee: fee(0, 10, 50),
+            newer_transfer_fee: fee(100, 20, 200),
+        };
+        assert_eq!(config.epoch_fee(99), &config.older_transfer_fee);
+        assert_eq!(config.epoch_fee(100), &config.newer_transfer_fee);
+        assert_eq!(config.epoch_fee(101).calculate_fee(10_000), 20);
+    }
+}
@@ -1,11 +1,15 @@
 use crate::{
     constants::trade::trade::DEFAULT_SLIPPAGE,
     instruction::utils::bonk::{
-        accounts, get_pool_pda, get_vault_pda, BUY_EXECT_IN_DISCRIMINATOR,
+        accounts, get_creator_fee_vault, get_creator_fee_vault_authority, get_metadata_pda,
+        get_platform_fee_vault, get_platform_fee_vault_authority, get_pool_pda, get_vault_pda,
+        get_vesting_record_pda, BUY_EXECT_IN_DISCRIMINATOR, CLAIM_CREATOR_FEE_DISCRIMINATOR,
+        CLAIM_PLATFORM_FEE_FROM_VAULT_DISCRIMINATOR, CLAIM_VESTED_TOKEN_DISCRIMINATOR,
+        CREATE_VESTING_ACCOUNT_DISCRIMINATOR, INITIALIZE_V2_DISCRIMINATOR,
         SELL_EXECT_IN_DISCRIMINATOR,
     },
     trading::core::{
-        params::{BonkParams, SwapParams},
+        params::{BonkCreateTokenParams, BonkParams, SwapParams},
         traits::InstructionBuilder,
     },
     utils::calc::bonk::{
@@ -313,3 +317,408 @@ impl InstructionBuilder for BonkInstructionBuilder {
         Ok(instructions)
     }
 }
+
+/// Quote mint and global config of a LaunchLab pool (SOL pools use WSOL, USD1 pools `USD1_GLOBAL_CONFIG`).
+#[inline]
+fn quote_mint_and_global_config(usd1_pool: bool) -> (Pubkey, Pubkey) {
+    if usd1_pool {
+        (crate::constants::USD1_TOKEN_ACCOUNT, accounts::USD1_GLOBAL_CONFIG)
+    } else {
+        (crate::constants::WSOL_TOKEN_ACCOUNT, accounts::GLOBAL_CONFIG)
+    }
+}
+
+/// LaunchLab `initialize_v2`: create the mint, its metadata and the bonding-curve pool in one instruction.
+///
+/// `creator` pays for the new accounts and `base_mint` must sign (fresh keypair). The mint is
+/// always created with the SPL Token program; without vesting the schedule is sent as all zeros.
+pub fn initialize_v2_instruction(
+    creator: &Pubkey,
+    base_mint: &Pubkey,
+    params: &BonkCreateTokenParams,
+) -> Result<Instruction> {
+    let (quote_mint, global_config) = quote_mint_and_global_config(params.is_usd1_pool());
+    let pool_state = get_pool_pda(base_mint, &quote_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk pool PDA"))?;
+    let base_vault = get_vault_pda(&pool_state, base_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk base vault PDA"))?;
+    let quote_vault = get_vault_pda(&pool_state, &quote_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk quote vault PDA"))?;
+    let metadata_account =
+        get_metadata_pda(base_mint).ok_or_else(|| anyhow!("Failed to derive metadata PDA"))?;
+
+    let mut data = Vec::with_capacity(128);
+    data.extend_from_slice(&INITIALIZE_V2_DISCRIMINATOR);
+    borsh::to_writer(&mut data, &params.mint_params)?;
+    borsh::to_writer(&mut data, &params.curve_params)?;
+    borsh::to_writer(&mut data, &params.vesting_params.clone().unwrap_or_default())?;
+    borsh::to_writer(&mut data, &params.amm_fee_on)?;
+
+    let accounts: [AccountMeta; 18] = [
+        AccountMeta::new(*creator, true),                // Payer (signer)
+        AccountMeta::new_readonly(*creator, true),       // Creator (signer)
+        AccountMeta::new_readonly(global_config, false), // Global Config (readonly)
+        AccountMeta::new_readonly(params.platform_config, false), // Platform Config (readonly)
+        accounts::AUTHORITY_META,                        // Authority (readonly)
+        AccountMeta::new(pool_state, false),             // Pool State
+        AccountMeta::new(*base_mint, true),              // Base Mint (signer)
+        AccountMeta::new_readonly(quote_mint, false),    // Quote Mint (readonly)
+        AccountMeta::new(base_vault, false),             // Base Vault
+        AccountMeta::new(quote_vault, false),            // Quote Vault
+        AccountMeta::new(metadata_account, false),       // Metadata Account
+        crate::constants::TOKEN_PROGRAM_META,            // Base Token Program (readonly)
+        crate::constants::TOKEN_PROGRAM_META,            // Quote Token Program (readonly)
+        AccountMeta::new_readonly(accounts::METADATA_PROGRAM, false), // Metadata Program (readonly)
+        crate::constants::SYSTEM_PROGRAM_META,           // System Program (readonly)
+        crate::constants::RENT_META,                     // Rent Program (readonly)
+        accounts::EVENT_AUTHORITY_META,                  // Event Authority (readonly)
+        accounts::BONK_META,                             // Program (readonly)
+    ];
+
+    Ok(Instruction::new_with_bytes(accounts::BONK, &data, accounts.to_vec()))
+}
+
+/// Creator's `buy_exact_in` of `params.initial_buy_amount`, built from PDAs only so it can follow
+/// [`initialize_v2_instruction`] in the same transaction (the pool does not exist yet).
+///
+/// Only the trade instruction is returned; the caller prepares the quote/base token accounts.
+pub fn initial_buy_instruction(
+    creator: &Pubkey,
+    base_mint: &Pubkey,
+    params: &BonkCreateTokenParams,
+    open_seed_optimize: bool,
+) -> Result<Instruction> {
+    let amount_in = params
+        .initial_buy_amount
+        .filter(|&a| a > 0)
+        .ok_or_else(|| anyhow!("Initial buy amount cannot be zero"))?;
+    let minimum_amount_out = params.initial_buy_minimum_amount_out;
+    let (quote_mint, global_config) = quote_mint_and_global_config(params.is_usd1_pool());
+    let pool_state = get_pool_pda(base_mint, &quote_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk pool PDA"))?;
+    let base_vault = get_vault_pda(&pool_state, base_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk base vault PDA"))?;
+    let quote_vault = get_vault_pda(&pool_state, &quote_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk quote vault PDA"))?;
+    let platform_fee_vault = get_platform_fee_vault(&params.platform_config, &quote_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk platform fee vault"))?;
+    let creator_fee_vault = get_creator_fee_vault(creator, &quote_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk creator fee vault"))?;
+
+    let user_base_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast_use_seed(
+            creator,
+            base_mint,
+            &crate::constants::TOKEN_PROGRAM,
+            open_seed_optimize,
+        );
+    let user_quote_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast_use_seed(
+            creator,
+            &quote_mint,
+            &crate::constants::TOKEN_PROGRAM,
+            open_seed_optimize,
+        );
+
+    let share_fee_rate: u64 = 0;
+    let mut data = [0u8; 32];
+    data[..8].copy_from_slice(&BUY_EXECT_IN_DISCRIMINATOR);
+    data[8..16].copy_from_slice(&amount_in.to_le_bytes());
+    data[16..24].copy_from_slice(&minimum_amount_out.to_le_bytes());
+    data[24..32].copy_from_slice(&share_fee_rate.to_le_bytes());
+
+    let accounts: [AccountMeta; 18] = [
+        AccountMeta::new(*creator, true),                // Payer (signer)
+        accounts::AUTHORITY_META,                        // Authority (readonly)
+        AccountMeta::new_readonly(global_config, false), // Global Config (readonly)
+        AccountMeta::new_readonly(params.platform_config, false), // Platform Config (readonly)
+        AccountMeta::new(pool_state, false),             // Pool State
+        AccountMeta::new(user_base_token_account, false), // User Base Token
+        AccountMeta::new(user_quote_token_account, false), // User Quote Token
+        AccountMeta::new(base_vault, false),             // Base Vault
+        AccountMeta::new(quote_vault, false),            // Quote Vault
+        AccountMeta::new_readonly(*base_mint, false),    // Base Token Mint (readonly)
+        AccountMeta::new_readonly(quote_mint, false),    // Quote Token Mint (readonly)
+        crate::constants::TOKEN_PROGRAM_META,            // Base Token Program (readonly)
+        crate::constants::TOKEN_PROGRAM_META,            // Quote Token Program (readonly)
+        accounts::EVENT_AUTHORITY_META,                  // Event Authority (readonly)
+        accounts::BONK_META,                             // Program (readonly)
+        crate::constants::SYSTEM_PROGRAM_META,           // System Program (readonly)
+        AccountMeta::new(platform_fee_vault, false),     // Platform Associated Account
+        AccountMeta::new(creator_fee_vault, false),      // Creator Associated Account
+    ];
+
+    Ok(Instruction::new_with_bytes(accounts::BONK, &data, accounts.to_vec()))
+}
+
+/// LaunchLab `create_vesting_account`: allocate `share_amount` of the pool's locked tokens to
+/// `beneficiary`. Signed by the pool creator, before the vesting schedule's allocation is used up.
+pub fn create_vesting_account_instruction(
+    creator: &Pubkey,
+    beneficiary: &Pubkey,
+    pool_state: &Pubkey,
+    share_amount: u64,
+) -> Result<Instruction> {
+    let vesting_record = get_vesting_record_pda(pool_state, beneficiary)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk vesting record PDA"))?;
+
+    let mut data = [0u8; 16];
+    data[..8].copy_from_slice(&CREATE_VESTING_ACCOUNT_DISCRIMINATOR);
+    data[8..16].copy_from_slice(&share_amount.to_le_bytes());
+
+    let accounts: [AccountMeta; 5] = [
+        AccountMeta::new(*creator, true),        // Creator (signer)
+        AccountMeta::new(*beneficiary, false),   // Beneficiary
+        AccountMeta::new(*pool_state, false),    // Pool State
+        AccountMeta::new(vesting_record, false), // Vesting Record
+        crate::constants::SYSTEM_PROGRAM_META,   // System Program (readonly)
+    ];
+
+    Ok(Instruction::new_with_bytes(accounts::BONK, &data, accounts.to_vec()))
+}
+
+/// LaunchLab `claim_vested_token`: release the unlocked part of `beneficiary`'s vesting record
+/// into its base token ATA (created by the program if missing). Only claimable after migration
+/// and the cliff period.
+pub fn claim_vested_token_instruction(
+    beneficiary: &Pubkey,
+    pool_state: &Pubkey,
+    base_mint: &Pubkey,
+) -> Result<Instruction> {
+    let vesting_record = get_vesting_record_pda(pool_state, beneficiary)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk vesting record PDA"))?;
+    let base_vault = get_vault_pda(pool_state, base_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk base vault PDA"))?;
+    let user_base_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            beneficiary,
+            base_mint,
+            &crate::constants::TOKEN_PROGRAM,
+        );
+
+    let accounts: [AccountMeta; 10] = [
+        AccountMeta::new(*beneficiary, true),    // Beneficiary (signer)
+        accounts::AUTHORITY_META,                // Authority (readonly)
+        AccountMeta::new(*pool_state, false),    // Pool State
+        AccountMeta::new(vesting_record, false), // Vesting Record
+        AccountMeta::new(base_vault, false),     // Base Vault
+        AccountMeta::new(user_base_token_account, false), // User Base Token
+        AccountMeta::new_readonly(*base_mint, false), // Base Token Mint (readonly)
+        crate::constants::TOKEN_PROGRAM_META,    // Base Token Program (readonly)
+        crate::constants::SYSTEM_PROGRAM_META,   // System Program (readonly)
+        AccountMeta::new_readonly(crate::constants::ASSOCIATED_TOKEN_PROGRAM_ID, false), // Associated Token Program (readonly)
+    ];
+
+    Ok(Instruction::new_with_bytes(
+        accounts::BONK,
+        &CLAIM_VESTED_TOKEN_DISCRIMINATOR,
+        accounts.to_vec(),
+    ))
+}
+
+/// LaunchLab `claim_creator_fee`: withdraw the creator's accumulated `quote_mint` trading fees
+/// (all pools of this creator share one vault per quote mint) into the creator's quote ATA.
+pub fn claim_creator_fee_instruction(creator: &Pubkey, quote_mint: &Pubkey) -> Result<Instruction> {
+    let fee_vault_authority = get_creator_fee_vault_authority()
+        .ok_or_else(|| anyhow!("Failed to derive Bonk creator fee vault authority"))?;
+    let creator_fee_vault = get_creator_fee_vault(creator, quote_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk creator fee vault"))?;
+    let recipient_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            creator,
+            quote_mint,
+            &crate::constants::TOKEN_PROGRAM,
+        );
+
+    let accounts: [AccountMeta; 8] = [
+        AccountMeta::new(*creator, true), // Creator (signer)
+        AccountMeta::new_readonly(fee_vault_authority, false), // Fee Vault Authority (readonly)
+        AccountMeta::new(creator_fee_vault, false), // Creator Fee Vault
+        AccountMeta::new(recipient_token_account, false), // Recipient Token Account
+        AccountMeta::new_readonly(*quote_mint, false), // Quote Mint (readonly)
+        crate::constants::TOKEN_PROGRAM_META, // Token Program (readonly)
+        crate::constants::SYSTEM_PROGRAM_META, // System Program (readonly)
+        AccountMeta::new_readonly(crate::constants::ASSOCIATED_TOKEN_PROGRAM_ID, false), // Associated Token Program (readonly)
+    ];
+
+    Ok(Instruction::new_with_bytes(
+        accounts::BONK,
+        &CLAIM_CREATOR_FEE_DISCRIMINATOR,
+        accounts.to_vec(),
+    ))
+}
+
+/// LaunchLab `claim_platform_fee_from_vault`: withdraw a platform's accumulated `quote_mint` fees.
+/// Must be signed by the platform's fee wallet (`platform_fee_wallet` in its platform config).
+pub fn claim_platform_fee_instruction(
+    platform_fee_wallet: &Pubkey,
+    platform_config: &Pubkey,
+    quote_mint: &Pubkey,
+) -> Result<Instruction> {
+    let fee_vault_authority = get_platform_fee_vault_authority()
+        .ok_or_else(|| anyhow!("Failed to derive Bonk platform fee vault authority"))?;
+    let platform_fee_vault = get_platform_fee_vault(platform_config, quote_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk platform fee vault"))?;
+    let recipient_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            platform_fee_wallet,
+            quote_mint,
+            &crate::constants::TOKEN_PROGRAM,
+        );
+
+    let accounts: [AccountMeta; 9] = [
+        AccountMeta::new(*platform_fee_wallet, true), // Platform Fee Wallet (signer)
+        AccountMeta::new_readonly(fee_vault_authority, false), // Fee Vault Authority (readonly)
+        AccountMeta::new_readonly(*platform_config, false), // Platform Config (readonly)
+        AccountMeta::new(platform_fee_vault, false),  // Platform Fee Vault
+        AccountMeta::new(recipient_token_account, false), // Recipient Token Account
+        AccountMeta::new_readonly(*quote_mint, false), // Quote Mint (readonly)
+        crate::constants::TOKEN_PROGRAM_META,         // Token Program (readonly)
+        crate::constants::SYSTEM_PROGRAM_META,        // System Program (readonly)
+        AccountMeta::new_readonly(crate::constants::ASSOCIATED_TOKEN_PROGRAM_ID, false), // Associated Token Program (readonly)
+    ];
+
+    Ok(Instruction::new_with_bytes(
+        accounts::BONK,
+        &CLAIM_PLATFORM_FEE_FROM_VAULT_DISCRIMINATOR,
+        accounts.to_vec(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::utils::bonk_types::{
+        CurveParams, FixedCurve, MintParams, VestingParams,
+    };
+
+    fn launch_params() -> BonkCreateTokenParams {
+        let mint_params = MintParams {
+            decimals: 6,
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            uri: "https://example.com/t.json".to_string(),
+        };
+        let curve_params = CurveParams::Fixed {
+            data: FixedCurve {
+                supply: 1_000_000_000_000_000,
+                total_quote_fund_raising: 85_000_000_000,
+                migrate_type: 1,
+            },
+        };
+        BonkCreateTokenParams::new(mint_params, curve_params, Pubkey::new_unique())
+    }
+
+    #[test]
+    fn initialize_v2_serializes_curve_vesting_and_usd1_accounts() {
+        let (creator, base_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut params = launch_params();
+        params.global_config = accounts::USD1_GLOBAL_CONFIG;
+        params.vesting_params = Some(VestingParams {
+            total_locked_amount: 1_000,
+            cliff_period: 60,
+            unlock_period: 600,
+        });
+        let ix = initialize_v2_instruction(&creator, &base_mint, &params).unwrap();
+
+        let mut expected = INITIALIZE_V2_DISCRIMINATOR.to_vec();
+        borsh::to_writer(&mut expected, &params.mint_params).unwrap();
+        expected.push(1); // CurveParams::Fixed
+        for value in [1_000_000_000_000_000u64, 85_000_000_000] {
+            expected.extend_from_slice(&value.to_le_bytes());
+        }
+        expected.push(1); // migrate_type
+        for value in [1_000u64, 60, 600] {
+            expected.extend_from_slice(&value.to_le_bytes());
+        }
+        expected.push(0); // AmmFeeOn::QuoteToken
+        assert_eq!(ix.data, expected);
+
+        let pool_state = get_pool_pda(&base_mint, &crate::constants::USD1_TOKEN_ACCOUNT).unwrap();
+        assert_eq!(ix.accounts[2].pubkey, accounts::USD1_GLOBAL_CONFIG);
+        assert_eq!(ix.accounts[5].pubkey, pool_state);
+        assert!(ix.accounts[6].is_signer && ix.accounts[6].pubkey == base_mint);
+        assert_eq!(ix.accounts[7].pubkey, crate::constants::USD1_TOKEN_ACCOUNT);
+    }
+
+    #[test]
+    fn initialize_v2_without_vesting_sends_zero_schedule() {
+        let ix = initialize_v2_instruction(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &launch_params(),
+        )
+        .unwrap();
+        // ... curve, then 3 zero u64s for the schedule and the fee-on byte.
+        assert_eq!(&ix.data[ix.data.len() - 25..], &[0u8; 25][..]);
+        assert_eq!(ix.accounts[2].pubkey, accounts::GLOBAL_CONFIG);
+        assert_eq!(ix.accounts[7].pubkey, crate::constants::WSOL_TOKEN_ACCOUNT);
+    }
+
+    #[test]
+    fn initial_buy_targets_the_new_pool() {
+        let (creator, base_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut params = launch_params();
+        assert!(initial_buy_instruction(&creator, &base_mint, &params, true).is_err());
+
+        params.initial_buy_amount = Some(500_000_000);
+        params.initial_buy_minimum_amount_out = 1_234;
+        let ix = initial_buy_instruction(&creator, &base_mint, &params, true).unwrap();
+        assert_eq!(&ix.data[..8], &BUY_EXECT_IN_DISCRIMINATOR);
+        assert_eq!(ix.data[8..16], 500_000_000u64.to_le_bytes());
+        assert_eq!(ix.data[16..24], 1_234u64.to_le_bytes());
+        let pool_state = get_pool_pda(&base_mint, &crate::constants::WSOL_TOKEN_ACCOUNT).unwrap();
+        assert_eq!(ix.accounts[4].pubkey, pool_state);
+        assert_eq!(
+            ix.accounts[17].pubkey,
+            get_creator_fee_vault(&creator, &crate::constants::WSOL_TOKEN_ACCOUNT).unwrap()
+        );
+    }
+
+    #[test]
+    fn vesting_record_is_per_pool_and_beneficiary() {
+        let (creator, pool_state) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let create = create_vesting_account_instruction(&creator, &alice, &pool_state, 42).unwrap();
+        assert_eq!(&create.data[..8], &CREATE_VESTING_ACCOUNT_DISCRIMINATOR);
+        assert_eq!(create.data[8..], 42u64.to_le_bytes());
+        let record = get_vesting_record_pda(&pool_state, &alice).unwrap();
+        assert_eq!(create.accounts[3].pubkey, record);
+        assert_ne!(record, get_vesting_record_pda(&pool_state, &bob).unwrap());
+
+        let base_mint = Pubkey::new_unique();
+        let claim = claim_vested_token_instruction(&alice, &pool_state, &base_mint).unwrap();
+        assert_eq!(claim.data, CLAIM_VESTED_TOKEN_DISCRIMINATOR);
+        assert!(claim.accounts[0].is_signer && claim.accounts[0].pubkey == alice);
+        assert_eq!(claim.accounts[3].pubkey, record);
+        assert_eq!(claim.accounts[4].pubkey, get_vault_pda(&pool_state, &base_mint).unwrap());
+    }
+
+    #[test]
+    fn fee_claims_use_the_vault_of_the_quote_mint() {
+        let creator = Pubkey::new_unique();
+        let usd1 = crate::constants::USD1_TOKEN_ACCOUNT;
+        let ix = claim_creator_fee_instruction(&creator, &usd1).unwrap();
+        assert_eq!(ix.data, CLAIM_CREATOR_FEE_DISCRIMINATOR);
+        assert_eq!(ix.accounts[2].pubkey, get_creator_fee_vault(&creator, &usd1).unwrap());
+        assert_eq!(
+            ix.accounts[3].pubkey,
+            crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+                &creator,
+                &usd1,
+                &crate::constants::TOKEN_PROGRAM,
+            )
+        );
+
+        let (fee_wallet, platform_config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let wsol = crate::constants::WSOL_TOKEN_ACCOUNT;
+        let ix = claim_platform_fee_instruction(&fee_wallet, &platform_config, &wsol).unwrap();
+        assert_eq!(ix.data, CLAIM_PLATFORM_FEE_FROM_VAULT_DISCRIMINATOR);
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].pubkey == fee_wallet);
+        assert_eq!(ix.accounts[3].pubkey, get_platform_fee_vault(&platform_config, &wsol).unwrap());
+        assert_eq!(
+            get_platform_fee_vault(&platform_config, &wsol),
+            crate::instruction::utils::bonk::get_platform_associated_account(&platform_config)
+        );
+    }
+}
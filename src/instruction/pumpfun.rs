@@ -9,9 +9,17 @@ use crate::{
 use crate::{
     instruction::utils::pumpfun::{
         accounts, get_bonding_curve_pda, get_bonding_curve_v2_pda, get_creator,
-        get_mayhem_fee_recipient_meta_random, get_user_volume_accumulator_pda,
+        get_creator_vault_pda, get_fee_program_event_authority_pda,
+        get_mayhem_fee_recipient_meta_random, get_sharing_config_pda,
+        get_user_volume_accumulator_pda,
         global_constants::{self},
-        BUY_DISCRIMINATOR, BUY_EXACT_SOL_IN_DISCRIMINATOR, SELL_DISCRIMINATOR,
+        Shareholder, BUY_DISCRIMINATOR, BUY_EXACT_SOL_IN_DISCRIMINATOR,
+        CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR, CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR,
+        COLLECT_CREATOR_FEE_DISCRIMINATOR, CREATE_FEE_SHARING_CONFIG_DISCRIMINATOR,
+        DISTRIBUTE_CREATOR_FEES_DISCRIMINATOR, GET_MINIMUM_DISTRIBUTABLE_FEE_DISCRIMINATOR,
+        INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR, REVOKE_FEE_SHARING_AUTHORITY_DISCRIMINATOR,
+        SELL_DISCRIMINATOR, SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR,
+        UPDATE_FEE_SHARES_DISCRIMINATOR,
     },
     utils::calc::{
         common::{calculate_with_slippage_buy, calculate_with_slippage_sell},
@@ -333,3 +341,292 @@ pub fn claim_cashback_pumpfun_instruction(payer: &Pubkey) -> Option<Instruction>
     ];
     Some(Instruction::new_with_bytes(accounts::PUMPFUN, &CLAIM_CASHBACK_DISCRIMINATOR, accounts))
 }
+
+/// Collect creator fees: transfers all lamports above rent from `creator`'s creator vault to `creator`.
+pub fn collect_creator_fee_instruction(creator: &Pubkey) -> Option<Instruction> {
+    let creator_vault = get_creator_vault_pda(creator)?;
+    let accounts = vec![
+        AccountMeta::new(*creator, true),       // creator (signer, writable)
+        AccountMeta::new(creator_vault, false), // creator_vault (writable)
+        crate::constants::SYSTEM_PROGRAM_META,
+        accounts::EVENT_AUTHORITY_META,
+        accounts::PUMPFUN_META,
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::PUMPFUN,
+        &COLLECT_CREATOR_FEE_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Distribute the creator vault of a fee-shared coin to its shareholders (permissionless).
+/// `shareholders` must be the current shareholders of the sharing config, in config order.
+pub fn distribute_creator_fees_instruction(
+    mint: &Pubkey,
+    shareholders: &[Pubkey],
+) -> Option<Instruction> {
+    let sharing_config = get_sharing_config_pda(mint)?;
+    let mut accounts = Vec::with_capacity(7 + shareholders.len());
+    accounts.extend([
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_bonding_curve_pda(mint)?, false),
+        AccountMeta::new_readonly(sharing_config, false),
+        AccountMeta::new(get_creator_vault_pda(&sharing_config)?, false), // creator_vault of bonding_curve.creator (= sharing_config)
+        crate::constants::SYSTEM_PROGRAM_META,
+        accounts::EVENT_AUTHORITY_META,
+        accounts::PUMPFUN_META,
+    ]);
+    accounts.extend(shareholders.iter().map(|s| AccountMeta::new(*s, false)));
+    Some(Instruction::new_with_bytes(
+        accounts::PUMPFUN,
+        &DISTRIBUTE_CREATOR_FEES_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Read-only check of a fee-shared coin's creator vault; meant to be simulated, the result is
+/// returned as `MinimumDistributableFee` return data.
+pub fn get_minimum_distributable_fee_instruction(mint: &Pubkey) -> Option<Instruction> {
+    let sharing_config = get_sharing_config_pda(mint)?;
+    let accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_bonding_curve_pda(mint)?, false),
+        AccountMeta::new_readonly(sharing_config, false),
+        AccountMeta::new_readonly(get_creator_vault_pda(&sharing_config)?, false),
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::PUMPFUN,
+        &GET_MINIMUM_DISTRIBUTABLE_FEE_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Create the fee sharing config of `mint` (fee program); `payer` must be the coin creator and
+/// becomes the config admin. Pass the PumpSwap `pool` when the coin has already migrated.
+pub fn create_fee_sharing_config_instruction(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    pool: Option<&Pubkey>,
+) -> Option<Instruction> {
+    use crate::instruction::utils::pumpswap::accounts as amm_accounts;
+    // Anchor encodes an absent optional account as the program id.
+    let (pool, amm_program, amm_event_authority) = match pool {
+        Some(pool) => (
+            AccountMeta::new(*pool, false),
+            AccountMeta::new_readonly(amm_accounts::AMM_PROGRAM, false),
+            AccountMeta::new_readonly(amm_accounts::EVENT_AUTHORITY, false),
+        ),
+        None => {
+            (accounts::FEE_PROGRAM_META, accounts::FEE_PROGRAM_META, accounts::FEE_PROGRAM_META)
+        }
+    };
+    let accounts = vec![
+        AccountMeta::new_readonly(get_fee_program_event_authority_pda(), false), // event_authority
+        accounts::FEE_PROGRAM_META,                                              // program
+        AccountMeta::new(*payer, true),                                          // payer (signer)
+        global_constants::GLOBAL_ACCOUNT_META,                                   // global
+        AccountMeta::new_readonly(*mint, false),                                 // mint
+        AccountMeta::new(get_sharing_config_pda(mint)?, false),                  // sharing_config
+        crate::constants::SYSTEM_PROGRAM_META,
+        AccountMeta::new(get_bonding_curve_pda(mint)?, false), // bonding_curve
+        accounts::PUMPFUN_META,                                // pump_program
+        accounts::EVENT_AUTHORITY_META,                        // pump_event_authority
+        pool,
+        amm_program,
+        amm_event_authority,
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::FEE_PROGRAM,
+        &CREATE_FEE_SHARING_CONFIG_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Replace the shareholders of `mint`'s fee sharing config (signed by the config admin).
+/// Pending fees are paid out to `current_shareholders` first, so pass them in config order.
+pub fn update_fee_shares_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    shareholders: &[Shareholder],
+    current_shareholders: &[Pubkey],
+) -> Option<Instruction> {
+    use crate::instruction::utils::pumpswap::{
+        accounts as amm_accounts, coin_creator_vault_ata, coin_creator_vault_authority,
+    };
+    let sharing_config = get_sharing_config_pda(mint)?;
+    let mut data = Vec::with_capacity(12 + shareholders.len() * 34);
+    data.extend_from_slice(&UPDATE_FEE_SHARES_DISCRIMINATOR);
+    borsh::to_writer(&mut data, &shareholders.to_vec()).ok()?;
+
+    let mut accounts = Vec::with_capacity(17 + current_shareholders.len());
+    accounts.extend([
+        AccountMeta::new_readonly(get_fee_program_event_authority_pda(), false), // event_authority
+        accounts::FEE_PROGRAM_META,                                              // program
+        AccountMeta::new_readonly(*authority, true), // authority (signer)
+        global_constants::GLOBAL_ACCOUNT_META,       // global
+        AccountMeta::new_readonly(*mint, false),     // mint
+        AccountMeta::new(sharing_config, false),     // sharing_config
+        AccountMeta::new_readonly(get_bonding_curve_pda(mint)?, false), // bonding_curve
+        AccountMeta::new(get_creator_vault_pda(&sharing_config)?, false), // pump_creator_vault
+        crate::constants::SYSTEM_PROGRAM_META,
+        accounts::PUMPFUN_META,         // pump_program
+        accounts::EVENT_AUTHORITY_META, // pump_event_authority
+        AccountMeta::new_readonly(amm_accounts::AMM_PROGRAM, false), // pump_amm_program
+        AccountMeta::new_readonly(amm_accounts::EVENT_AUTHORITY, false), // amm_event_authority
+        crate::constants::WSOL_TOKEN_ACCOUNT_META, // wsol_mint
+        crate::constants::TOKEN_PROGRAM_META, // token_program
+        AccountMeta::new_readonly(accounts::ASSOCIATED_TOKEN_PROGRAM, false),
+        AccountMeta::new(coin_creator_vault_authority(sharing_config), false),
+        AccountMeta::new(
+            coin_creator_vault_ata(sharing_config, crate::constants::WSOL_TOKEN_ACCOUNT),
+            false,
+        ),
+    ]);
+    accounts.extend(current_shareholders.iter().map(|s| AccountMeta::new(*s, false)));
+    Some(Instruction::new_with_bytes(accounts::FEE_PROGRAM, &data, accounts))
+}
+
+/// Permanently give up admin rights over `mint`'s fee sharing config (shares become immutable).
+pub fn revoke_fee_sharing_authority_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+) -> Option<Instruction> {
+    let accounts = vec![
+        AccountMeta::new_readonly(*authority, true), // authority (signer)
+        global_constants::GLOBAL_ACCOUNT_META,       // global
+        AccountMeta::new_readonly(*mint, false),     // mint
+        AccountMeta::new(get_sharing_config_pda(mint)?, false), // sharing_config
+        AccountMeta::new_readonly(get_fee_program_event_authority_pda(), false), // event_authority
+        accounts::FEE_PROGRAM_META,                  // program
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::FEE_PROGRAM,
+        &REVOKE_FEE_SHARING_AUTHORITY_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Create `user`'s volume accumulator, rent paid by `payer`. Buys create it on demand otherwise.
+pub fn init_user_volume_accumulator_instruction(
+    payer: &Pubkey,
+    user: &Pubkey,
+) -> Option<Instruction> {
+    let accounts = vec![
+        AccountMeta::new(*payer, true),          // payer (signer, writable)
+        AccountMeta::new_readonly(*user, false), // user
+        AccountMeta::new(get_user_volume_accumulator_pda(user)?, false), // user_volume_accumulator (writable)
+        crate::constants::SYSTEM_PROGRAM_META,
+        accounts::EVENT_AUTHORITY_META,
+        accounts::PUMPFUN_META,
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::PUMPFUN,
+        &INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Sync `user`'s volume accumulator with the global one (permissionless).
+pub fn sync_user_volume_accumulator_instruction(user: &Pubkey) -> Option<Instruction> {
+    let accounts = vec![
+        AccountMeta::new_readonly(*user, false), // user
+        accounts::GLOBAL_VOLUME_ACCUMULATOR_META,
+        AccountMeta::new(get_user_volume_accumulator_pda(user)?, false), // user_volume_accumulator (writable)
+        accounts::EVENT_AUTHORITY_META,
+        accounts::PUMPFUN_META,
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::PUMPFUN,
+        &SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Close `user`'s volume accumulator and return its rent to `user` (must sign).
+pub fn close_user_volume_accumulator_instruction(user: &Pubkey) -> Option<Instruction> {
+    let accounts = vec![
+        AccountMeta::new(*user, true), // user (signer, writable)
+        AccountMeta::new(get_user_volume_accumulator_pda(user)?, false), // user_volume_accumulator (writable)
+        accounts::EVENT_AUTHORITY_META,
+        accounts::PUMPFUN_META,
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::PUMPFUN,
+        &CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Claim `user`'s incentive tokens (`GlobalVolumeAccumulator::mint`) into `user`'s ATA, which the
+/// program creates if needed; `payer` signs and pays rent.
+pub fn claim_token_incentives_instruction(
+    user: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Option<Instruction> {
+    let user_ata = crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+        user,
+        mint,
+        token_program,
+    );
+    let global_incentive_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            &accounts::GLOBAL_VOLUME_ACCUMULATOR,
+            mint,
+            token_program,
+        );
+    let accounts = vec![
+        AccountMeta::new_readonly(*user, false), // user
+        AccountMeta::new(user_ata, false),       // user_ata (writable)
+        accounts::GLOBAL_VOLUME_ACCUMULATOR_META,
+        AccountMeta::new(global_incentive_token_account, false), // global_incentive_token_account (writable)
+        AccountMeta::new(get_user_volume_accumulator_pda(user)?, false), // user_volume_accumulator (writable)
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        crate::constants::SYSTEM_PROGRAM_META,
+        AccountMeta::new_readonly(crate::constants::ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        accounts::EVENT_AUTHORITY_META,
+        accounts::PUMPFUN_META,
+        AccountMeta::new(*payer, true), // payer (signer, writable)
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::PUMPFUN,
+        &CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volume_accumulator_lifecycle_targets_one_pda() {
+        let payer = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let pda = get_user_volume_accumulator_pda(&user).unwrap();
+
+        let init = init_user_volume_accumulator_instruction(&payer, &user).unwrap();
+        assert_eq!(init.program_id, accounts::PUMPFUN);
+        assert_eq!(init.data, INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR.to_vec());
+        assert_eq!(init.accounts[0].pubkey, payer);
+        assert!(init.accounts[0].is_signer && init.accounts[0].is_writable);
+        assert_eq!(init.accounts[1].pubkey, user);
+        assert!(!init.accounts[1].is_signer);
+        assert_eq!(init.accounts[2].pubkey, pda);
+        assert!(init.accounts[2].is_writable);
+
+        let sync = sync_user_volume_accumulator_instruction(&user).unwrap();
+        assert_eq!(sync.data, SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR.to_vec());
+        assert!(sync.accounts.iter().all(|a| !a.is_signer));
+        assert_eq!(sync.accounts[1].pubkey, accounts::GLOBAL_VOLUME_ACCUMULATOR);
+        assert_eq!(sync.accounts[2].pubkey, pda);
+
+        let close = close_user_volume_accumulator_instruction(&user).unwrap();
+        assert_eq!(close.data, CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR.to_vec());
+        assert_eq!(close.accounts[0].pubkey, user);
+        assert!(close.accounts[0].is_signer && close.accounts[0].is_writable);
+        assert_eq!(close.accounts[1].pubkey, pda);
+    }
+}
@@ -1,19 +1,31 @@
 use crate::{
     constants::trade::trade::DEFAULT_SLIPPAGE,
     instruction::utils::pumpswap::{
-        accounts, fee_recipient_ata, get_mayhem_fee_recipient_random, get_pool_v2_pda,
+        accounts, coin_creator_vault_authority, fee_recipient_ata, get_lp_mint_pda,
+        get_mayhem_fee_recipient_random, get_pool_pda, get_pool_v2_pda,
         get_user_volume_accumulator_pda, get_user_volume_accumulator_quote_ata,
         get_user_volume_accumulator_wsol_ata, BUY_DISCRIMINATOR, BUY_EXACT_QUOTE_IN_DISCRIMINATOR,
-        SELL_DISCRIMINATOR,
+        CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR, CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR,
+        COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR, CREATE_POOL_DISCRIMINATOR, DEPOSIT_DISCRIMINATOR,
+        INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR, SELL_DISCRIMINATOR,
+        SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR, TRANSFER_CREATOR_FEES_TO_PUMP_DISCRIMINATOR,
+        WITHDRAW_DISCRIMINATOR,
     },
+    instruction::utils::pumpswap_types::Pool,
     trading::{
         common::wsol_manager,
         core::{
-            params::{PumpSwapParams, SwapParams},
+            params::{PumpSwapCreatePoolParams, PumpSwapParams, SwapParams},
             traits::InstructionBuilder,
         },
     },
-    utils::calc::pumpswap::{buy_quote_input_internal, sell_base_input_internal},
+    utils::calc::{
+        common::transfer_fee_excluded_amount,
+        pumpswap::{
+            buy_quote_input_internal, sell_base_input_internal, DepositLiquidityResult,
+            WithdrawLiquidityResult,
+        },
+    },
 };
 use anyhow::{anyhow, Result};
 use solana_sdk::{
@@ -97,8 +109,14 @@ impl InstructionBuilder for PumpSwapInstructionBuilder {
                 &creator,
             )
             .unwrap();
-            // min_quote_amount_out, base_amount_in
-            (result.min_quote, params.input_amount.unwrap_or(0))
+            // min_quote_amount_out (net of the token's transfer fee), base_amount_in
+            (
+                transfer_fee_excluded_amount(
+                    result.min_quote,
+                    protocol_params.quote_transfer_fee.as_ref(),
+                ),
+                params.input_amount.unwrap_or(0),
+            )
         };
 
         if params.fixed_output_amount.is_some() {
@@ -283,9 +301,19 @@ impl InstructionBuilder for PumpSwapInstructionBuilder {
             creator = params_coin_creator_vault_authority;
         }
 
+        // The pool receives the tokens net of their transfer fee.
+        let (token_transfer_fee, sol_transfer_fee) = if quote_is_wsol_or_usdc {
+            (&protocol_params.base_transfer_fee, &protocol_params.quote_transfer_fee)
+        } else {
+            (&protocol_params.quote_transfer_fee, &protocol_params.base_transfer_fee)
+        };
+
         let (token_amount, mut sol_amount) = if quote_is_wsol_or_usdc {
             let result = sell_base_input_internal(
-                params.input_amount.unwrap(),
+                transfer_fee_excluded_amount(
+                    params.input_amount.unwrap(),
+                    token_transfer_fee.as_ref(),
+                ),
                 params.slippage_basis_points.unwrap_or(DEFAULT_SLIPPAGE),
                 pool_base_token_reserves,
                 pool_quote_token_reserves,
@@ -293,7 +321,10 @@ impl InstructionBuilder for PumpSwapInstructionBuilder {
             )
             .unwrap();
             // base_amount_in, min_quote_amount_out
-            (params.input_amount.unwrap(), result.min_quote)
+            (
+                params.input_amount.unwrap(),
+                transfer_fee_excluded_amount(result.min_quote, sol_transfer_fee.as_ref()),
+            )
         } else {
             let result = buy_quote_input_internal(
                 params.input_amount.unwrap(),
@@ -474,3 +505,466 @@ pub fn claim_cashback_pumpswap_instruction(
         accounts,
     ))
 }
+
+/// Collect the coin creator's accumulated PumpSwap fees in `quote_mint` from the coin creator vault
+/// into `coin_creator`'s `quote_mint` ATA (which must exist).
+pub fn collect_coin_creator_fee_instruction(
+    coin_creator: &Pubkey,
+    quote_mint: Pubkey,
+    quote_token_program: Pubkey,
+) -> Instruction {
+    let vault_authority = coin_creator_vault_authority(*coin_creator);
+    let vault_ata = crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+        &vault_authority,
+        &quote_mint,
+        &quote_token_program,
+    );
+    let coin_creator_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            coin_creator,
+            &quote_mint,
+            &quote_token_program,
+        );
+    let accounts = vec![
+        AccountMeta::new_readonly(quote_mint, false),
+        AccountMeta::new_readonly(quote_token_program, false),
+        AccountMeta::new_readonly(*coin_creator, false), // coin_creator
+        AccountMeta::new_readonly(vault_authority, false), // coin_creator_vault_authority
+        AccountMeta::new(vault_ata, false),              // coin_creator_vault_ata (writable)
+        AccountMeta::new(coin_creator_token_account, false), // coin_creator_token_account (writable)
+        accounts::EVENT_AUTHORITY_META,
+        accounts::AMM_PROGRAM_META,
+    ];
+    Instruction::new_with_bytes(
+        accounts::AMM_PROGRAM,
+        &COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR,
+        accounts,
+    )
+}
+
+/// Move `coin_creator`'s WSOL fees from the PumpSwap coin creator vault into its PumpFun
+/// `creator_vault` (used by fee-shared coins, where `coin_creator` is the sharing config).
+/// Skipped on-chain while the vault holds less than a token account's rent.
+pub fn transfer_creator_fees_to_pump_instruction(coin_creator: &Pubkey) -> Option<Instruction> {
+    let vault_authority = coin_creator_vault_authority(*coin_creator);
+    let vault_ata = crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+        &vault_authority,
+        &crate::constants::WSOL_TOKEN_ACCOUNT,
+        &crate::constants::TOKEN_PROGRAM,
+    );
+    let pump_creator_vault =
+        crate::instruction::utils::pumpfun::get_creator_vault_pda(coin_creator)?;
+    let accounts = vec![
+        crate::constants::WSOL_TOKEN_ACCOUNT_META, // wsol_mint
+        crate::constants::TOKEN_PROGRAM_META,
+        crate::constants::SYSTEM_PROGRAM_META,
+        AccountMeta::new_readonly(accounts::ASSOCIATED_TOKEN_PROGRAM, false),
+        AccountMeta::new_readonly(*coin_creator, false), // coin_creator
+        AccountMeta::new(vault_authority, false),        // coin_creator_vault_authority (writable)
+        AccountMeta::new(vault_ata, false),              // coin_creator_vault_ata (writable)
+        AccountMeta::new(pump_creator_vault, false),     // pump_creator_vault (writable)
+        accounts::EVENT_AUTHORITY_META,
+        accounts::AMM_PROGRAM_META,
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::AMM_PROGRAM,
+        &TRANSFER_CREATOR_FEES_TO_PUMP_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Accounts shared by `deposit` and `withdraw` (IDL order), without event authority / program.
+fn liquidity_accounts(
+    user: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &Pool,
+    base_token_program: &Pubkey,
+    quote_token_program: &Pubkey,
+) -> Vec<AccountMeta> {
+    let user_base_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            user,
+            &pool.base_mint,
+            base_token_program,
+        );
+    let user_quote_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            user,
+            &pool.quote_mint,
+            quote_token_program,
+        );
+    let user_pool_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            user,
+            &pool.lp_mint,
+            &crate::constants::TOKEN_PROGRAM_2022,
+        );
+    vec![
+        AccountMeta::new(*pool_address, false),
+        accounts::GLOBAL_ACCOUNT_META,
+        AccountMeta::new_readonly(*user, true), // user (signer)
+        AccountMeta::new_readonly(pool.base_mint, false),
+        AccountMeta::new_readonly(pool.quote_mint, false),
+        AccountMeta::new(pool.lp_mint, false),
+        AccountMeta::new(user_base_token_account, false),
+        AccountMeta::new(user_quote_token_account, false),
+        AccountMeta::new(user_pool_token_account, false),
+        AccountMeta::new(pool.pool_base_token_account, false),
+        AccountMeta::new(pool.pool_quote_token_account, false),
+        crate::constants::TOKEN_PROGRAM_META,
+        crate::constants::TOKEN_PROGRAM_2022_META,
+        accounts::EVENT_AUTHORITY_META,
+        accounts::AMM_PROGRAM_META,
+    ]
+}
+
+/// Deposit liquidity: mint `amounts.lp_token` LP tokens for at most `max_base` / `max_quote`.
+/// The user's base, quote and LP (Token-2022) ATAs must exist.
+pub fn deposit_instruction(
+    user: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &Pool,
+    base_token_program: &Pubkey,
+    quote_token_program: &Pubkey,
+    amounts: &DepositLiquidityResult,
+) -> Instruction {
+    let mut data = [0u8; 32];
+    data[..8].copy_from_slice(&DEPOSIT_DISCRIMINATOR);
+    data[8..16].copy_from_slice(&amounts.lp_token.to_le_bytes());
+    data[16..24].copy_from_slice(&amounts.max_base.to_le_bytes());
+    data[24..32].copy_from_slice(&amounts.max_quote.to_le_bytes());
+    let accounts =
+        liquidity_accounts(user, pool_address, pool, base_token_program, quote_token_program);
+    Instruction::new_with_bytes(accounts::AMM_PROGRAM, &data, accounts)
+}
+
+/// Withdraw liquidity: burn `amounts.lp_token` LP tokens for at least `min_base` / `min_quote`.
+/// The user's base and quote ATAs must exist.
+pub fn withdraw_instruction(
+    user: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &Pool,
+    base_token_program: &Pubkey,
+    quote_token_program: &Pubkey,
+    amounts: &WithdrawLiquidityResult,
+) -> Instruction {
+    let mut data = [0u8; 32];
+    data[..8].copy_from_slice(&WITHDRAW_DISCRIMINATOR);
+    data[8..16].copy_from_slice(&amounts.lp_token.to_le_bytes());
+    data[16..24].copy_from_slice(&amounts.min_base.to_le_bytes());
+    data[24..32].copy_from_slice(&amounts.min_quote.to_le_bytes());
+    let accounts =
+        liquidity_accounts(user, pool_address, pool, base_token_program, quote_token_program);
+    Instruction::new_with_bytes(accounts::AMM_PROGRAM, &data, accounts)
+}
+
+/// Create a pool owned by `creator` and seed it with the initial liquidity. The program creates
+/// the pool vaults, the LP mint and the creator's LP ATA; `creator`'s base / quote ATAs must hold
+/// the deposit. Returns the instruction and the new pool address.
+pub fn create_pool_instruction(
+    creator: &Pubkey,
+    params: &PumpSwapCreatePoolParams,
+) -> Option<(Instruction, Pubkey)> {
+    let pool = get_pool_pda(params.index, creator, &params.base_mint, &params.quote_mint)?;
+    let lp_mint = get_lp_mint_pda(&pool)?;
+    let ata = crate::common::fast_fn::get_associated_token_address_with_program_id_fast;
+
+    let mut data = Vec::with_capacity(8 + 2 + 8 + 8 + 32 + 1 + 1);
+    data.extend_from_slice(&CREATE_POOL_DISCRIMINATOR);
+    data.extend_from_slice(&params.index.to_le_bytes());
+    data.extend_from_slice(&params.base_amount_in.to_le_bytes());
+    data.extend_from_slice(&params.quote_amount_in.to_le_bytes());
+    data.extend_from_slice(params.coin_creator.as_ref());
+    data.push(params.is_mayhem_mode as u8);
+    data.push(params.is_cashback_coin as u8); // OptionBool
+
+    let accounts = vec![
+        AccountMeta::new(pool, false),
+        accounts::GLOBAL_ACCOUNT_META,
+        AccountMeta::new(*creator, true), // creator (signer)
+        AccountMeta::new_readonly(params.base_mint, false),
+        AccountMeta::new_readonly(params.quote_mint, false),
+        AccountMeta::new(lp_mint, false),
+        AccountMeta::new(ata(creator, &params.base_mint, &params.base_token_program), false),
+        AccountMeta::new(ata(creator, &params.quote_mint, &params.quote_token_program), false),
+        AccountMeta::new(ata(creator, &lp_mint, &crate::constants::TOKEN_PROGRAM_2022), false),
+        AccountMeta::new(ata(&pool, &params.base_mint, &params.base_token_program), false),
+        AccountMeta::new(ata(&pool, &params.quote_mint, &params.quote_token_program), false),
+        crate::constants::SYSTEM_PROGRAM_META,
+        crate::constants::TOKEN_PROGRAM_2022_META,
+        AccountMeta::new_readonly(params.base_token_program, false),
+        AccountMeta::new_readonly(params.quote_token_program, false),
+        accounts::ASSOCIATED_TOKEN_PROGRAM_META,
+        accounts::EVENT_AUTHORITY_META,
+        accounts::AMM_PROGRAM_META,
+    ];
+    Some((Instruction::new_with_bytes(accounts::AMM_PROGRAM, &data, accounts), pool))
+}
+
+/// Create `user`'s volume accumulator, rent paid by `payer`. Buys create it on demand otherwise.
+pub fn init_user_volume_accumulator_instruction(
+    payer: &Pubkey,
+    user: &Pubkey,
+) -> Option<Instruction> {
+    let accounts = vec![
+        AccountMeta::new(*payer, true),          // payer (signer, writable)
+        AccountMeta::new_readonly(*user, false), // user
+        AccountMeta::new(get_user_volume_accumulator_pda(user)?, false), // user_volume_accumulator (writable)
+        crate::constants::SYSTEM_PROGRAM_META,
+        accounts::EVENT_AUTHORITY_META,
+        accounts::AMM_PROGRAM_META,
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::AMM_PROGRAM,
+        &INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Sync `user`'s volume accumulator with the global one (permissionless).
+pub fn sync_user_volume_accumulator_instruction(user: &Pubkey) -> Option<Instruction> {
+    let accounts = vec![
+        AccountMeta::new_readonly(*user, false), // user
+        accounts::GLOBAL_VOLUME_ACCUMULATOR_META,
+        AccountMeta::new(get_user_volume_accumulator_pda(user)?, false), // user_volume_accumulator (writable)
+        accounts::EVENT_AUTHORITY_META,
+        accounts::AMM_PROGRAM_META,
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::AMM_PROGRAM,
+        &SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Close `user`'s volume accumulator and return its rent to `user` (must sign).
+pub fn close_user_volume_accumulator_instruction(user: &Pubkey) -> Option<Instruction> {
+    let accounts = vec![
+        AccountMeta::new(*user, true), // user (signer, writable)
+        AccountMeta::new(get_user_volume_accumulator_pda(user)?, false), // user_volume_accumulator (writable)
+        accounts::EVENT_AUTHORITY_META,
+        accounts::AMM_PROGRAM_META,
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::AMM_PROGRAM,
+        &CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+/// Claim `user`'s incentive tokens (`GlobalVolumeAccumulator::mint`) into `user`'s ATA, which the
+/// program creates if needed; `payer` signs and pays rent.
+pub fn claim_token_incentives_instruction(
+    user: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Option<Instruction> {
+    let user_ata = crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+        user,
+        mint,
+        token_program,
+    );
+    let global_incentive_token_account =
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            &accounts::GLOBAL_VOLUME_ACCUMULATOR,
+            mint,
+            token_program,
+        );
+    let accounts = vec![
+        AccountMeta::new_readonly(*user, false), // user
+        AccountMeta::new(user_ata, false),       // user_ata (writable)
+        accounts::GLOBAL_VOLUME_ACCUMULATOR_META,
+        AccountMeta::new(global_incentive_token_account, false), // global_incentive_token_account (writable)
+        AccountMeta::new(get_user_volume_accumulator_pda(user)?, false), // user_volume_accumulator (writable)
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        crate::constants::SYSTEM_PROGRAM_META,
+        accounts::ASSOCIATED_TOKEN_PROGRAM_META,
+        accounts::EVENT_AUTHORITY_META,
+        accounts::AMM_PROGRAM_META,
+        AccountMeta::new(*payer, true), // payer (signer, writable)
+    ];
+    Some(Instruction::new_with_bytes(
+        accounts::AMM_PROGRAM,
+        &CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR,
+        accounts,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::spl_token_2022::TransferFee;
+    use crate::constants::{TOKEN_PROGRAM, TOKEN_PROGRAM_2022, WSOL_TOKEN_ACCOUNT};
+    use crate::instruction::utils::pumpswap::coin_creator_vault_ata;
+    use crate::swqos::TradeType;
+    use crate::trading::core::params::DexParamEnum;
+
+    #[test]
+    fn collect_coin_creator_fee_moves_vault_balance_to_creator_ata() {
+        let creator = Pubkey::new_unique();
+        let ix = collect_coin_creator_fee_instruction(&creator, WSOL_TOKEN_ACCOUNT, TOKEN_PROGRAM);
+
+        assert_eq!(ix.program_id, accounts::AMM_PROGRAM);
+        assert_eq!(ix.data, COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR.to_vec());
+        assert_eq!(ix.accounts.len(), 8);
+        assert_eq!(ix.accounts[0].pubkey, WSOL_TOKEN_ACCOUNT);
+        assert_eq!(ix.accounts[1].pubkey, TOKEN_PROGRAM);
+        assert_eq!(ix.accounts[2].pubkey, creator);
+        assert!(!ix.accounts[2].is_signer);
+        assert_eq!(ix.accounts[3].pubkey, coin_creator_vault_authority(creator));
+        assert_eq!(ix.accounts[4].pubkey, coin_creator_vault_ata(creator, WSOL_TOKEN_ACCOUNT));
+        assert!(ix.accounts[4].is_writable);
+        assert_eq!(
+            ix.accounts[5].pubkey,
+            crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+                &creator,
+                &WSOL_TOKEN_ACCOUNT,
+                &TOKEN_PROGRAM,
+            )
+        );
+        assert!(ix.accounts[5].is_writable);
+    }
+
+    #[test]
+    fn collect_coin_creator_fee_derives_atas_with_the_quote_token_program() {
+        let creator = Pubkey::new_unique();
+        let quote_mint = Pubkey::new_unique();
+        let classic = collect_coin_creator_fee_instruction(&creator, quote_mint, TOKEN_PROGRAM);
+        let token_2022 =
+            collect_coin_creator_fee_instruction(&creator, quote_mint, TOKEN_PROGRAM_2022);
+
+        assert_eq!(token_2022.accounts[1].pubkey, TOKEN_PROGRAM_2022);
+        assert_ne!(classic.accounts[4].pubkey, token_2022.accounts[4].pubkey);
+        assert_ne!(classic.accounts[5].pubkey, token_2022.accounts[5].pubkey);
+    }
+
+    #[test]
+    fn transfer_creator_fees_to_pump_targets_the_pumpfun_creator_vault() {
+        let creator = Pubkey::new_unique();
+        let ix = transfer_creator_fees_to_pump_instruction(&creator).unwrap();
+
+        assert_eq!(ix.program_id, accounts::AMM_PROGRAM);
+        assert_eq!(ix.data, TRANSFER_CREATOR_FEES_TO_PUMP_DISCRIMINATOR.to_vec());
+        assert_eq!(ix.accounts[0].pubkey, WSOL_TOKEN_ACCOUNT);
+        assert_eq!(ix.accounts[4].pubkey, creator);
+        assert_eq!(ix.accounts[5].pubkey, coin_creator_vault_authority(creator));
+        assert!(ix.accounts[5].is_writable);
+        assert_eq!(ix.accounts[6].pubkey, coin_creator_vault_ata(creator, WSOL_TOKEN_ACCOUNT));
+        assert_eq!(
+            ix.accounts[7].pubkey,
+            crate::instruction::utils::pumpfun::get_creator_vault_pda(&creator).unwrap()
+        );
+        assert!(ix.accounts[7].is_writable);
+    }
+
+    #[test]
+    fn volume_accumulator_lifecycle_targets_one_pda() {
+        let payer = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let pda = get_user_volume_accumulator_pda(&user).unwrap();
+        assert_ne!(
+            pda,
+            crate::instruction::utils::pumpfun::get_user_volume_accumulator_pda(&user).unwrap()
+        );
+
+        let init = init_user_volume_accumulator_instruction(&payer, &user).unwrap();
+        assert_eq!(init.program_id, accounts::AMM_PROGRAM);
+        assert_eq!(init.data, INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR.to_vec());
+        assert_eq!(init.accounts[0].pubkey, payer);
+        assert!(init.accounts[0].is_signer);
+        assert_eq!(init.accounts[1].pubkey, user);
+        assert!(!init.accounts[1].is_signer);
+        assert_eq!(init.accounts[2].pubkey, pda);
+
+        let sync = sync_user_volume_accumulator_instruction(&user).unwrap();
+        assert_eq!(sync.data, SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR.to_vec());
+        assert!(sync.accounts.iter().all(|a| !a.is_signer));
+        assert_eq!(sync.accounts[2].pubkey, pda);
+
+        let close = close_user_volume_accumulator_instruction(&user).unwrap();
+        assert_eq!(close.data, CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR.to_vec());
+        assert_eq!(close.accounts[0].pubkey, user);
+        assert!(close.accounts[0].is_signer);
+        assert_eq!(close.accounts[1].pubkey, pda);
+    }
+
+    /// WSOL pool of a Token-2022 `base_mint` / `quote_mint` with the given transfer fees.
+    fn fee_pool(
+        base_mint: Pubkey,
+        quote_mint: Pubkey,
+        base_transfer_fee: Option<TransferFee>,
+        quote_transfer_fee: Option<TransferFee>,
+    ) -> PumpSwapParams {
+        let program =
+            |mint| if mint == WSOL_TOKEN_ACCOUNT { TOKEN_PROGRAM } else { TOKEN_PROGRAM_2022 };
+        PumpSwapParams::new(
+            Pubkey::new_unique(),
+            base_mint,
+            quote_mint,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1_000_000_000_000,
+            100_000_000_000,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            program(base_mint),
+            program(quote_mint),
+            Pubkey::new_unique(),
+            false,
+        )
+        .with_transfer_fees(base_transfer_fee, quote_transfer_fee)
+    }
+
+    /// `(first, second)` amount of the PumpSwap swap instruction.
+    fn swap_amounts(instructions: &[Instruction]) -> (u64, u64) {
+        let ix = instructions.iter().find(|ix| ix.program_id == accounts::AMM_PROGRAM).unwrap();
+        (
+            u64::from_le_bytes(ix.data[8..16].try_into().unwrap()),
+            u64::from_le_bytes(ix.data[16..24].try_into().unwrap()),
+        )
+    }
+
+    #[tokio::test]
+    async fn min_quote_is_net_of_transfer_fees() {
+        crate::common::seed::set_default_rents();
+        let fee = TransferFee { epoch: 0, maximum_fee: u64::MAX, transfer_fee_basis_points: 100 };
+        let token = Pubkey::new_unique();
+
+        // Selling a fee token into a WSOL-quoted pool: the pool receives the input net of fee.
+        let pool = fee_pool(token, WSOL_TOKEN_ACCOUNT, Some(fee), None);
+        let creator = pool.coin_creator_vault_authority;
+        let params = SwapParams::for_test(
+            TradeType::Sell,
+            token,
+            WSOL_TOKEN_ACCOUNT,
+            DexParamEnum::PumpSwap(pool.clone()),
+        );
+        let instructions =
+            PumpSwapInstructionBuilder.build_sell_instructions(&params).await.unwrap();
+        let expected =
+            sell_base_input_internal(990_000, 100, 1_000_000_000_000, 100_000_000_000, &creator)
+                .unwrap();
+        assert_eq!(swap_amounts(&instructions), (1_000_000, expected.min_quote));
+
+        // Buying a fee token that is the pool's quote: the minimum is what the payer receives.
+        let pool = fee_pool(WSOL_TOKEN_ACCOUNT, token, None, Some(fee));
+        let creator = pool.coin_creator_vault_authority;
+        let params = SwapParams::for_test(
+            TradeType::Buy,
+            WSOL_TOKEN_ACCOUNT,
+            token,
+            DexParamEnum::PumpSwap(pool),
+        );
+        let instructions =
+            PumpSwapInstructionBuilder.build_buy_instructions(&params).await.unwrap();
+        let expected =
+            sell_base_input_internal(1_000_000, 100, 1_000_000_000_000, 100_000_000_000, &creator)
+                .unwrap();
+        assert_eq!(
+            swap_amounts(&instructions),
+            (1_000_000, fee.calculate_post_fee_amount(expected.min_quote))
+        );
+    }
+}
@@ -1,15 +1,25 @@
 use crate::{
     constants::trade::trade::DEFAULT_SLIPPAGE,
-    instruction::utils::raydium_amm_v4::{accounts, SWAP_BASE_IN_DISCRIMINATOR},
+    instruction::utils::{
+        raydium_amm_v4::{
+            accounts, MarketAccounts, DEPOSIT_DISCRIMINATOR, SWAP_BASE_IN_DISCRIMINATOR,
+            WITHDRAW_DISCRIMINATOR,
+        },
+        raydium_amm_v4_types::AmmInfo,
+    },
     trading::core::{
         params::{RaydiumAmmV4Params, SwapParams},
         traits::InstructionBuilder,
     },
-    utils::calc::raydium_amm_v4::compute_swap_amount,
+    utils::calc::{
+        common::{DepositLiquidityResult, WithdrawLiquidityResult},
+        raydium_amm_v4::compute_swap_amount,
+    },
 };
 use anyhow::{anyhow, Result};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
     signer::Signer,
 };
 
@@ -257,3 +267,92 @@ impl InstructionBuilder for RaydiumAmmV4InstructionBuilder {
         Ok(instructions)
     }
 }
+
+/// Deposit into an AMM v4 pool with the coin side fixed (`base_side = 0`): the program takes
+/// exactly `amounts.base` coin and the matching pc amount, failing if that exceeds `max_quote`.
+/// The owner's coin, pc and LP ATAs must exist.
+pub fn deposit_instruction(
+    owner: &Pubkey,
+    amm: &Pubkey,
+    amm_info: &AmmInfo,
+    market: &MarketAccounts,
+    amounts: &DepositLiquidityResult,
+) -> Instruction {
+    let (user_coin, user_pc, user_lp) = user_liquidity_accounts(owner, amm_info);
+    let accounts: [AccountMeta; 14] = [
+        crate::constants::TOKEN_PROGRAM_META, // Token Program (readonly)
+        AccountMeta::new(*amm, false),        // Amm
+        accounts::AUTHORITY_META,             // Amm Authority (readonly)
+        AccountMeta::new_readonly(amm_info.open_orders, false), // Amm Open Orders (readonly)
+        AccountMeta::new(amm_info.target_orders, false), // Amm Target Orders
+        AccountMeta::new(amm_info.lp_mint, false), // LP Mint
+        AccountMeta::new(amm_info.token_coin, false), // Pool Coin Token Account
+        AccountMeta::new(amm_info.token_pc, false), // Pool Pc Token Account
+        AccountMeta::new_readonly(market.market, false), // Serum Market (readonly)
+        AccountMeta::new(user_coin, false),   // User Coin Token Account
+        AccountMeta::new(user_pc, false),     // User Pc Token Account
+        AccountMeta::new(user_lp, false),     // User LP Token Account
+        AccountMeta::new_readonly(*owner, true), // User Owner (signer)
+        AccountMeta::new_readonly(market.event_queue, false), // Serum Event Queue (readonly)
+    ];
+    let mut data = [0u8; 25];
+    data[..1].copy_from_slice(DEPOSIT_DISCRIMINATOR);
+    data[1..9].copy_from_slice(&amounts.base.to_le_bytes());
+    data[9..17].copy_from_slice(&amounts.max_quote.to_le_bytes());
+    // base_side = 0: coin amount is fixed
+    Instruction::new_with_bytes(accounts::RAYDIUM_AMM_V4, &data, accounts.to_vec())
+}
+
+/// Withdraw from an AMM v4 pool: burn `amounts.lp_token` LP tokens for at least `min_base` coin
+/// and `min_quote` pc. The owner's coin and pc ATAs must exist.
+pub fn withdraw_instruction(
+    owner: &Pubkey,
+    amm: &Pubkey,
+    amm_info: &AmmInfo,
+    market: &MarketAccounts,
+    amounts: &WithdrawLiquidityResult,
+) -> Instruction {
+    let (user_coin, user_pc, user_lp) = user_liquidity_accounts(owner, amm_info);
+    let accounts: [AccountMeta; 22] = [
+        crate::constants::TOKEN_PROGRAM_META, // Token Program (readonly)
+        AccountMeta::new(*amm, false),        // Amm
+        accounts::AUTHORITY_META,             // Amm Authority (readonly)
+        AccountMeta::new(amm_info.open_orders, false), // Amm Open Orders
+        AccountMeta::new(amm_info.target_orders, false), // Amm Target Orders
+        AccountMeta::new(amm_info.lp_mint, false), // LP Mint
+        AccountMeta::new(amm_info.token_coin, false), // Pool Coin Token Account
+        AccountMeta::new(amm_info.token_pc, false), // Pool Pc Token Account
+        AccountMeta::new(amm_info.withdraw_queue, false), // Pool Withdraw Queue
+        AccountMeta::new(amm_info.token_temp_lp, false), // Pool Temp LP Token Account
+        AccountMeta::new_readonly(market.market_program, false), // Serum Program (readonly)
+        AccountMeta::new(market.market, false), // Serum Market
+        AccountMeta::new(market.coin_vault, false), // Serum Coin Vault
+        AccountMeta::new(market.pc_vault, false), // Serum Pc Vault
+        AccountMeta::new_readonly(market.vault_signer, false), // Serum Vault Signer (readonly)
+        AccountMeta::new(user_lp, false),     // User LP Token Account
+        AccountMeta::new(user_coin, false),   // User Coin Token Account
+        AccountMeta::new(user_pc, false),     // User Pc Token Account
+        AccountMeta::new_readonly(*owner, true), // User Owner (signer)
+        AccountMeta::new(market.event_queue, false), // Serum Event Queue
+        AccountMeta::new(market.bids, false), // Serum Bids
+        AccountMeta::new(market.asks, false), // Serum Asks
+    ];
+    let mut data = [0u8; 25];
+    data[..1].copy_from_slice(WITHDRAW_DISCRIMINATOR);
+    data[1..9].copy_from_slice(&amounts.lp_token.to_le_bytes());
+    data[9..17].copy_from_slice(&amounts.min_base.to_le_bytes());
+    data[17..25].copy_from_slice(&amounts.min_quote.to_le_bytes());
+    Instruction::new_with_bytes(accounts::RAYDIUM_AMM_V4, &data, accounts.to_vec())
+}
+
+/// The owner's coin, pc and LP ATAs (AMM v4 pools are SPL Token only).
+fn user_liquidity_accounts(owner: &Pubkey, amm_info: &AmmInfo) -> (Pubkey, Pubkey, Pubkey) {
+    let ata = |mint: &Pubkey| {
+        crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+            owner,
+            mint,
+            &crate::constants::TOKEN_PROGRAM,
+        )
+    };
+    (ata(&amm_info.coin_mint), ata(&amm_info.pc_mint), ata(&amm_info.lp_mint))
+}
@@ -1,15 +1,21 @@
 use crate::{
     common::fast_fn::get_associated_token_address_with_program_id_fast_use_seed,
     constants::trade::trade::DEFAULT_SLIPPAGE,
-    instruction::utils::raydium_cpmm::{
-        accounts, get_observation_state_pda, get_pool_pda, get_vault_account,
-        SWAP_BASE_IN_DISCRIMINATOR,
+    instruction::utils::{
+        raydium_cpmm::{
+            accounts, get_observation_state_pda, get_pool_pda, get_vault_account,
+            DEPOSIT_DISCRIMINATOR, SWAP_BASE_IN_DISCRIMINATOR, WITHDRAW_DISCRIMINATOR,
+        },
+        raydium_cpmm_types::PoolState,
     },
     trading::core::{
         params::{RaydiumCpmmParams, SwapParams},
         traits::InstructionBuilder,
     },
-    utils::calc::raydium_cpmm::compute_swap_amount,
+    utils::calc::{
+        common::{DepositLiquidityResult, WithdrawLiquidityResult},
+        raydium_cpmm::compute_swap_amount_with_transfer_fees,
+    },
 };
 use anyhow::{anyhow, Result};
 use solana_sdk::{
@@ -69,13 +75,21 @@ impl InstructionBuilder for RaydiumCpmmInstructionBuilder {
             protocol_params.base_token_program
         };
 
+        let (input_transfer_fee, output_transfer_fee) = if is_base_in {
+            (&protocol_params.base_transfer_fee, &protocol_params.quote_transfer_fee)
+        } else {
+            (&protocol_params.quote_transfer_fee, &protocol_params.base_transfer_fee)
+        };
+
         let amount_in: u64 = params.input_amount.unwrap_or(0);
-        let result = compute_swap_amount(
+        let result = compute_swap_amount_with_transfer_fees(
             protocol_params.base_reserve,
             protocol_params.quote_reserve,
             is_base_in,
             amount_in,
             params.slippage_basis_points.unwrap_or(DEFAULT_SLIPPAGE),
+            input_transfer_fee.as_ref(),
+            output_transfer_fee.as_ref(),
         );
         let minimum_amount_out = match params.fixed_output_amount {
             Some(fixed) => fixed,
@@ -225,15 +239,23 @@ impl InstructionBuilder for RaydiumCpmmInstructionBuilder {
             protocol_params.quote_token_program
         };
 
+        let (input_transfer_fee, output_transfer_fee) = if is_quote_out {
+            (&protocol_params.base_transfer_fee, &protocol_params.quote_transfer_fee)
+        } else {
+            (&protocol_params.quote_transfer_fee, &protocol_params.base_transfer_fee)
+        };
+
         let minimum_amount_out: u64 = match params.fixed_output_amount {
             Some(fixed) => fixed,
             None => {
-                compute_swap_amount(
+                compute_swap_amount_with_transfer_fees(
                     protocol_params.base_reserve,
                     protocol_params.quote_reserve,
                     is_quote_out,
                     params.input_amount.unwrap_or(0),
                     params.slippage_basis_points.unwrap_or(DEFAULT_SLIPPAGE),
+                    input_transfer_fee.as_ref(),
+                    output_transfer_fee.as_ref(),
                 )
                 .min_amount_out
             }
@@ -332,3 +354,75 @@ impl InstructionBuilder for RaydiumCpmmInstructionBuilder {
         Ok(instructions)
     }
 }
+
+/// Accounts shared by `deposit` and `withdraw` (withdraw appends the memo program).
+fn liquidity_accounts(owner: &Pubkey, pool_address: &Pubkey, pool: &PoolState) -> Vec<AccountMeta> {
+    let owner_lp_token = crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+        owner,
+        &pool.lp_mint,
+        &crate::constants::TOKEN_PROGRAM,
+    );
+    let token_0_account = crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+        owner,
+        &pool.token0_mint,
+        &pool.token0_program,
+    );
+    let token_1_account = crate::common::fast_fn::get_associated_token_address_with_program_id_fast(
+        owner,
+        &pool.token1_mint,
+        &pool.token1_program,
+    );
+    vec![
+        AccountMeta::new_readonly(*owner, true),    // Owner (signer)
+        accounts::AUTHORITY_META,                   // Authority (readonly)
+        AccountMeta::new(*pool_address, false),     // Pool State
+        AccountMeta::new(owner_lp_token, false),    // Owner LP Token Account
+        AccountMeta::new(token_0_account, false),   // Owner Token 0 Account
+        AccountMeta::new(token_1_account, false),   // Owner Token 1 Account
+        AccountMeta::new(pool.token0_vault, false), // Token 0 Vault
+        AccountMeta::new(pool.token1_vault, false), // Token 1 Vault
+        crate::constants::TOKEN_PROGRAM_META,       // Token Program (readonly)
+        crate::constants::TOKEN_PROGRAM_2022_META,  // Token Program 2022 (readonly)
+        AccountMeta::new_readonly(pool.token0_mint, false), // Vault 0 Mint (readonly)
+        AccountMeta::new_readonly(pool.token1_mint, false), // Vault 1 Mint (readonly)
+        AccountMeta::new(pool.lp_mint, false),      // LP Mint
+    ]
+}
+
+/// Deposit into a CPMM pool: mint `amounts.lp_token` LP tokens for at most `max_base` token0 and
+/// `max_quote` token1. The owner's LP, token0 and token1 ATAs must exist.
+pub fn deposit_instruction(
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &PoolState,
+    amounts: &DepositLiquidityResult,
+) -> Instruction {
+    let mut data = [0u8; 32];
+    data[..8].copy_from_slice(DEPOSIT_DISCRIMINATOR);
+    data[8..16].copy_from_slice(&amounts.lp_token.to_le_bytes());
+    data[16..24].copy_from_slice(&amounts.max_base.to_le_bytes());
+    data[24..32].copy_from_slice(&amounts.max_quote.to_le_bytes());
+    Instruction::new_with_bytes(
+        accounts::RAYDIUM_CPMM,
+        &data,
+        liquidity_accounts(owner, pool_address, pool),
+    )
+}
+
+/// Withdraw from a CPMM pool: burn `amounts.lp_token` LP tokens for at least `min_base` token0 and
+/// `min_quote` token1. The owner's token0 and token1 ATAs must exist.
+pub fn withdraw_instruction(
+    owner: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &PoolState,
+    amounts: &WithdrawLiquidityResult,
+) -> Instruction {
+    let mut data = [0u8; 32];
+    data[..8].copy_from_slice(WITHDRAW_DISCRIMINATOR);
+    data[8..16].copy_from_slice(&amounts.lp_token.to_le_bytes());
+    data[16..24].copy_from_slice(&amounts.min_base.to_le_bytes());
+    data[24..32].copy_from_slice(&amounts.min_quote.to_le_bytes());
+    let mut accounts = liquidity_accounts(owner, pool_address, pool);
+    accounts.push(AccountMeta::new_readonly(accounts::MEMO_PROGRAM, false)); // Memo Program
+    Instruction::new_with_bytes(accounts::RAYDIUM_CPMM, &data, accounts)
+}
@@ -9,6 +9,26 @@ use solana_sdk::pubkey::Pubkey;
 pub mod seeds {
     pub const POOL_SEED: &[u8] = b"pool";
     pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
+    pub const METADATA_SEED: &[u8] = b"metadata";
+    pub const POOL_VESTING_SEED: &[u8] = b"pool_vesting";
+    pub const CREATOR_FEE_VAULT_AUTH_SEED: &[u8] = b"creator_fee_vault_auth_seed";
+    pub const PLATFORM_FEE_VAULT_AUTH_SEED: &[u8] = b"platform_fee_vault_auth_seed";
+}
+
+/// `PoolState::status` values
+pub mod pool_status {
+    /// Bonding curve is trading
+    pub const FUND: u8 = 0;
+    /// Curve completed, waiting for migration
+    pub const MIGRATE: u8 = 1;
+    /// Migrated; trading happens on the Raydium pool given by `migrate_type`
+    pub const TRADE: u8 = 2;
+}
+
+/// `PoolState::migrate_type` values
+pub mod migrate_type {
+    pub const AMM_V4: u8 = 0;
+    pub const CPMM: u8 = 1;
 }
 
 /// Constants related to program accounts and authorities
@@ -20,6 +40,8 @@ pub mod accounts {
     pub const USD1_GLOBAL_CONFIG: Pubkey = pubkey!("EPiZbnrThjyLnoQ6QQzkxeFqyL5uyg9RzNHHAudUPxBz");
     pub const EVENT_AUTHORITY: Pubkey = pubkey!("2DPAtwB8L12vrMRExbLuyGnC7n2J5LNoZQSejeQGpwkr");
     pub const BONK: Pubkey = pubkey!("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj");
+    /// Metaplex Token Metadata program (metadata account of launched mints)
+    pub const METADATA_PROGRAM: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
 
     pub const PLATFORM_FEE_RATE: u128 = 100; // 1%
     pub const PROTOCOL_FEE_RATE: u128 = 25; // 0.25%
@@ -58,6 +80,12 @@ pub mod accounts {
 
 pub const BUY_EXECT_IN_DISCRIMINATOR: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236];
 pub const SELL_EXECT_IN_DISCRIMINATOR: [u8; 8] = [149, 39, 222, 155, 211, 124, 152, 26];
+pub const INITIALIZE_V2_DISCRIMINATOR: [u8; 8] = [67, 153, 175, 39, 218, 16, 38, 32];
+pub const CREATE_VESTING_ACCOUNT_DISCRIMINATOR: [u8; 8] = [129, 178, 2, 13, 217, 172, 230, 218];
+pub const CLAIM_VESTED_TOKEN_DISCRIMINATOR: [u8; 8] = [49, 33, 104, 30, 189, 157, 79, 35];
+pub const CLAIM_CREATOR_FEE_DISCRIMINATOR: [u8; 8] = [26, 97, 138, 203, 132, 171, 141, 252];
+pub const CLAIM_PLATFORM_FEE_FROM_VAULT_DISCRIMINATOR: [u8; 8] =
+    [117, 241, 198, 168, 248, 218, 80, 29];
 
 pub async fn fetch_pool_state(
     rpc: &SolanaRpcClient,
@@ -179,17 +207,59 @@ pub fn get_vault_pda(pool_state: &Pubkey, mint: &Pubkey) -> Option<Pubkey> {
     )
 }
 
-pub fn get_platform_associated_account(platform_config: &Pubkey) -> Option<Pubkey> {
-    let seeds: &[&[u8]; 2] =
-        &[platform_config.as_ref(), crate::constants::WSOL_TOKEN_ACCOUNT.as_ref()];
+/// Metaplex metadata account of `mint`.
+pub fn get_metadata_pda(mint: &Pubkey) -> Option<Pubkey> {
+    let seeds: &[&[u8]; 3] =
+        &[seeds::METADATA_SEED, accounts::METADATA_PROGRAM.as_ref(), mint.as_ref()];
+    let pda: Option<(Pubkey, u8)> =
+        Pubkey::try_find_program_address(seeds, &accounts::METADATA_PROGRAM);
+    pda.map(|pubkey| pubkey.0)
+}
+
+/// Platform fee vault for `quote_mint` (WSOL or USD1 pools).
+pub fn get_platform_fee_vault(platform_config: &Pubkey, quote_mint: &Pubkey) -> Option<Pubkey> {
+    let seeds: &[&[u8]; 2] = &[platform_config.as_ref(), quote_mint.as_ref()];
     let program_id: &Pubkey = &accounts::BONK;
     let pda: Option<(Pubkey, u8)> = Pubkey::try_find_program_address(seeds, program_id);
     pda.map(|pubkey| pubkey.0)
 }
 
-pub fn get_creator_associated_account(creator: &Pubkey) -> Option<Pubkey> {
-    let seeds: &[&[u8]; 2] = &[creator.as_ref(), crate::constants::WSOL_TOKEN_ACCOUNT.as_ref()];
+/// Creator fee vault for `quote_mint` (WSOL or USD1 pools).
+pub fn get_creator_fee_vault(creator: &Pubkey, quote_mint: &Pubkey) -> Option<Pubkey> {
+    let seeds: &[&[u8]; 2] = &[creator.as_ref(), quote_mint.as_ref()];
+    let program_id: &Pubkey = &accounts::BONK;
+    let pda: Option<(Pubkey, u8)> = Pubkey::try_find_program_address(seeds, program_id);
+    pda.map(|pubkey| pubkey.0)
+}
+
+/// Vesting record of `beneficiary` in `pool_state`.
+pub fn get_vesting_record_pda(pool_state: &Pubkey, beneficiary: &Pubkey) -> Option<Pubkey> {
+    let seeds: &[&[u8]; 3] = &[seeds::POOL_VESTING_SEED, pool_state.as_ref(), beneficiary.as_ref()];
+    let program_id: &Pubkey = &accounts::BONK;
+    let pda: Option<(Pubkey, u8)> = Pubkey::try_find_program_address(seeds, program_id);
+    pda.map(|pubkey| pubkey.0)
+}
+
+/// Authority of all creator fee vaults.
+pub fn get_creator_fee_vault_authority() -> Option<Pubkey> {
+    let seeds: &[&[u8]; 1] = &[seeds::CREATOR_FEE_VAULT_AUTH_SEED];
     let program_id: &Pubkey = &accounts::BONK;
     let pda: Option<(Pubkey, u8)> = Pubkey::try_find_program_address(seeds, program_id);
     pda.map(|pubkey| pubkey.0)
 }
+
+/// Authority of all platform fee vaults.
+pub fn get_platform_fee_vault_authority() -> Option<Pubkey> {
+    let seeds: &[&[u8]; 1] = &[seeds::PLATFORM_FEE_VAULT_AUTH_SEED];
+    let program_id: &Pubkey = &accounts::BONK;
+    let pda: Option<(Pubkey, u8)> = Pubkey::try_find_program_address(seeds, program_id);
+    pda.map(|pubkey| pubkey.0)
+}
+
+pub fn get_platform_associated_account(platform_config: &Pubkey) -> Option<Pubkey> {
+    get_platform_fee_vault(platform_config, &crate::constants::WSOL_TOKEN_ACCOUNT)
+}
+
+pub fn get_creator_associated_account(creator: &Pubkey) -> Option<Pubkey> {
+    get_creator_fee_vault(creator, &crate::constants::WSOL_TOKEN_ACCOUNT)
+}
@@ -36,6 +36,12 @@ pub mod accounts {
 
 pub const SWAP_DISCRIMINATOR: &[u8] = &[248, 198, 158, 145, 225, 117, 135, 200];
 
+/// Pool account size including the 8-byte discriminator.
+pub(crate) const POOL_ACCOUNT_DATA_LEN: u64 = 8 + 1104;
+/// Mint offsets in the pool account: 8(discriminator) + PoolFeesStruct(160), then + 32.
+pub(crate) const TOKEN_A_MINT_OFFSET: usize = 168;
+pub(crate) const TOKEN_B_MINT_OFFSET: usize = 200;
+
 pub async fn fetch_pool(
     rpc: &SolanaRpcClient,
     pool_address: &Pubkey,
@@ -1,5 +1,6 @@
 use crate::common::{bonding_curve::BondingCurveAccount, SolanaRpcClient};
 use anyhow::anyhow;
+use borsh::{BorshDeserialize, BorshSerialize};
 use rand::seq::IndexedRandom;
 use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey};
 use std::sync::Arc;
@@ -24,6 +25,12 @@ pub mod seeds {
     pub const GLOBAL_VOLUME_ACCUMULATOR_SEED: &[u8] = b"global_volume_accumulator";
 
     pub const FEE_CONFIG_SEED: &[u8] = b"fee_config";
+
+    /// Seed for fee sharing config PDAs (owned by the fee program)
+    pub const SHARING_CONFIG_SEED: &[u8] = b"sharing-config";
+
+    /// Seed for Anchor event authority PDAs
+    pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
 }
 
 pub mod global_constants {
@@ -171,6 +178,22 @@ pub mod accounts {
 pub const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
 pub const BUY_EXACT_SOL_IN_DISCRIMINATOR: [u8; 8] = [56, 252, 116, 8, 158, 223, 205, 95];
 pub const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+pub const COLLECT_CREATOR_FEE_DISCRIMINATOR: [u8; 8] = [20, 22, 86, 123, 198, 28, 219, 132];
+pub const DISTRIBUTE_CREATOR_FEES_DISCRIMINATOR: [u8; 8] = [165, 114, 103, 0, 121, 206, 247, 81];
+pub const GET_MINIMUM_DISTRIBUTABLE_FEE_DISCRIMINATOR: [u8; 8] =
+    [117, 225, 127, 202, 134, 95, 68, 35];
+pub const INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR: [u8; 8] =
+    [94, 6, 202, 115, 255, 96, 232, 183];
+pub const SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR: [u8; 8] = [86, 31, 192, 87, 163, 87, 79, 238];
+pub const CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR: [u8; 8] =
+    [249, 69, 164, 218, 150, 103, 84, 138];
+pub const CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR: [u8; 8] = [16, 4, 71, 28, 204, 1, 40, 27];
+
+/// Instruction discriminators for the Pump fee program (fee sharing)
+pub const CREATE_FEE_SHARING_CONFIG_DISCRIMINATOR: [u8; 8] = [195, 78, 86, 76, 111, 52, 251, 213];
+pub const UPDATE_FEE_SHARES_DISCRIMINATOR: [u8; 8] = [189, 13, 136, 99, 187, 164, 237, 35];
+pub const REVOKE_FEE_SHARING_AUTHORITY_DISCRIMINATOR: [u8; 8] =
+    [18, 233, 158, 39, 185, 207, 58, 104];
 
 /// Returns a random Mayhem fee recipient AccountMeta (pump-public-docs: Bonding Curve 2nd account = Mayhem fee recipient; use any one randomly).
 #[inline]
@@ -255,6 +278,167 @@ pub fn get_user_volume_accumulator_pda(user: &Pubkey) -> Option<Pubkey> {
     )
 }
 
+/// Fee sharing config of `mint` (seeds: ["sharing-config", mint], fee program). Once created it
+/// replaces the coin creator, so the creator vault becomes `get_creator_vault_pda(&sharing_config)`.
+#[inline]
+pub fn get_sharing_config_pda(mint: &Pubkey) -> Option<Pubkey> {
+    let seeds: &[&[u8]; 2] = &[seeds::SHARING_CONFIG_SEED, mint.as_ref()];
+    let pda: Option<(Pubkey, u8)> = Pubkey::try_find_program_address(seeds, &accounts::FEE_PROGRAM);
+    pda.map(|pubkey| pubkey.0)
+}
+
+/// Event authority of the fee program.
+pub fn get_fee_program_event_authority_pda() -> Pubkey {
+    static EVENT_AUTHORITY: std::sync::LazyLock<Pubkey> = std::sync::LazyLock::new(|| {
+        Pubkey::find_program_address(&[seeds::EVENT_AUTHORITY_SEED], &accounts::FEE_PROGRAM).0
+    });
+    *EVENT_AUTHORITY
+}
+
+/// One recipient of a fee sharing config; all `share_bps` of a config sum to 10_000.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Shareholder {
+    pub address: Pubkey,
+    pub share_bps: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize)]
+pub enum SharingConfigStatus {
+    Paused,
+    Active,
+}
+
+/// Fee sharing config account (fee program).
+#[derive(Clone, Debug, BorshDeserialize)]
+pub struct SharingConfig {
+    pub bump: u8,
+    pub version: u8,
+    pub status: SharingConfigStatus,
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub admin_revoked: bool,
+    pub shareholders: Vec<Shareholder>,
+}
+
+/// Return data of `get_minimum_distributable_fee`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize)]
+pub struct MinimumDistributableFee {
+    /// Lamports the creator vault must hold before `distribute_creator_fees` succeeds.
+    pub minimum_required: u64,
+    /// Lamports that would be distributed now.
+    pub distributable_fees: u64,
+    pub can_distribute: bool,
+}
+
+/// Per-user volume accumulator (same layout in the Pump and PumpSwap programs).
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshDeserialize)]
+pub struct UserVolumeAccumulator {
+    pub user: Pubkey,
+    pub needs_claim: bool,
+    pub total_unclaimed_tokens: u64,
+    pub total_claimed_tokens: u64,
+    /// SOL volume traded on the day of `last_update_timestamp`.
+    pub current_sol_volume: u64,
+    pub last_update_timestamp: i64,
+    pub has_total_claimed_tokens: bool,
+    pub cashback_earned: u64,
+    pub total_cashback_claimed: u64,
+}
+
+/// Size of `UserVolumeAccumulator` without the 8-byte discriminator.
+pub const USER_VOLUME_ACCUMULATOR_SIZE: usize = 32 + 1 + 8 + 8 + 8 + 8 + 1 + 8 + 8;
+
+/// Program-wide incentive schedule: `total_token_supply[day]` incentive tokens are shared
+/// pro rata by `sol_volumes[day]`.
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize)]
+pub struct GlobalVolumeAccumulator {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub seconds_in_a_day: i64,
+    /// Incentive token mint.
+    pub mint: Pubkey,
+    pub total_token_supply: [u64; 30],
+    pub sol_volumes: [u64; 30],
+}
+
+impl UserVolumeAccumulator {
+    /// Decode account data (with discriminator). Accounts created before the cashback fields were
+    /// added are shorter; missing trailing fields decode as zero.
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        let body = data.get(8..)?;
+        let mut buf = [0u8; USER_VOLUME_ACCUMULATOR_SIZE];
+        let len = body.len().min(USER_VOLUME_ACCUMULATOR_SIZE);
+        buf[..len].copy_from_slice(&body[..len]);
+        Self::deserialize(&mut &buf[..]).ok()
+    }
+
+    /// Incentive tokens claimable at `now` (unix seconds): the settled unclaimed amount plus the
+    /// share earned on the last traded day once that day has ended.
+    pub fn claimable_tokens(&self, global: &GlobalVolumeAccumulator, now: i64) -> u64 {
+        let mut total = self.total_unclaimed_tokens;
+        if global.start_time == 0
+            || global.end_time == 0
+            || global.seconds_in_a_day == 0
+            || now < global.start_time
+        {
+            return total;
+        }
+        let current_day = (now - global.start_time) / global.seconds_in_a_day;
+        let last_day = (self.last_update_timestamp - global.start_time) / global.seconds_in_a_day;
+        if current_day > last_day && last_day >= 0 {
+            if let (Some(&supply), Some(&volume)) = (
+                global.total_token_supply.get(last_day as usize),
+                global.sol_volumes.get(last_day as usize),
+            ) {
+                if volume > 0 {
+                    total += ((self.current_sol_volume as u128) * (supply as u128)
+                        / (volume as u128)) as u64;
+                }
+            }
+        }
+        total
+    }
+}
+
+/// Claimable incentives and cashback of one user on one program.
+#[derive(Clone, Debug)]
+pub struct TokenIncentives {
+    pub user_volume_accumulator: Pubkey,
+    /// `None` when the user has no accumulator yet.
+    pub user: Option<UserVolumeAccumulator>,
+    pub global: GlobalVolumeAccumulator,
+    /// Incentive tokens `claim_token_incentives` would transfer now.
+    pub claimable_tokens: u64,
+    /// Cashback `claim_cashback` would transfer now (lamports on Pump, WSOL on PumpSwap).
+    pub claimable_cashback: u64,
+}
+
+/// Fetch and decode a global volume accumulator account (either program).
+pub async fn fetch_global_volume_accumulator(
+    rpc: &SolanaRpcClient,
+    address: &Pubkey,
+) -> Result<GlobalVolumeAccumulator, anyhow::Error> {
+    let data = rpc.get_account_data(address).await?;
+    let body = data.get(8..).ok_or(anyhow!("Global volume accumulator not found"))?;
+    GlobalVolumeAccumulator::deserialize(&mut &body[..])
+        .map_err(|e| anyhow!("Failed to deserialize global volume accumulator: {}", e))
+}
+
+pub async fn fetch_sharing_config(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+) -> Result<(SharingConfig, Pubkey), anyhow::Error> {
+    let sharing_config_pda =
+        get_sharing_config_pda(mint).ok_or(anyhow!("Sharing config not found"))?;
+    let account = rpc.get_account(&sharing_config_pda).await?;
+    if account.owner != accounts::FEE_PROGRAM || account.data.len() < 8 {
+        return Err(anyhow!("Sharing config not found"));
+    }
+    let sharing_config = SharingConfig::deserialize(&mut &account.data[8..])
+        .map_err(|e| anyhow!("Failed to deserialize sharing config: {}", e))?;
+    Ok((sharing_config, sharing_config_pda))
+}
+
 #[inline]
 pub async fn fetch_bonding_curve_account(
     rpc: &SolanaRpcClient,
@@ -322,4 +506,40 @@ mod tests {
         let b = get_creator_vault_pda(&creator).unwrap();
         assert_eq!(a, b);
     }
+
+    #[test]
+    fn pumpfun_claimable_tokens_adds_finished_day_share() {
+        let mut global = GlobalVolumeAccumulator {
+            start_time: 1_000,
+            end_time: 1_000 + 30 * 100,
+            seconds_in_a_day: 100,
+            mint: Pubkey::new_unique(),
+            total_token_supply: [0; 30],
+            sol_volumes: [0; 30],
+        };
+        global.total_token_supply[1] = 1_000;
+        global.sol_volumes[1] = 400;
+        let mut data = vec![0u8; 8];
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.push(1); // needs_claim
+        data.extend_from_slice(&50u64.to_le_bytes()); // total_unclaimed_tokens
+        data.extend_from_slice(&0u64.to_le_bytes()); // total_claimed_tokens
+        data.extend_from_slice(&100u64.to_le_bytes()); // current_sol_volume
+        data.extend_from_slice(&1_150i64.to_le_bytes()); // last_update_timestamp (day 1)
+        let user = UserVolumeAccumulator::from_account_data(&data).unwrap();
+        assert_eq!(user.cashback_earned, 0);
+        // Day 1 still running: only the settled amount
+        assert_eq!(user.claimable_tokens(&global, 1_199), 50);
+        // Day 1 over: 100 / 400 of 1_000
+        assert_eq!(user.claimable_tokens(&global, 1_200), 300);
+    }
+
+    #[test]
+    fn pumpfun_shareholder_borsh_layout() {
+        let holder = Shareholder { address: Pubkey::new_unique(), share_bps: 2_500 };
+        let data = borsh::to_vec(&vec![holder]).unwrap();
+        // u32 vec len + 32-byte pubkey + u16 bps
+        assert_eq!(data.len(), 4 + 32 + 2);
+        assert_eq!(&data[36..], &2_500u16.to_le_bytes());
+    }
 }
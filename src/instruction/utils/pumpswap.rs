@@ -12,7 +12,11 @@ use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey};
 
 /// PumpSwap 池账户总长度（见 pump-public-docs Breaking Change）：8 字节 discriminator + 244 字节 Pool。
 /// 官方文档：pool structure needs to be 244 bytes (was 243)，含 is_mayhem_mode。DataSize 必须与此一致，否则 getProgramAccounts 会返回 0。
-const POOL_ACCOUNT_DATA_LEN: u64 = 8 + 244;
+pub(crate) const POOL_ACCOUNT_DATA_LEN: u64 = 8 + 244;
+/// base_mint 在账户布局中的偏移：8(discriminator) + 1(bump) + 2(index) + 32(creator) = 43
+pub(crate) const BASE_MINT_OFFSET: usize = 43;
+/// quote_mint 偏移：8 + 1 + 2 + 32 + 32 = 75
+pub(crate) const QUOTE_MINT_OFFSET: usize = 75;
 
 /// Constants used as seeds for deriving PDAs (Program Derived Addresses)
 pub mod seeds {
@@ -38,6 +42,8 @@ pub mod seeds {
     pub const POOL_SEED: &[u8] = b"pool";
     /// Pump program: pool-authority PDA seed (creator for canonical pool)
     pub const POOL_AUTHORITY_SEED: &[u8] = b"pool-authority";
+    /// LP mint PDA seed (used with pool)
+    pub const LP_MINT_SEED: &[u8] = b"pool_lp_mint";
 }
 
 /// Constants related to program accounts and authorities
@@ -162,6 +168,18 @@ pub mod accounts {
 pub const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
 pub const BUY_EXACT_QUOTE_IN_DISCRIMINATOR: [u8; 8] = [198, 46, 21, 82, 180, 217, 232, 112];
 pub const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+pub const COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR: [u8; 8] = [160, 57, 89, 42, 181, 139, 43, 66];
+pub const TRANSFER_CREATOR_FEES_TO_PUMP_DISCRIMINATOR: [u8; 8] =
+    [139, 52, 134, 85, 228, 229, 108, 241];
+pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+pub const CREATE_POOL_DISCRIMINATOR: [u8; 8] = [233, 146, 209, 142, 207, 104, 64, 188];
+pub const INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR: [u8; 8] =
+    [94, 6, 202, 115, 255, 96, 232, 183];
+pub const SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR: [u8; 8] = [86, 31, 192, 87, 163, 87, 79, 238];
+pub const CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR: [u8; 8] =
+    [249, 69, 164, 218, 150, 103, 84, 138];
+pub const CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR: [u8; 8] = [16, 4, 71, 28, 204, 1, 40, 27];
 
 /// Returns a random Mayhem fee recipient and its AccountMeta (pump-public-docs: use any one randomly).
 #[inline]
@@ -289,6 +307,46 @@ pub fn get_global_volume_accumulator_pda() -> Option<Pubkey> {
     pda.map(|pubkey| pubkey.0)
 }
 
+/// Pool PDA (seeds: ["pool", index, creator, base_mint, quote_mint]).
+#[inline]
+pub fn get_pool_pda(
+    index: u16,
+    creator: &Pubkey,
+    base_mint: &Pubkey,
+    quote_mint: &Pubkey,
+) -> Option<Pubkey> {
+    let index = index.to_le_bytes();
+    let seeds: &[&[u8]; 5] =
+        &[seeds::POOL_SEED, &index, creator.as_ref(), base_mint.as_ref(), quote_mint.as_ref()];
+    let pda: Option<(Pubkey, u8)> = Pubkey::try_find_program_address(seeds, &accounts::AMM_PROGRAM);
+    pda.map(|pubkey| pubkey.0)
+}
+
+/// LP mint PDA of a pool (seeds: ["pool_lp_mint", pool]); LP tokens use Token-2022.
+#[inline]
+pub fn get_lp_mint_pda(pool: &Pubkey) -> Option<Pubkey> {
+    let seeds: &[&[u8]; 2] = &[seeds::LP_MINT_SEED, pool.as_ref()];
+    let pda: Option<(Pubkey, u8)> = Pubkey::try_find_program_address(seeds, &accounts::AMM_PROGRAM);
+    pda.map(|pubkey| pubkey.0)
+}
+
+/// Token programs of a pool's (base, quote) mints, inferred from its vault addresses (no RPC).
+pub fn get_pool_token_programs(pool_address: &Pubkey, pool: &Pool) -> (Pubkey, Pubkey) {
+    let program_of = |mint: &Pubkey, vault: &Pubkey| {
+        if get_associated_token_address_with_program_id(pool_address, mint, &TOKEN_PROGRAM)
+            == *vault
+        {
+            TOKEN_PROGRAM
+        } else {
+            crate::constants::TOKEN_PROGRAM_2022
+        }
+    };
+    (
+        program_of(&pool.base_mint, &pool.pool_base_token_account),
+        program_of(&pool.quote_mint, &pool.pool_quote_token_account),
+    )
+}
+
 pub async fn fetch_pool(
     rpc: &SolanaRpcClient,
     pool_address: &Pubkey,
@@ -1,6 +1,6 @@
 use crate::{
     common::SolanaRpcClient,
-    instruction::utils::raydium_amm_v4_types::{amm_info_decode, AmmInfo},
+    instruction::utils::raydium_amm_v4_types::{amm_info_decode, AmmInfo, AMM_INFO_SIZE},
 };
 use anyhow::anyhow;
 use solana_sdk::pubkey::Pubkey;
@@ -33,6 +33,35 @@ pub mod accounts {
 
 pub const SWAP_BASE_IN_DISCRIMINATOR: &[u8] = &[9];
 pub const SWAP_BASE_OUT_DISCRIMINATOR: &[u8] = &[11];
+pub const DEPOSIT_DISCRIMINATOR: &[u8] = &[3];
+pub const WITHDRAW_DISCRIMINATOR: &[u8] = &[4];
+
+/// `AmmInfo` has no discriminator: 16 u64 + Fees(64) + OutPutData(144) + token_coin + token_pc = 400
+pub(crate) const COIN_MINT_OFFSET: usize = 400;
+pub(crate) const PC_MINT_OFFSET: usize = 432;
+
+/// OpenBook/Serum market accounts the AMM v4 `deposit`/`withdraw` instructions still require.
+#[derive(Clone, Debug)]
+pub struct MarketAccounts {
+    pub market: Pubkey,
+    pub market_program: Pubkey,
+    pub bids: Pubkey,
+    pub asks: Pubkey,
+    pub event_queue: Pubkey,
+    pub coin_vault: Pubkey,
+    pub pc_vault: Pubkey,
+    pub vault_signer: Pubkey,
+}
+
+/// Market state v3 layout: 5-byte "serum" head, then account flags, own address, vault signer
+/// nonce, mints, vaults and queues.
+const MARKET_STATE_MIN_SIZE: usize = 349;
+const MARKET_VAULT_SIGNER_NONCE_OFFSET: usize = 45;
+const MARKET_COIN_VAULT_OFFSET: usize = 117;
+const MARKET_PC_VAULT_OFFSET: usize = 165;
+const MARKET_EVENT_QUEUE_OFFSET: usize = 253;
+const MARKET_BIDS_OFFSET: usize = 285;
+const MARKET_ASKS_OFFSET: usize = 317;
 
 pub async fn fetch_amm_info(rpc: &SolanaRpcClient, amm: Pubkey) -> Result<AmmInfo, anyhow::Error> {
     let amm_info = rpc.get_account_data(&amm).await?;
@@ -40,3 +69,143 @@ pub async fn fetch_amm_info(rpc: &SolanaRpcClient, amm: Pubkey) -> Result<AmmInf
         amm_info_decode(&amm_info).ok_or_else(|| anyhow!("Failed to decode amm info"))?;
     Ok(amm_info)
 }
+
+/// Fetch the market accounts of `amm_info` (`AmmInfo::market` owned by `AmmInfo::serum_dex`).
+pub async fn fetch_market_accounts(
+    rpc: &SolanaRpcClient,
+    amm_info: &AmmInfo,
+) -> Result<MarketAccounts, anyhow::Error> {
+    let data = rpc.get_account_data(&amm_info.market).await?;
+    decode_market_accounts(&data, amm_info)
+}
+
+/// Decode the market state v3 `data` of `amm_info.market`.
+pub(crate) fn decode_market_accounts(
+    data: &[u8],
+    amm_info: &AmmInfo,
+) -> Result<MarketAccounts, anyhow::Error> {
+    if data.len() < MARKET_STATE_MIN_SIZE {
+        return Err(anyhow!("Failed to decode market state"));
+    }
+    let pubkey_at = |offset: usize| {
+        Pubkey::new_from_array(data[offset..offset + 32].try_into().expect("32-byte slice"))
+    };
+    let nonce = u64::from_le_bytes(
+        data[MARKET_VAULT_SIGNER_NONCE_OFFSET..MARKET_VAULT_SIGNER_NONCE_OFFSET + 8]
+            .try_into()
+            .expect("8-byte slice"),
+    );
+    let vault_signer = Pubkey::create_program_address(
+        &[amm_info.market.as_ref(), &nonce.to_le_bytes()],
+        &amm_info.serum_dex,
+    )
+    .map_err(|e| anyhow!("Failed to derive market vault signer: {}", e))?;
+    Ok(MarketAccounts {
+        market: amm_info.market,
+        market_program: amm_info.serum_dex,
+        bids: pubkey_at(MARKET_BIDS_OFFSET),
+        asks: pubkey_at(MARKET_ASKS_OFFSET),
+        event_queue: pubkey_at(MARKET_EVENT_QUEUE_OFFSET),
+        coin_vault: pubkey_at(MARKET_COIN_VAULT_OFFSET),
+        pc_vault: pubkey_at(MARKET_PC_VAULT_OFFSET),
+        vault_signer,
+    })
+}
+
+/// Find the AMM v4 pool for `coin_mint` / `pc_mint` via getProgramAccounts (largest LP amount wins).
+pub async fn find_amm_by_mints(
+    rpc: &SolanaRpcClient,
+    coin_mint: &Pubkey,
+    pc_mint: &Pubkey,
+) -> Result<(Pubkey, AmmInfo), anyhow::Error> {
+    crate::trading::pool_finder::scan_program_accounts(
+        rpc,
+        &accounts::RAYDIUM_AMM_V4,
+        AMM_INFO_SIZE as u64,
+        &[(COIN_MINT_OFFSET, coin_mint), (PC_MINT_OFFSET, pc_mint)],
+    )
+    .await?
+    .into_iter()
+    .filter_map(|(addr, data)| amm_info_decode(&data).map(|amm| (addr, amm)))
+    .max_by_key(|(_, amm)| amm.lp_amount)
+    .ok_or_else(|| anyhow!("No Raydium AMM v4 pool found for {} / {}", coin_mint, pc_mint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market_amm_info() -> AmmInfo {
+        AmmInfo {
+            market: Pubkey::new_unique(),
+            serum_dex: Pubkey::new_unique(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn market_accounts_are_read_at_the_v3_offsets() {
+        let amm_info = market_amm_info();
+        let nonce = (0u64..256)
+            .find(|n| {
+                Pubkey::create_program_address(
+                    &[amm_info.market.as_ref(), &n.to_le_bytes()],
+                    &amm_info.serum_dex,
+                )
+                .is_ok()
+            })
+            .unwrap();
+        let mut data = vec![0u8; MARKET_STATE_MIN_SIZE];
+        data[MARKET_VAULT_SIGNER_NONCE_OFFSET..MARKET_VAULT_SIGNER_NONCE_OFFSET + 8]
+            .copy_from_slice(&nonce.to_le_bytes());
+        let keys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        for (offset, key) in [
+            MARKET_COIN_VAULT_OFFSET,
+            MARKET_PC_VAULT_OFFSET,
+            MARKET_EVENT_QUEUE_OFFSET,
+            MARKET_BIDS_OFFSET,
+            MARKET_ASKS_OFFSET,
+        ]
+        .into_iter()
+        .zip(&keys)
+        {
+            data[offset..offset + 32].copy_from_slice(key.as_ref());
+        }
+
+        let market = decode_market_accounts(&data, &amm_info).unwrap();
+        assert_eq!(market.market, amm_info.market);
+        assert_eq!(market.market_program, amm_info.serum_dex);
+        assert_eq!(market.coin_vault, keys[0]);
+        assert_eq!(market.pc_vault, keys[1]);
+        assert_eq!(market.event_queue, keys[2]);
+        assert_eq!(market.bids, keys[3]);
+        assert_eq!(market.asks, keys[4]);
+        assert_eq!(
+            market.vault_signer,
+            Pubkey::create_program_address(
+                &[amm_info.market.as_ref(), &nonce.to_le_bytes()],
+                &amm_info.serum_dex,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn short_market_state_is_rejected() {
+        let data = vec![0u8; MARKET_STATE_MIN_SIZE - 1];
+        assert!(decode_market_accounts(&data, &market_amm_info()).is_err());
+    }
+
+    #[test]
+    fn mint_filter_offsets_match_the_amm_info_layout() {
+        let coin_mint = Pubkey::new_unique();
+        let pc_mint = Pubkey::new_unique();
+        let mut data = vec![0u8; AMM_INFO_SIZE];
+        data[COIN_MINT_OFFSET..COIN_MINT_OFFSET + 32].copy_from_slice(coin_mint.as_ref());
+        data[PC_MINT_OFFSET..PC_MINT_OFFSET + 32].copy_from_slice(pc_mint.as_ref());
+
+        let amm_info = amm_info_decode(&data).unwrap();
+        assert_eq!(amm_info.coin_mint, coin_mint);
+        assert_eq!(amm_info.pc_mint, pc_mint);
+    }
+}
@@ -6,9 +6,16 @@ use crate::{
 use anyhow::anyhow;
 use solana_sdk::pubkey::Pubkey;
 
+/// Pool state account size including the 8-byte discriminator.
+pub(crate) const POOL_STATE_ACCOUNT_DATA_LEN: u64 = 8 + 629;
+/// Mint offsets in the pool state account: 8(discriminator) + 5 * 32, then + 32.
+pub(crate) const TOKEN0_MINT_OFFSET: usize = 168;
+pub(crate) const TOKEN1_MINT_OFFSET: usize = 200;
+
 /// Constants used as seeds for deriving PDAs (Program Derived Addresses)
 pub mod seeds {
     pub const POOL_SEED: &[u8] = b"pool";
+    pub const AMM_CONFIG_SEED: &[u8] = b"amm_config";
     pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
     pub const OBSERVATION_STATE_SEED: &[u8] = b"observation";
 }
@@ -18,6 +25,7 @@ pub mod accounts {
     use solana_sdk::{pubkey, pubkey::Pubkey};
     pub const AUTHORITY: Pubkey = pubkey!("GpMZbSM2GgvTKHJirzeGfMFoaZ8UR2X7F4v8vHTvxFbL");
     pub const RAYDIUM_CPMM: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+    pub const MEMO_PROGRAM: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
     pub const FEE_RATE_DENOMINATOR_VALUE: u128 = 1_000_000;
     pub const TRADE_FEE_RATE: u64 = 2500;
     pub const CREATOR_FEE_RATE: u64 = 0;
@@ -34,6 +42,8 @@ pub mod accounts {
 
 pub const SWAP_BASE_IN_DISCRIMINATOR: &[u8] = &[143, 190, 90, 218, 196, 30, 51, 222];
 pub const SWAP_BASE_OUT_DISCRIMINATOR: &[u8] = &[55, 217, 98, 86, 163, 74, 180, 173];
+pub const DEPOSIT_DISCRIMINATOR: &[u8] = &[242, 35, 198, 137, 82, 225, 242, 182];
+pub const WITHDRAW_DISCRIMINATOR: &[u8] = &[183, 18, 70, 156, 148, 109, 161, 34];
 
 pub async fn fetch_pool_state(
     rpc: &SolanaRpcClient,
@@ -48,6 +58,30 @@ pub async fn fetch_pool_state(
     Ok(pool_state)
 }
 
+/// Find the CPMM pool trading `mint_a` against `mint_b` (any order) via getProgramAccounts.
+///
+/// When several pools (different amm configs) exist, the one with the largest LP supply is returned.
+pub async fn find_pool_by_mints(
+    rpc: &SolanaRpcClient,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+) -> Result<(Pubkey, PoolState), anyhow::Error> {
+    // token0 < token1 by key bytes
+    let (token0_mint, token1_mint) =
+        if mint_a < mint_b { (mint_a, mint_b) } else { (mint_b, mint_a) };
+    crate::trading::pool_finder::scan_program_accounts(
+        rpc,
+        &accounts::RAYDIUM_CPMM,
+        POOL_STATE_ACCOUNT_DATA_LEN,
+        &[(TOKEN0_MINT_OFFSET, token0_mint), (TOKEN1_MINT_OFFSET, token1_mint)],
+    )
+    .await?
+    .into_iter()
+    .filter_map(|(addr, data)| data.get(8..).and_then(pool_state_decode).map(|pool| (addr, pool)))
+    .max_by_key(|(_, pool)| pool.lp_supply)
+    .ok_or_else(|| anyhow!("No Raydium CPMM pool found for {} / {}", mint_a, mint_b))
+}
+
 pub fn get_pool_pda(amm_config: &Pubkey, mint1: &Pubkey, mint2: &Pubkey) -> Option<Pubkey> {
     let seeds: &[&[u8]; 4] =
         &[seeds::POOL_SEED, amm_config.as_ref(), mint1.as_ref(), mint2.as_ref()];
@@ -56,6 +90,15 @@ pub fn get_pool_pda(amm_config: &Pubkey, mint1: &Pubkey, mint2: &Pubkey) -> Opti
     pda.map(|pubkey| pubkey.0)
 }
 
+/// Amm config PDA for fee tier `index` (seeds: ["amm_config", index as big-endian u16]).
+pub fn get_amm_config_pda(index: u16) -> Option<Pubkey> {
+    let index = index.to_be_bytes();
+    let seeds: &[&[u8]; 2] = &[seeds::AMM_CONFIG_SEED, &index];
+    let pda: Option<(Pubkey, u8)> =
+        Pubkey::try_find_program_address(seeds, &accounts::RAYDIUM_CPMM);
+    pda.map(|pubkey| pubkey.0)
+}
+
 pub fn get_vault_pda(pool_state: &Pubkey, mint: &Pubkey) -> Option<Pubkey> {
     let seeds: &[&[u8]; 3] = &[seeds::POOL_VAULT_SEED, pool_state.as_ref(), mint.as_ref()];
     let program_id: &Pubkey = &accounts::RAYDIUM_CPMM;
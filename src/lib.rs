@@ -18,9 +18,19 @@ use crate::constants::WSOL_TOKEN_ACCOUNT;
 use crate::swqos::common::TradeError;
 use crate::swqos::SwqosClient;
 use crate::swqos::SwqosConfig;
+use crate::swqos::SwqosType;
 use crate::swqos::TradeType;
 // Re-export for SWQOS HTTP/QUIC choice in SwqosConfig (e.g. Astralane)
 pub use crate::swqos::SwqosTransport;
+
+pub use crate::swqos::health::{
+    CircuitBreakerConfig, CircuitState, SwqosHealthEvent, SwqosHealthEventHandler,
+    SwqosHealthMonitor,
+};
+pub use crate::swqos::scoreboard::{
+    SwqosScoreSnapshot, SwqosScoreboard, DEFAULT_SCOREBOARD_WINDOW,
+};
+pub use crate::trading::core::async_executor::{LandedRouteHandler, SwqosRoute};
 use crate::trading::core::params::BonkParams;
 use crate::trading::core::params::DexParamEnum;
 use crate::trading::core::params::MeteoraDammV2Params;
@@ -32,6 +42,7 @@ use crate::trading::factory::DexType;
 use crate::trading::MiddlewareManager;
 use crate::trading::SwapParams;
 use crate::trading::TradeFactory;
+use arc_swap::ArcSwap;
 use common::SolanaRpcClient;
 use parking_lot::Mutex;
 use rustls::crypto::{ring::default_provider, CryptoProvider};
@@ -56,17 +67,27 @@ fn validate_protocol_params(dex_type: DexType, params: &DexParamEnum) -> bool {
     }
 }
 
-/// 按 mint 查找池地址（通用入口，根据 DEX 类型分发，仅 PumpSwap 等已实现的类型会走优化路径）。
+/// 按 mint 查找池（通用入口，根据 DEX 类型分发），返回全部候选池，按配对代币分组（WSOL、USDC、USD1、其他），组内按储备从大到小排序。
+///
+/// * PumpFun：未完成的 bonding curve；Bonk：WSOL / USD1 的 `get_pool_pda`（仅未迁移的池）。
+/// * PumpSwap / Raydium CPMM：先查 PDA（CPMM 按 amm config 索引），再用 getProgramAccounts 补全。
+/// * Raydium AMM v4 / Meteora DAMM v2：getProgramAccounts 按 mint 偏移 memcmp 过滤。
 ///
-/// * `dex_type`：PumpSwap 时先走 PDA 再回退 getProgramAccounts，其他类型返回未实现错误。
+/// See `trading::pool_finder` for the lookup and ranking details.
 pub async fn find_pool_by_mint(
     rpc: &SolanaRpcClient,
     mint: &Pubkey,
     dex_type: DexType,
-) -> Result<Pubkey, anyhow::Error> {
-    match dex_type {
-        DexType::PumpSwap => crate::instruction::utils::pumpswap::find_pool(rpc, mint).await,
-        _ => Err(anyhow::anyhow!("find_pool_by_mint not implemented for {:?}", dex_type)),
+) -> Result<Vec<trading::pool_finder::PoolCandidate>, anyhow::Error> {
+    trading::pool_finder::find_pools_by_mint(rpc, mint, dex_type).await
+}
+
+/// Mint a pool must pair with to trade `token_type` (SOL trades go through WSOL pools).
+fn route_quote_mint(token_type: &TradeTokenType) -> Pubkey {
+    match token_type {
+        TradeTokenType::SOL | TradeTokenType::WSOL => WSOL_TOKEN_ACCOUNT,
+        TradeTokenType::USD1 => USD1_TOKEN_ACCOUNT,
+        TradeTokenType::USDC => USDC_TOKEN_ACCOUNT,
     }
 }
 
@@ -86,8 +107,13 @@ pub enum TradeTokenType {
 pub struct TradingInfrastructure {
     /// Shared RPC client for blockchain interactions
     pub rpc: Arc<SolanaRpcClient>,
-    /// Shared SWQOS clients for transaction priority and routing
-    pub swqos_clients: Vec<Arc<SwqosClient>>,
+    /// Shared SWQOS clients for transaction priority and routing.
+    /// Hot-swappable: each trade loads a snapshot, so add/remove/replace never affect in-flight trades.
+    pub swqos_clients: Arc<ArcSwap<Vec<Arc<SwqosClient>>>>,
+    /// Per-provider submit/landing statistics; drives optional adaptive SWQOS selection
+    pub swqos_scoreboard: Arc<SwqosScoreboard>,
+    /// Health probes and circuit breaker; open providers are left out of the fan-out
+    pub swqos_health: Arc<SwqosHealthMonitor>,
     /// Configuration used to create this infrastructure
     pub config: InfrastructureConfig,
 }
@@ -121,61 +147,120 @@ impl TradingInfrastructure {
                 if sdk_log::sdk_log_enabled() {
                     warn!(target: "sol_trade_sdk", "rent update failed: {}, using defaults", e);
                 }
-                common::seed::set_default_rents();
+                crate::common::seed::set_default_rents();
             }
             Err(_) => {
                 if sdk_log::sdk_log_enabled() {
                     warn!(target: "sol_trade_sdk", "rent update timed out ({}s), using defaults; check RPC", RENT_UPDATE_TIMEOUT.as_secs());
                 }
-                common::seed::set_default_rents();
+                crate::common::seed::set_default_rents();
             }
         }
         common::seed::start_rent_updater(rpc.clone());
 
         // Create SWQOS clients with blacklist checking（单节点超时 5s，避免某一家卡死整段初始化）
-        const SWQOS_CLIENT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
         let mut swqos_clients: Vec<Arc<SwqosClient>> = vec![];
         for swqos in &config.swqos_configs {
-            if swqos.is_blacklisted() {
-                if sdk_log::sdk_log_enabled() {
-                    warn!(target: "sol_trade_sdk", "⚠️ SWQOS {:?} is blacklisted, skipping", swqos.swqos_type());
-                }
-                continue;
-            }
-            match tokio::time::timeout(
-                SWQOS_CLIENT_TIMEOUT,
-                SwqosConfig::get_swqos_client(
-                    config.rpc_url.clone(),
-                    config.commitment.clone(),
-                    swqos.clone(),
-                ),
-            )
-            .await
-            {
-                Ok(Ok(swqos_client)) => swqos_clients.push(swqos_client),
-                Ok(Err(err)) => {
-                    if sdk_log::sdk_log_enabled() {
-                        warn!(
-                            target: "sol_trade_sdk",
-                            "failed to create {:?} swqos client: {err}. Excluding from swqos list",
-                            swqos.swqos_type()
-                        );
-                    }
-                }
-                Err(_) => {
+            match Self::create_swqos_client(&config, swqos).await {
+                Ok(swqos_client) => swqos_clients.push(swqos_client),
+                Err(err) => {
                     if sdk_log::sdk_log_enabled() {
-                        warn!(
-                            target: "sol_trade_sdk",
-                            "swqos {:?} init timed out ({}s), skipping",
-                            swqos.swqos_type(),
-                            SWQOS_CLIENT_TIMEOUT.as_secs()
-                        );
+                        warn!(target: "sol_trade_sdk", "⚠️ {err}. Excluding from swqos list");
                     }
                 }
             }
         }
+        let swqos_clients = Arc::new(ArcSwap::from_pointee(swqos_clients));
+
+        let swqos_scoreboard = Arc::new(SwqosScoreboard::new(DEFAULT_SCOREBOARD_WINDOW));
+
+        let swqos_health = Arc::new(SwqosHealthMonitor::default());
+        swqos_health.start_probes(swqos_clients.clone());
+
+        Self { rpc, swqos_clients, swqos_scoreboard, swqos_health, config }
+    }
+
+    /// Create one SWQOS client (blacklist check + init timeout).
+    async fn create_swqos_client(
+        config: &InfrastructureConfig,
+        swqos: &SwqosConfig,
+    ) -> Result<Arc<SwqosClient>, anyhow::Error> {
+        const SWQOS_CLIENT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+        if swqos.is_blacklisted() {
+            return Err(anyhow::anyhow!("SWQOS {:?} is blacklisted", swqos.swqos_type()));
+        }
+        match tokio::time::timeout(
+            SWQOS_CLIENT_TIMEOUT,
+            SwqosConfig::get_swqos_client(
+                config.rpc_url.clone(),
+                config.commitment.clone(),
+                swqos.clone(),
+            ),
+        )
+        .await
+        {
+            Ok(Ok(swqos_client)) => Ok(swqos_client),
+            Ok(Err(err)) => Err(anyhow::anyhow!(
+                "failed to create {:?} swqos client: {err}",
+                swqos.swqos_type()
+            )),
+            Err(_) => Err(anyhow::anyhow!(
+                "swqos {:?} init timed out ({}s)",
+                swqos.swqos_type(),
+                SWQOS_CLIENT_TIMEOUT.as_secs()
+            )),
+        }
+    }
+
+    /// Snapshot of the current SWQOS client set
+    pub fn swqos_clients(&self) -> Arc<Vec<Arc<SwqosClient>>> {
+        self.swqos_clients.load_full()
+    }
+
+    /// Create a SWQOS client and add it to the live client set
+    ///
+    /// Safe to call while trades are in flight: running trades keep the set they started with.
+    /// `config.swqos_configs` is not modified (it keeps describing the initial set / cache key).
+    pub async fn add_swqos(&self, swqos_config: SwqosConfig) -> Result<(), anyhow::Error> {
+        let swqos_client = Self::create_swqos_client(&self.config, &swqos_config).await?;
+        self.swqos_clients.rcu(|clients| {
+            let mut next = Vec::clone(clients);
+            next.push(swqos_client.clone());
+            next
+        });
+        Ok(())
+    }
+
+    /// Remove every client of `swqos_type` from the live client set
+    ///
+    /// Returns the number of clients removed.
+    pub fn remove_swqos(&self, swqos_type: SwqosType) -> usize {
+        let mut removed = 0;
+        self.swqos_clients.rcu(|clients| {
+            let next: Vec<Arc<SwqosClient>> =
+                clients.iter().filter(|c| c.get_swqos_type() != swqos_type).cloned().collect();
+            removed = clients.len() - next.len();
+            next
+        });
+        self.swqos_health.reset(swqos_type);
+        removed
+    }
 
-        Self { rpc, swqos_clients, config }
+    /// Replace all clients of the config's `SwqosType` with a new client built from `swqos_config`
+    ///
+    /// Use this to rotate an API key or move a provider to another region. The new client is created
+    /// first; if that fails the current clients are left untouched. Adds the client if none existed.
+    pub async fn replace_swqos(&self, swqos_config: SwqosConfig) -> Result<(), anyhow::Error> {
+        let swqos_type = swqos_config.swqos_type();
+        let swqos_client = Self::create_swqos_client(&self.config, &swqos_config).await?;
+        self.swqos_clients.rcu(|clients| {
+            let mut next: Vec<Arc<SwqosClient>> =
+                clients.iter().filter(|c| c.get_swqos_type() != swqos_type).cloned().collect();
+            next.push(swqos_client.clone());
+            next
+        });
+        self.swqos_health.reset(swqos_type);
+        Ok(())
     }
 }
 
@@ -201,6 +286,14 @@ pub struct TradingClient {
     pub log_enabled: bool,
     /// Whether to check minimum tip per SWQOS (from TradeConfig.check_min_tip). Default false for lower latency.
     pub check_min_tip: bool,
+    /// Optional callback told which SWQOS route (provider, fee strategy, tip, cu_price) landed a confirmed trade
+    pub landed_route_handler: Option<LandedRouteHandler>,
+    /// Optional ledger every confirmed trade is recorded to (see `trading::ledger`)
+    pub ledger: Option<Arc<trading::ledger::Ledger>>,
+    /// Optional risk limits every trade is checked against (see `trading::risk`)
+    pub risk: Option<Arc<trading::risk::RiskEngine>>,
+    /// Optional mint checks run before every buy (see `trading::mint_safety`)
+    pub mint_safety: Option<Arc<trading::mint_safety::MintSafetyPolicy>>,
 }
 
 static INSTANCE: Mutex<Option<Arc<TradingClient>>> = Mutex::new(None);
@@ -218,6 +311,10 @@ impl Clone for TradingClient {
             use_core_affinity: self.use_core_affinity,
             log_enabled: self.log_enabled,
             check_min_tip: self.check_min_tip,
+            landed_route_handler: self.landed_route_handler.clone(),
+            ledger: self.ledger.clone(),
+            risk: self.risk.clone(),
+            mint_safety: self.mint_safety.clone(),
         }
     }
 }
@@ -347,6 +444,10 @@ impl TradingClient {
             use_core_affinity: true,
             log_enabled: true,
             check_min_tip: false,
+            landed_route_handler: None,
+            ledger: None,
+            risk: None,
+            mint_safety: None,
         }
     }
 
@@ -387,6 +488,10 @@ impl TradingClient {
             use_core_affinity: true,
             log_enabled: true,
             check_min_tip: false,
+            landed_route_handler: None,
+            ledger: None,
+            risk: None,
+            mint_safety: None,
         }
     }
 
@@ -562,6 +667,10 @@ impl TradingClient {
             use_core_affinity: trade_config.use_core_affinity,
             log_enabled: trade_config.log_enabled,
             check_min_tip: trade_config.check_min_tip,
+            landed_route_handler: None,
+            ledger: None,
+            risk: None,
+            mint_safety: None,
         };
 
         let mut current = INSTANCE.lock();
@@ -585,6 +694,57 @@ impl TradingClient {
         self
     }
 
+    /// Registers a callback that receives the winning SWQOS route of each confirmed trade
+    ///
+    /// With durable nonces every SWQOS submits a differently-signed transaction, so the confirmed
+    /// signature tells which provider and fee config landed. Only called for trades with
+    /// `wait_transaction_confirmed = true`. Useful for measuring per-provider landing rates.
+    ///
+    /// # Arguments
+    /// * `handler` - Callback invoked with the landed [`SwqosRoute`]
+    pub fn with_landed_route_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&SwqosRoute) + Send + Sync + 'static,
+    {
+        self.landed_route_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Records every confirmed trade to `ledger` (see `trading::ledger`)
+    ///
+    /// Only trades with `wait_transaction_confirmed = true` are recorded; amounts are read from
+    /// the landed transaction in the background, after the trade call has returned.
+    pub fn with_ledger(mut self, ledger: Arc<trading::ledger::Ledger>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    /// Checks every trade (`buy` / `sell`, split and two-hop) against `risk` before building it
+    /// (see `trading::risk`)
+    ///
+    /// Limits can be changed at runtime through the shared engine; a refused trade returns an
+    /// error holding a [`trading::risk::RiskRejection`]. Give the engine the client's ledger
+    /// (`RiskEngine::with_ledger`) so a full sell releases its mint.
+    pub fn with_risk_limits(mut self, risk: Arc<trading::risk::RiskEngine>) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    /// Checks the mint of every buy (`buy`, split and two-hop) against `policy` first (see
+    /// `trading::mint_safety`)
+    ///
+    /// Costs one `get_account` per buy. A blocked mint returns an error holding a
+    /// [`trading::mint_safety::MintSafetyRejection`].
+    pub fn with_mint_safety(mut self, policy: trading::mint_safety::MintSafetyPolicy) -> Self {
+        self.mint_safety = Some(Arc::new(policy));
+        self
+    }
+
+    /// Cost basis and PnL per quote token of `mint` from the ledger (empty without one)
+    pub fn pnl(&self, mint: &Pubkey) -> Vec<trading::ledger::MintPnl> {
+        self.ledger.as_ref().map(|ledger| ledger.pnl(mint)).unwrap_or_default()
+    }
+
     /// Gets the RPC client instance for direct Solana blockchain interactions
     ///
     /// This provides access to the underlying Solana RPC client that can be used
@@ -596,6 +756,24 @@ impl TradingClient {
         &self.infrastructure.rpc
     }
 
+    /// Gets the SWQOS scoreboard (per-provider submit latency, errors and landing wins)
+    ///
+    /// Wins are only recorded for trades with `wait_transaction_confirmed = true`.
+    /// Call [`SwqosScoreboard::set_adaptive_top_k`] to only fan out to the best K providers
+    /// (`Default` is always kept) and reduce tip spend.
+    pub fn get_swqos_scoreboard(&self) -> &Arc<SwqosScoreboard> {
+        &self.infrastructure.swqos_scoreboard
+    }
+
+    /// Gets the SWQOS health monitor (periodic probes + circuit breaker)
+    ///
+    /// A provider is removed from the fan-out after consecutive submit/probe failures and
+    /// re-admitted after a cool-down. Use [`SwqosHealthMonitor::set_event_handler`] to receive
+    /// [`SwqosHealthEvent`]s and [`SwqosHealthMonitor::set_config`] to tune thresholds.
+    pub fn get_swqos_health(&self) -> &Arc<SwqosHealthMonitor> {
+        &self.infrastructure.swqos_health
+    }
+
     /// Gets the current globally shared SolanaTrade instance
     ///
     /// This provides access to the singleton instance that was created with `new()`.
@@ -658,60 +836,23 @@ impl TradingClient {
         }
         if params.input_token_type == TradeTokenType::USD1 && params.dex_type != DexType::Bonk {
             return Err(anyhow::anyhow!(
-                " Current version only supports USD1 trading on Bonk protocols"
+                " Current version only supports USD1 trading on Bonk protocols (use buy_two_hop to route through USD1)"
             ));
         }
-        let protocol_params = params.extension_params;
-        if !validate_protocol_params(params.dex_type, &protocol_params) {
+        if !validate_protocol_params(params.dex_type, &params.extension_params) {
             return Err(anyhow::anyhow!(
                 "Invalid protocol params for Trade (dex={:?})",
                 params.dex_type
             ));
         }
-        let input_token_mint = if params.input_token_type == TradeTokenType::SOL {
-            SOL_TOKEN_ACCOUNT
-        } else if params.input_token_type == TradeTokenType::WSOL {
-            WSOL_TOKEN_ACCOUNT
-        } else if params.input_token_type == TradeTokenType::USDC {
-            USDC_TOKEN_ACCOUNT
-        } else {
-            USD1_TOKEN_ACCOUNT
-        };
+        let commitment = self.pre_buy(&params).await?;
         let executor = TradeFactory::create_executor(params.dex_type);
-        let buy_params = SwapParams {
-            rpc: Some(self.infrastructure.rpc.clone()),
-            payer: self.payer.clone(),
-            trade_type: TradeType::Buy,
-            input_mint: input_token_mint,
-            output_mint: params.mint,
-            input_token_program: None,
-            output_token_program: None,
-            input_amount: Some(params.input_token_amount),
-            slippage_basis_points: params.slippage_basis_points,
-            address_lookup_table_account: params.address_lookup_table_account,
-            recent_blockhash: params.recent_blockhash,
-            wait_transaction_confirmed: params.wait_transaction_confirmed,
-            protocol_params,
-            open_seed_optimize: self.use_seed_optimize, // 使用全局seed优化配置
-            swqos_clients: self.infrastructure.swqos_clients.clone(),
-            middleware_manager: self.middleware_manager.clone(),
-            durable_nonce: params.durable_nonce,
-            with_tip: true,
-            create_input_mint_ata: params.create_input_token_ata,
-            close_input_mint_ata: params.close_input_token_ata,
-            create_output_mint_ata: params.create_mint_ata,
-            close_output_mint_ata: false,
-            fixed_output_amount: params.fixed_output_token_amount,
-            gas_fee_strategy: params.gas_fee_strategy,
-            simulate: params.simulate,
-            log_enabled: self.log_enabled,
-            use_core_affinity: self.use_core_affinity,
-            check_min_tip: self.check_min_tip,
-            grpc_recv_us: params.grpc_recv_us,
-            use_exact_sol_amount: params.use_exact_sol_amount,
-        };
+        let buy_params = self.buy_swap_params(params);
 
         let swap_result = executor.swap(buy_params).await;
+        if let Some(commitment) = commitment {
+            commitment.settle(&swap_result);
+        }
         let result =
             swap_result.map(|(success, sigs, err)| (success, sigs, err.map(TradeError::from)));
         return result;
@@ -762,58 +903,18 @@ impl TradingClient {
         }
         if params.output_token_type == TradeTokenType::USD1 && params.dex_type != DexType::Bonk {
             return Err(anyhow::anyhow!(
-                " Current version only supports USD1 trading on Bonk protocols"
+                " Current version only supports USD1 trading on Bonk protocols (use sell_two_hop to route through USD1)"
             ));
         }
-        let protocol_params = params.extension_params;
-        if !validate_protocol_params(params.dex_type, &protocol_params) {
+        if !validate_protocol_params(params.dex_type, &params.extension_params) {
             return Err(anyhow::anyhow!(
                 "Invalid protocol params for Trade (dex={:?})",
                 params.dex_type
             ));
         }
+        self.pre_sell(&params)?;
         let executor = TradeFactory::create_executor(params.dex_type);
-        let output_token_mint = if params.output_token_type == TradeTokenType::SOL {
-            SOL_TOKEN_ACCOUNT
-        } else if params.output_token_type == TradeTokenType::WSOL {
-            WSOL_TOKEN_ACCOUNT
-        } else if params.output_token_type == TradeTokenType::USDC {
-            USDC_TOKEN_ACCOUNT
-        } else {
-            USD1_TOKEN_ACCOUNT
-        };
-        let sell_params = SwapParams {
-            rpc: Some(self.infrastructure.rpc.clone()),
-            payer: self.payer.clone(),
-            trade_type: TradeType::Sell,
-            input_mint: params.mint,
-            output_mint: output_token_mint,
-            input_token_program: None,
-            output_token_program: None,
-            input_amount: Some(params.input_token_amount),
-            slippage_basis_points: params.slippage_basis_points,
-            address_lookup_table_account: params.address_lookup_table_account,
-            recent_blockhash: params.recent_blockhash,
-            wait_transaction_confirmed: params.wait_transaction_confirmed,
-            protocol_params,
-            with_tip: params.with_tip,
-            open_seed_optimize: self.use_seed_optimize, // 使用全局seed优化配置
-            swqos_clients: self.infrastructure.swqos_clients.clone(),
-            middleware_manager: self.middleware_manager.clone(),
-            durable_nonce: params.durable_nonce,
-            create_input_mint_ata: false,
-            close_input_mint_ata: params.close_mint_token_ata,
-            create_output_mint_ata: params.create_output_token_ata,
-            close_output_mint_ata: params.close_output_token_ata,
-            fixed_output_amount: params.fixed_output_token_amount,
-            gas_fee_strategy: params.gas_fee_strategy,
-            simulate: params.simulate,
-            log_enabled: self.log_enabled,
-            use_core_affinity: self.use_core_affinity,
-            check_min_tip: self.check_min_tip,
-            grpc_recv_us: params.grpc_recv_us,
-            use_exact_sol_amount: None,
-        };
+        let sell_params = self.sell_swap_params(params);
 
         let swap_result = executor.swap(sell_params).await;
         let result =
@@ -821,6 +922,312 @@ impl TradingClient {
         return result;
     }
 
+    /// Like `buy`, but follows launchpad migrations first: a `PumpFun` trade whose curve is
+    /// complete goes to the canonical PumpSwap pool, a migrated `Bonk` trade goes to its Raydium
+    /// CPMM / AMM v4 pool. `extension_params` are rebuilt from chain state for `PumpFun` / `Bonk`
+    /// (USD1 Bonk pools when `input_token_type` is `USD1`); other DEX types are passed through.
+    /// USD1 is only routed while the Bonk mint is on its curve: the CPMM / AMM v4 pools a USD1
+    /// pool migrates to cannot be traded in USD1, and the trade fails without sending.
+    pub async fn route_buy(
+        &self,
+        mut params: TradeBuyParams,
+    ) -> Result<(bool, Vec<Signature>, Option<TradeError>), anyhow::Error> {
+        if matches!(params.dex_type, DexType::PumpFun | DexType::Bonk) {
+            let venue = trading::migration::resolve_venue(
+                &self.infrastructure.rpc,
+                &params.mint,
+                params.dex_type,
+                params.input_token_type == TradeTokenType::USD1,
+            )
+            .await?;
+            if params.input_token_type == TradeTokenType::USD1 {
+                trading::migration::ensure_usd1_venue(venue.dex_type, &params.mint)?;
+            }
+            params.dex_type = venue.dex_type;
+            params.extension_params = venue.params;
+        }
+        self.buy(params).await
+    }
+
+    /// Like `sell`, but follows launchpad migrations first (see `route_buy`).
+    pub async fn route_sell(
+        &self,
+        mut params: TradeSellParams,
+    ) -> Result<(bool, Vec<Signature>, Option<TradeError>), anyhow::Error> {
+        if matches!(params.dex_type, DexType::PumpFun | DexType::Bonk) {
+            let venue = trading::migration::resolve_venue(
+                &self.infrastructure.rpc,
+                &params.mint,
+                params.dex_type,
+                params.output_token_type == TradeTokenType::USD1,
+            )
+            .await?;
+            if params.output_token_type == TradeTokenType::USD1 {
+                trading::migration::ensure_usd1_venue(venue.dex_type, &params.mint)?;
+            }
+            params.dex_type = venue.dex_type;
+            params.extension_params = venue.params;
+        }
+        self.sell(params).await
+    }
+
+    /// Best-price router over this client's RPC (all DEX types; narrow with `with_dex_types`).
+    pub fn router(&self) -> trading::router::Router {
+        trading::router::Router::new(self.infrastructure.rpc.clone())
+    }
+
+    /// Quote `params.mint` on every DEX and buy through the route with the highest output.
+    ///
+    /// `dex_type` / `extension_params` of `params` are replaced by the best route; the returned
+    /// table lists every alternative with its price impact. Fails without trading when no DEX
+    /// quotes the mint.
+    pub async fn buy_best_route(
+        &self,
+        mut params: TradeBuyParams,
+    ) -> Result<trading::router::RoutedTrade, anyhow::Error> {
+        let quote_mint = route_quote_mint(&params.input_token_type);
+        let table = self
+            .router()
+            .quote_buy(
+                &params.mint,
+                &quote_mint,
+                params.input_token_amount,
+                params.slippage_basis_points,
+            )
+            .await?;
+        let best = table
+            .best()
+            .ok_or_else(|| anyhow::anyhow!("No route quoted for mint {}", params.mint))?;
+        best.apply_to_buy(&mut params);
+        let (success, signatures, error) = self.buy(params).await?;
+        Ok(trading::router::RoutedTrade { table, success, signatures, error })
+    }
+
+    /// Quote `params.mint` on every DEX and sell through the route with the highest output
+    /// (see `buy_best_route`).
+    pub async fn sell_best_route(
+        &self,
+        mut params: TradeSellParams,
+    ) -> Result<trading::router::RoutedTrade, anyhow::Error> {
+        let quote_mint = route_quote_mint(&params.output_token_type);
+        let table = self
+            .router()
+            .quote_sell(
+                &params.mint,
+                &quote_mint,
+                params.input_token_amount,
+                params.slippage_basis_points,
+            )
+            .await?;
+        let best = table
+            .best()
+            .ok_or_else(|| anyhow::anyhow!("No route quoted for mint {}", params.mint))?;
+        best.apply_to_sell(&mut params);
+        let (success, signatures, error) = self.sell(params).await?;
+        Ok(trading::router::RoutedTrade { table, success, signatures, error })
+    }
+
+    /// Buy `params.mint` split across several pools in one transaction (see `trading::split`).
+    ///
+    /// Routes are quoted like `buy_best_route`, the input is spread to equalize marginal prices,
+    /// and legs are dropped until the transaction fits size / compute limits. Fails before
+    /// sending when the summed leg minimums are below `config.min_amount_out`.
+    pub async fn buy_split(
+        &self,
+        params: TradeBuyParams,
+        config: trading::split::SplitConfig,
+    ) -> Result<trading::split::SplitTrade, anyhow::Error> {
+        if params.recent_blockhash.is_none() && params.durable_nonce.is_none() {
+            return Err(anyhow::anyhow!(
+                "Must provide either recent_blockhash or durable_nonce for buy (required for transaction validity)"
+            ));
+        }
+        let commitment = self.pre_buy(&params).await?;
+        let quote_mint = route_quote_mint(&params.input_token_type);
+        let table = self
+            .router()
+            .quote_buy(
+                &params.mint,
+                &quote_mint,
+                params.input_token_amount,
+                params.slippage_basis_points,
+            )
+            .await?;
+        let swap_params = self.buy_swap_params(params);
+        self.execute_split(table, &config, swap_params, commitment).await
+    }
+
+    /// Sell `params.input_token_amount` of `params.mint` split across several pools in one
+    /// transaction (see `buy_split`).
+    pub async fn sell_split(
+        &self,
+        params: TradeSellParams,
+        config: trading::split::SplitConfig,
+    ) -> Result<trading::split::SplitTrade, anyhow::Error> {
+        if params.recent_blockhash.is_none() && params.durable_nonce.is_none() {
+            return Err(anyhow::anyhow!(
+                "Must provide either recent_blockhash or durable_nonce for sell (required for transaction validity)"
+            ));
+        }
+        self.pre_sell(&params)?;
+        let quote_mint = route_quote_mint(&params.output_token_type);
+        let table = self
+            .router()
+            .quote_sell(
+                &params.mint,
+                &quote_mint,
+                params.input_token_amount,
+                params.slippage_basis_points,
+            )
+            .await?;
+        let swap_params = self.sell_swap_params(params);
+        self.execute_split(table, &config, swap_params, None).await
+    }
+
+    /// Buy `params.mint` through an intermediate quote token in one transaction, e.g. a
+    /// USD1-quoted Bonk token with SOL (SOL -> USD1 -> token). See `trading::multi_hop`.
+    ///
+    /// The intermediate (WSOL / USDC / USD1) with the highest output is picked;
+    /// `dex_type` / `extension_params` of `params` are ignored. `slippage_basis_points` bounds
+    /// the end-to-end output.
+    pub async fn buy_two_hop(
+        &self,
+        params: TradeBuyParams,
+    ) -> Result<trading::multi_hop::TwoHopTrade, anyhow::Error> {
+        if params.recent_blockhash.is_none() && params.durable_nonce.is_none() {
+            return Err(anyhow::anyhow!(
+                "Must provide either recent_blockhash or durable_nonce for buy (required for transaction validity)"
+            ));
+        }
+        let commitment = self.pre_buy(&params).await?;
+        let input_mint = route_quote_mint(&params.input_token_type);
+        let route = trading::multi_hop::quote_two_hop(
+            &self.router(),
+            &input_mint,
+            &params.mint,
+            params.input_token_amount,
+            params.slippage_basis_points,
+        )
+        .await?;
+        let mut swap_params = self.buy_swap_params(params);
+        swap_params.landed_route_handler = self.trade_route_handler(
+            TradeType::Buy,
+            route.output_mint,
+            route.input_mint,
+            "TwoHop".to_string(),
+        );
+        let result = trading::multi_hop::execute_two_hop(&route, swap_params).await;
+        if let Some(commitment) = commitment {
+            commitment.settle(&result);
+        }
+        let (success, signatures, error) = result?;
+        Ok(trading::multi_hop::TwoHopTrade {
+            route,
+            success,
+            signatures,
+            error: error.map(TradeError::from),
+        })
+    }
+
+    /// Sell `params.mint` through an intermediate quote token in one transaction (see
+    /// `buy_two_hop`).
+    pub async fn sell_two_hop(
+        &self,
+        params: TradeSellParams,
+    ) -> Result<trading::multi_hop::TwoHopTrade, anyhow::Error> {
+        if params.recent_blockhash.is_none() && params.durable_nonce.is_none() {
+            return Err(anyhow::anyhow!(
+                "Must provide either recent_blockhash or durable_nonce for sell (required for transaction validity)"
+            ));
+        }
+        self.pre_sell(&params)?;
+        let output_mint = route_quote_mint(&params.output_token_type);
+        let route = trading::multi_hop::quote_two_hop(
+            &self.router(),
+            &params.mint,
+            &output_mint,
+            params.input_token_amount,
+            params.slippage_basis_points,
+        )
+        .await?;
+        let mut swap_params = self.sell_swap_params(params);
+        swap_params.landed_route_handler = self.trade_route_handler(
+            TradeType::Sell,
+            route.input_mint,
+            route.output_mint,
+            "TwoHop".to_string(),
+        );
+        let (success, signatures, error) =
+            trading::multi_hop::execute_two_hop(&route, swap_params).await?;
+        Ok(trading::multi_hop::TwoHopTrade {
+            route,
+            success,
+            signatures,
+            error: error.map(TradeError::from),
+        })
+    }
+
+    /// `buy` guarded against a price move since `params.extension_params` was captured (see
+    /// `trading::price_guard`). In `Rpc` mode the pool is re-read at `processed` commitment
+    /// and the buy is not sent when the price rose more than `guard.max_move_bps`; in `OnChain`
+    /// mode the slippage is capped at `guard.max_move_bps` instead, and buys the slippage does
+    /// not bound (fixed output, `MeteoraDammV2`) are refused.
+    pub async fn buy_with_guard(
+        &self,
+        mut params: TradeBuyParams,
+        guard: &trading::price_guard::PriceGuard,
+    ) -> Result<(bool, Vec<Signature>, Option<TradeError>), anyhow::Error> {
+        match guard.mode {
+            trading::price_guard::PriceGuardMode::Rpc => {
+                let check = trading::price_guard::check_price(
+                    &self.infrastructure.rpc,
+                    &params.extension_params,
+                    &params.mint,
+                    TradeType::Buy,
+                )
+                .await?;
+                guard.ensure(&check)?;
+            }
+            trading::price_guard::PriceGuardMode::OnChain => {
+                params.slippage_basis_points = guard.on_chain_slippage(
+                    &params.extension_params,
+                    params.fixed_output_token_amount,
+                    params.slippage_basis_points,
+                )?;
+            }
+        }
+        self.buy(params).await
+    }
+
+    /// `sell` guarded against a price drop since `params.extension_params` was captured (see
+    /// `buy_with_guard`).
+    pub async fn sell_with_guard(
+        &self,
+        mut params: TradeSellParams,
+        guard: &trading::price_guard::PriceGuard,
+    ) -> Result<(bool, Vec<Signature>, Option<TradeError>), anyhow::Error> {
+        match guard.mode {
+            trading::price_guard::PriceGuardMode::Rpc => {
+                let check = trading::price_guard::check_price(
+                    &self.infrastructure.rpc,
+                    &params.extension_params,
+                    &params.mint,
+                    TradeType::Sell,
+                )
+                .await?;
+                guard.ensure(&check)?;
+            }
+            trading::price_guard::PriceGuardMode::OnChain => {
+                params.slippage_basis_points = guard.on_chain_slippage(
+                    &params.extension_params,
+                    params.fixed_output_token_amount,
+                    params.slippage_basis_points,
+                )?;
+            }
+        }
+        self.sell(params).await
+    }
+
     /// Execute a sell order for a percentage of the specified token amount
     ///
     /// This is a convenience function that calculates the exact amount to sell based on
@@ -1065,4 +1472,1422 @@ impl TradingClient {
         let signature = self.infrastructure.rpc.send_and_confirm_transaction(&transaction).await?;
         Ok(signature.to_string())
     }
+
+    /// Launch a token on Bonk (Raydium LaunchLab) with the payer as creator.
+    ///
+    /// Creates the mint (`mint` must be a fresh keypair), its metadata and the bonding-curve pool via
+    /// `initialize_v2`, quoted in SOL or USD1 depending on `params.global_config`. When
+    /// `params.initial_buy_amount` is set, the creator's buy is appended to the same transaction so it
+    /// is the first trade on the curve.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Invalid global config, build or send failure
+    pub async fn create_bonk_token(
+        &self,
+        mint: &Keypair,
+        params: trading::core::params::BonkCreateTokenParams,
+    ) -> Result<String, anyhow::Error> {
+        use crate::instruction::utils::bonk::accounts;
+        use solana_sdk::transaction::Transaction;
+
+        if params.global_config != accounts::GLOBAL_CONFIG
+            && params.global_config != accounts::USD1_GLOBAL_CONFIG
+        {
+            return Err(anyhow::anyhow!(
+                "Unsupported Bonk global config {}, expected SOL or USD1 global config",
+                params.global_config
+            ));
+        }
+        let usd1_pool = params.is_usd1_pool();
+        let payer = self.payer.pubkey();
+
+        let mut instructions: Vec<solana_sdk::instruction::Instruction> =
+            crate::trading::common::compute_budget_instructions(
+                params.compute_unit_price,
+                params.compute_unit_limit,
+            )
+            .into_vec();
+        instructions.push(crate::instruction::bonk::initialize_v2_instruction(
+            &payer,
+            &mint.pubkey(),
+            &params,
+        )?);
+
+        if let Some(amount_in) = params.initial_buy_amount.filter(|&a| a > 0) {
+            if !usd1_pool {
+                instructions.extend(crate::trading::common::handle_wsol(&payer, amount_in));
+            }
+            instructions.extend(
+                crate::common::fast_fn::create_associated_token_account_idempotent_fast_use_seed(
+                    &payer,
+                    &payer,
+                    &mint.pubkey(),
+                    &crate::constants::TOKEN_PROGRAM,
+                    self.use_seed_optimize,
+                ),
+            );
+            instructions.push(crate::instruction::bonk::initial_buy_instruction(
+                &payer,
+                &mint.pubkey(),
+                &params,
+                self.use_seed_optimize,
+            )?);
+            if !usd1_pool {
+                instructions.extend(crate::trading::common::close_wsol(&payer));
+            }
+        }
+
+        let recent_blockhash = self.infrastructure.rpc.get_latest_blockhash().await?;
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer));
+        transaction.sign(&[&*self.payer, mint], recent_blockhash);
+        let signature = self.infrastructure.rpc.send_and_confirm_transaction(&transaction).await?;
+        Ok(signature.to_string())
+    }
+
+    /// Create a Bonk (LaunchLab) vesting record giving `beneficiary` `share_amount` of the pool's
+    /// locked tokens. The payer must be the pool creator.
+    ///
+    /// * `quote_mint` - WSOL or USD1, selects the pool of `mint`
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure
+    pub async fn create_vesting_account_bonk(
+        &self,
+        mint: &Pubkey,
+        quote_mint: &Pubkey,
+        beneficiary: &Pubkey,
+        share_amount: u64,
+    ) -> Result<String, anyhow::Error> {
+        let pool_state = crate::instruction::utils::bonk::get_pool_pda(mint, quote_mint)
+            .ok_or_else(|| anyhow::anyhow!("Failed to derive Bonk pool PDA"))?;
+        let ix = crate::instruction::bonk::create_vesting_account_instruction(
+            &self.payer.pubkey(),
+            beneficiary,
+            &pool_state,
+            share_amount,
+        )?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Claim the payer's unlocked Bonk (LaunchLab) vested tokens of `mint` into its token ATA.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure (e.g. pool not migrated or still in cliff)
+    pub async fn claim_vested_token_bonk(
+        &self,
+        mint: &Pubkey,
+        quote_mint: &Pubkey,
+    ) -> Result<String, anyhow::Error> {
+        let pool_state = crate::instruction::utils::bonk::get_pool_pda(mint, quote_mint)
+            .ok_or_else(|| anyhow::anyhow!("Failed to derive Bonk pool PDA"))?;
+        let ix = crate::instruction::bonk::claim_vested_token_instruction(
+            &self.payer.pubkey(),
+            &pool_state,
+            mint,
+        )?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Claim the payer's Bonk (LaunchLab) creator fees accumulated in `quote_mint`.
+    ///
+    /// Fees land in the payer's `quote_mint` ATA (WSOL stays wrapped, see `close_wsol`).
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure
+    pub async fn claim_creator_fee_bonk(
+        &self,
+        quote_mint: &Pubkey,
+    ) -> Result<String, anyhow::Error> {
+        let ix = crate::instruction::bonk::claim_creator_fee_instruction(
+            &self.payer.pubkey(),
+            quote_mint,
+        )?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Claim Bonk (LaunchLab) platform fees of `platform_config` accumulated in `quote_mint`.
+    /// The payer must be the platform's fee wallet.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure
+    pub async fn claim_platform_fee_bonk(
+        &self,
+        platform_config: &Pubkey,
+        quote_mint: &Pubkey,
+    ) -> Result<String, anyhow::Error> {
+        let ix = crate::instruction::bonk::claim_platform_fee_instruction(
+            &self.payer.pubkey(),
+            platform_config,
+            quote_mint,
+        )?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Collect PumpFun creator fees from the payer's `creator_vault` to the payer (native SOL).
+    ///
+    /// For coins with a fee sharing config the vault belongs to the config instead; use
+    /// `distribute_creator_fees_pumpfun` there.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure
+    pub async fn collect_creator_fee_pumpfun(&self) -> Result<String, anyhow::Error> {
+        let ix = crate::instruction::pumpfun::collect_creator_fee_instruction(&self.payer.pubkey())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Failed to build PumpFun collect_creator_fee instruction")
+            })?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Create the fee sharing config of `mint`; the payer must be the coin creator and becomes
+    /// the config admin. Pass the PumpSwap `pool` if the coin has already migrated.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure
+    pub async fn create_fee_sharing_config_pumpfun(
+        &self,
+        mint: &Pubkey,
+        pool: Option<&Pubkey>,
+    ) -> Result<String, anyhow::Error> {
+        let ix = crate::instruction::pumpfun::create_fee_sharing_config_instruction(
+            &self.payer.pubkey(),
+            mint,
+            pool,
+        )
+        .ok_or_else(|| anyhow::anyhow!("Failed to build create_fee_sharing_config instruction"))?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Replace the fee shares of `mint` (payer must be the config admin). Shares must sum to
+    /// 10_000 bps; the current shareholders are read on-chain and paid out first by the program.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Invalid shares, missing sharing config, build or send failure
+    pub async fn update_fee_shares_pumpfun(
+        &self,
+        mint: &Pubkey,
+        shareholders: &[crate::instruction::utils::pumpfun::Shareholder],
+    ) -> Result<String, anyhow::Error> {
+        let total_bps: u32 = shareholders.iter().map(|s| s.share_bps as u32).sum();
+        if shareholders.is_empty() || total_bps != 10_000 {
+            return Err(anyhow::anyhow!(
+                "Fee shares must sum to 10000 bps, got {} across {} shareholders",
+                total_bps,
+                shareholders.len()
+            ));
+        }
+        let (sharing_config, _) = crate::instruction::utils::pumpfun::fetch_sharing_config(
+            &self.infrastructure.rpc,
+            mint,
+        )
+        .await?;
+        let current: Vec<Pubkey> = sharing_config.shareholders.iter().map(|s| s.address).collect();
+        let ix = crate::instruction::pumpfun::update_fee_shares_instruction(
+            &self.payer.pubkey(),
+            mint,
+            shareholders,
+            &current,
+        )
+        .ok_or_else(|| anyhow::anyhow!("Failed to build update_fee_shares instruction"))?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Permanently revoke the payer's admin rights over `mint`'s fee sharing config.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure
+    pub async fn revoke_fee_sharing_authority_pumpfun(
+        &self,
+        mint: &Pubkey,
+    ) -> Result<String, anyhow::Error> {
+        let ix = crate::instruction::pumpfun::revoke_fee_sharing_authority_instruction(
+            &self.payer.pubkey(),
+            mint,
+        )
+        .ok_or_else(|| {
+            anyhow::anyhow!("Failed to build revoke_fee_sharing_authority instruction")
+        })?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Distribute the creator fees of a fee-shared `mint` to its shareholders (anyone may call).
+    ///
+    /// Fails on-chain while the vault is below the minimum, see `get_minimum_distributable_fee_pumpfun`.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Missing sharing config, build or send failure
+    pub async fn distribute_creator_fees_pumpfun(
+        &self,
+        mint: &Pubkey,
+    ) -> Result<String, anyhow::Error> {
+        let (sharing_config, _) = crate::instruction::utils::pumpfun::fetch_sharing_config(
+            &self.infrastructure.rpc,
+            mint,
+        )
+        .await?;
+        let shareholders: Vec<Pubkey> =
+            sharing_config.shareholders.iter().map(|s| s.address).collect();
+        let ix =
+            crate::instruction::pumpfun::distribute_creator_fees_instruction(mint, &shareholders)
+                .ok_or_else(|| {
+                anyhow::anyhow!("Failed to build distribute_creator_fees instruction")
+            })?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Query the distributable creator fees of a fee-shared `mint` by simulating
+    /// `get_minimum_distributable_fee` (no transaction is sent).
+    pub async fn get_minimum_distributable_fee_pumpfun(
+        &self,
+        mint: &Pubkey,
+    ) -> Result<crate::instruction::utils::pumpfun::MinimumDistributableFee, anyhow::Error> {
+        use base64::Engine;
+        use borsh::BorshDeserialize;
+        use solana_client::rpc_config::RpcSimulateTransactionConfig;
+        use solana_sdk::transaction::Transaction;
+
+        let ix = crate::instruction::pumpfun::get_minimum_distributable_fee_instruction(mint)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Failed to build get_minimum_distributable_fee instruction")
+            })?;
+        let recent_blockhash = self.infrastructure.rpc.get_latest_blockhash().await?;
+        let mut transaction = Transaction::new_with_payer(&[ix], Some(&self.payer.pubkey()));
+        transaction.sign(&[&*self.payer], recent_blockhash);
+        let result = self
+            .infrastructure
+            .rpc
+            .simulate_transaction_with_config(
+                &transaction,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    ..Default::default()
+                },
+            )
+            .await?
+            .value;
+        if let Some(err) = result.err {
+            return Err(anyhow::anyhow!(
+                "get_minimum_distributable_fee simulation failed: {:?}",
+                err
+            ));
+        }
+        let return_data = result
+            .return_data
+            .ok_or_else(|| anyhow::anyhow!("get_minimum_distributable_fee returned no data"))?;
+        let data = base64::engine::general_purpose::STANDARD.decode(&return_data.data.0)?;
+        crate::instruction::utils::pumpfun::MinimumDistributableFee::try_from_slice(&data)
+            .map_err(|e| anyhow::anyhow!("Failed to decode minimum distributable fee: {}", e))
+    }
+
+    /// Collect the payer's PumpSwap coin-creator fees accumulated in `quote_mint`.
+    ///
+    /// Creates the payer's `quote_mint` ATA idempotently, then collects. With `unwrap_wsol` and a
+    /// WSOL quote, the WSOL ATA is closed in the same transaction so fees (and any other WSOL
+    /// balance in that ATA) arrive as native SOL.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure
+    pub async fn collect_coin_creator_fee_pumpswap(
+        &self,
+        quote_mint: &Pubkey,
+        quote_token_program: &Pubkey,
+        unwrap_wsol: bool,
+    ) -> Result<String, anyhow::Error> {
+        let payer = self.payer.pubkey();
+        let mut instructions =
+            crate::common::fast_fn::create_associated_token_account_idempotent_fast_use_seed(
+                &payer,
+                &payer,
+                quote_mint,
+                quote_token_program,
+                false,
+            );
+        instructions.push(crate::instruction::pumpswap::collect_coin_creator_fee_instruction(
+            &payer,
+            *quote_mint,
+            *quote_token_program,
+        ));
+        if unwrap_wsol && *quote_mint == WSOL_TOKEN_ACCOUNT {
+            instructions.extend(crate::trading::common::close_wsol(&payer));
+        }
+        self.send_payer_transaction(&instructions).await
+    }
+
+    /// Move `coin_creator`'s PumpSwap WSOL creator fees into its PumpFun creator vault, so a
+    /// fee-shared coin can distribute them with `distribute_creator_fees_pumpfun` (anyone may call).
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure
+    pub async fn transfer_creator_fees_to_pump_pumpswap(
+        &self,
+        coin_creator: &Pubkey,
+    ) -> Result<String, anyhow::Error> {
+        let ix =
+            crate::instruction::pumpswap::transfer_creator_fees_to_pump_instruction(coin_creator)
+                .ok_or_else(|| {
+                anyhow::anyhow!("Failed to build transfer_creator_fees_to_pump instruction")
+            })?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Create `user`'s volume accumulator ahead of time, rent paid by the payer.
+    ///
+    /// Buys otherwise create the accumulator on first use, which adds rent and an account
+    /// allocation to the hot path; calling this when onboarding a wallet keeps both out of it.
+    ///
+    /// Supported: `DexType::PumpFun`, `DexType::PumpSwap`.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Unsupported DEX, accumulator already exists, build or send failure
+    pub async fn init_user_volume_accumulator(
+        &self,
+        dex_type: DexType,
+        user: &Pubkey,
+    ) -> Result<String, anyhow::Error> {
+        let payer = self.payer.pubkey();
+        let (accumulator, ix) = match dex_type {
+            DexType::PumpFun => (
+                crate::instruction::utils::pumpfun::get_user_volume_accumulator_pda(user),
+                crate::instruction::pumpfun::init_user_volume_accumulator_instruction(&payer, user),
+            ),
+            DexType::PumpSwap => (
+                crate::instruction::utils::pumpswap::get_user_volume_accumulator_pda(user),
+                crate::instruction::pumpswap::init_user_volume_accumulator_instruction(
+                    &payer, user,
+                ),
+            ),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "init_user_volume_accumulator not implemented for {:?}",
+                    dex_type
+                ))
+            }
+        };
+        let accumulator = accumulator
+            .ok_or_else(|| anyhow::anyhow!("Failed to derive user volume accumulator"))?;
+        let ix = ix.ok_or_else(|| {
+            anyhow::anyhow!("Failed to build init_user_volume_accumulator instruction")
+        })?;
+        if self.infrastructure.rpc.get_account(&accumulator).await.is_ok() {
+            return Err(anyhow::anyhow!("User volume accumulator {} already exists", accumulator));
+        }
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Sync `user`'s volume accumulator with the program's global accumulator (permissionless).
+    ///
+    /// Supported: `DexType::PumpFun`, `DexType::PumpSwap`.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Unsupported DEX, build or send failure
+    pub async fn sync_user_volume_accumulator(
+        &self,
+        dex_type: DexType,
+        user: &Pubkey,
+    ) -> Result<String, anyhow::Error> {
+        let ix = match dex_type {
+            DexType::PumpFun => {
+                crate::instruction::pumpfun::sync_user_volume_accumulator_instruction(user)
+            }
+            DexType::PumpSwap => {
+                crate::instruction::pumpswap::sync_user_volume_accumulator_instruction(user)
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "sync_user_volume_accumulator not implemented for {:?}",
+                    dex_type
+                ))
+            }
+        }
+        .ok_or_else(|| {
+            anyhow::anyhow!("Failed to build sync_user_volume_accumulator instruction")
+        })?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Close the payer's volume accumulator and reclaim its rent, e.g. when retiring a wallet.
+    ///
+    /// The accumulator tracks unclaimed token incentives, so claim those before closing it.
+    ///
+    /// Supported: `DexType::PumpFun`, `DexType::PumpSwap`.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Unsupported DEX, build or send failure
+    pub async fn close_user_volume_accumulator(
+        &self,
+        dex_type: DexType,
+    ) -> Result<String, anyhow::Error> {
+        let payer = self.payer.pubkey();
+        let ix = match dex_type {
+            DexType::PumpFun => {
+                crate::instruction::pumpfun::close_user_volume_accumulator_instruction(&payer)
+            }
+            DexType::PumpSwap => {
+                crate::instruction::pumpswap::close_user_volume_accumulator_instruction(&payer)
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "close_user_volume_accumulator not implemented for {:?}",
+                    dex_type
+                ))
+            }
+        }
+        .ok_or_else(|| {
+            anyhow::anyhow!("Failed to build close_user_volume_accumulator instruction")
+        })?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Read `user`'s claimable incentive tokens and cashback from the volume accumulators.
+    ///
+    /// Claimable tokens include the share of the last traded day once that day has ended, i.e.
+    /// what `claim_token_incentives` would transfer now; cashback is what `claim_cashback_pumpfun`
+    /// / `claim_cashback_pumpswap` would transfer.
+    ///
+    /// Supported: `DexType::PumpFun`, `DexType::PumpSwap`.
+    ///
+    /// # Returns
+    /// * `Ok(TokenIncentives)` - `user` is `None` when the user has no accumulator yet
+    /// * `Err(anyhow::Error)` - Unsupported DEX or RPC/decode failure
+    pub async fn get_token_incentives(
+        &self,
+        dex_type: DexType,
+        user: &Pubkey,
+    ) -> Result<crate::instruction::utils::pumpfun::TokenIncentives, anyhow::Error> {
+        use crate::instruction::utils::pumpfun::{
+            fetch_global_volume_accumulator, TokenIncentives, UserVolumeAccumulator,
+        };
+        use crate::instruction::utils::{pumpfun, pumpswap};
+
+        let rpc = &self.infrastructure.rpc;
+        let (user_volume_accumulator, global_address) = match dex_type {
+            DexType::PumpFun => (
+                pumpfun::get_user_volume_accumulator_pda(user),
+                pumpfun::accounts::GLOBAL_VOLUME_ACCUMULATOR,
+            ),
+            DexType::PumpSwap => (
+                pumpswap::get_user_volume_accumulator_pda(user),
+                pumpswap::accounts::GLOBAL_VOLUME_ACCUMULATOR,
+            ),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "get_token_incentives not implemented for {:?}",
+                    dex_type
+                ))
+            }
+        };
+        let user_volume_accumulator = user_volume_accumulator
+            .ok_or_else(|| anyhow::anyhow!("Failed to derive user volume accumulator"))?;
+        let global = fetch_global_volume_accumulator(rpc, &global_address).await?;
+        let account = rpc.get_account(&user_volume_accumulator).await.ok();
+        let user_accumulator =
+            account.as_ref().and_then(|a| UserVolumeAccumulator::from_account_data(&a.data));
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let claimable_tokens =
+            user_accumulator.as_ref().map(|u| u.claimable_tokens(&global, now)).unwrap_or(0);
+        let claimable_cashback = match (dex_type, account) {
+            // Pump keeps cashback as lamports on the accumulator itself
+            (DexType::PumpFun, Some(account)) => {
+                let rent = rpc.get_minimum_balance_for_rent_exemption(account.data.len()).await?;
+                account.lamports.saturating_sub(rent)
+            }
+            // PumpSwap keeps it in the accumulator's WSOL ATA
+            (DexType::PumpSwap, Some(_)) => {
+                match pumpswap::get_user_volume_accumulator_wsol_ata(user) {
+                    Some(ata) => match rpc.get_token_account_balance(&ata).await {
+                        Ok(balance) => balance.amount.parse::<u64>().unwrap_or(0),
+                        Err(_) => 0,
+                    },
+                    None => 0,
+                }
+            }
+            _ => 0,
+        };
+        Ok(TokenIncentives {
+            user_volume_accumulator,
+            user: user_accumulator,
+            global,
+            claimable_tokens,
+            claimable_cashback,
+        })
+    }
+
+    /// Claim the payer's incentive tokens into its ATA for the incentive mint.
+    ///
+    /// Use `get_token_incentives` first to check that anything is claimable.
+    ///
+    /// Supported: `DexType::PumpFun`, `DexType::PumpSwap`.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Unsupported DEX, RPC, build or send failure
+    pub async fn claim_token_incentives(&self, dex_type: DexType) -> Result<String, anyhow::Error> {
+        use crate::instruction::utils::pumpfun::fetch_global_volume_accumulator;
+        use crate::instruction::utils::{pumpfun, pumpswap};
+
+        let rpc = &self.infrastructure.rpc;
+        let payer = self.payer.pubkey();
+        let global_address = match dex_type {
+            DexType::PumpFun => pumpfun::accounts::GLOBAL_VOLUME_ACCUMULATOR,
+            DexType::PumpSwap => pumpswap::accounts::GLOBAL_VOLUME_ACCUMULATOR,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "claim_token_incentives not implemented for {:?}",
+                    dex_type
+                ))
+            }
+        };
+        let mint = fetch_global_volume_accumulator(rpc, &global_address).await?.mint;
+        let token_program = rpc.get_account(&mint).await?.owner;
+        let ix = if dex_type == DexType::PumpFun {
+            crate::instruction::pumpfun::claim_token_incentives_instruction(
+                &payer,
+                &payer,
+                &mint,
+                &token_program,
+            )
+        } else {
+            crate::instruction::pumpswap::claim_token_incentives_instruction(
+                &payer,
+                &payer,
+                &mint,
+                &token_program,
+            )
+        }
+        .ok_or_else(|| anyhow::anyhow!("Failed to build claim_token_incentives instruction"))?;
+        self.send_payer_transaction(&[ix]).await
+    }
+
+    /// Add liquidity to `pool_address`, minting `lp_token_amount` LP tokens.
+    ///
+    /// Base/quote amounts follow the current reserves; the deposit fails on-chain if it would take
+    /// more than `slippage_basis_points` (default `DEFAULT_SLIPPAGE`) above them. A WSOL side is
+    /// wrapped from SOL in the same transaction and leftovers are unwrapped again.
+    ///
+    /// Supported: `DexType::PumpSwap`, `DexType::RaydiumCpmm`, `DexType::RaydiumAmmV4` (for AMM v4
+    /// the coin side of a deposit is fixed and only the pc side is bounded).
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Unsupported DEX, pool fetch, build or send failure
+    pub async fn add_liquidity(
+        &self,
+        dex_type: DexType,
+        pool_address: &Pubkey,
+        lp_token_amount: u64,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<String, anyhow::Error> {
+        if lp_token_amount == 0 {
+            return Err(anyhow::anyhow!("LP token amount cannot be zero"));
+        }
+        let slippage_basis_points =
+            slippage_basis_points.unwrap_or(crate::constants::trade::trade::DEFAULT_SLIPPAGE);
+        let instructions = match dex_type {
+            DexType::PumpSwap => {
+                self.pumpswap_liquidity_instructions(
+                    pool_address,
+                    lp_token_amount,
+                    slippage_basis_points,
+                    true,
+                )
+                .await?
+            }
+            DexType::RaydiumCpmm => {
+                self.raydium_cpmm_liquidity_instructions(
+                    pool_address,
+                    lp_token_amount,
+                    slippage_basis_points,
+                    true,
+                )
+                .await?
+            }
+            DexType::RaydiumAmmV4 => {
+                self.raydium_amm_v4_liquidity_instructions(
+                    pool_address,
+                    lp_token_amount,
+                    slippage_basis_points,
+                    true,
+                )
+                .await?
+            }
+            _ => return Err(anyhow::anyhow!("add_liquidity not implemented for {:?}", dex_type)),
+        };
+        self.send_payer_transaction(&instructions).await
+    }
+
+    /// Remove liquidity from `pool_address` by burning `lp_token_amount` LP tokens.
+    ///
+    /// Fails on-chain if either side returns less than `slippage_basis_points` (default
+    /// `DEFAULT_SLIPPAGE`) below the current-reserve amounts. A WSOL side is unwrapped to SOL.
+    ///
+    /// Supported: `DexType::PumpSwap`, `DexType::RaydiumCpmm`, `DexType::RaydiumAmmV4`.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Unsupported DEX, pool fetch, build or send failure
+    pub async fn remove_liquidity(
+        &self,
+        dex_type: DexType,
+        pool_address: &Pubkey,
+        lp_token_amount: u64,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<String, anyhow::Error> {
+        if lp_token_amount == 0 {
+            return Err(anyhow::anyhow!("LP token amount cannot be zero"));
+        }
+        let slippage_basis_points =
+            slippage_basis_points.unwrap_or(crate::constants::trade::trade::DEFAULT_SLIPPAGE);
+        let instructions = match dex_type {
+            DexType::PumpSwap => {
+                self.pumpswap_liquidity_instructions(
+                    pool_address,
+                    lp_token_amount,
+                    slippage_basis_points,
+                    false,
+                )
+                .await?
+            }
+            DexType::RaydiumCpmm => {
+                self.raydium_cpmm_liquidity_instructions(
+                    pool_address,
+                    lp_token_amount,
+                    slippage_basis_points,
+                    false,
+                )
+                .await?
+            }
+            DexType::RaydiumAmmV4 => {
+                self.raydium_amm_v4_liquidity_instructions(
+                    pool_address,
+                    lp_token_amount,
+                    slippage_basis_points,
+                    false,
+                )
+                .await?
+            }
+            _ => {
+                return Err(anyhow::anyhow!("remove_liquidity not implemented for {:?}", dex_type))
+            }
+        };
+        self.send_payer_transaction(&instructions).await
+    }
+
+    /// Create a PumpSwap pool owned by the payer and seed it with the initial liquidity.
+    ///
+    /// The pool address is `instruction::utils::pumpswap::get_pool_pda(index, payer, base, quote)`;
+    /// the payer receives the initial LP tokens. A WSOL quote is wrapped from SOL in the same transaction.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Transaction signature
+    /// * `Err(anyhow::Error)` - Build or send failure
+    pub async fn create_pool(
+        &self,
+        params: trading::core::params::PumpSwapCreatePoolParams,
+    ) -> Result<String, anyhow::Error> {
+        if params.base_amount_in == 0 || params.quote_amount_in == 0 {
+            return Err(anyhow::anyhow!("Initial pool liquidity cannot be zero"));
+        }
+        let payer = self.payer.pubkey();
+        let (ix, _pool) = crate::instruction::pumpswap::create_pool_instruction(&payer, &params)
+            .ok_or_else(|| anyhow::anyhow!("Failed to build PumpSwap create_pool instruction"))?;
+        let mut instructions: Vec<solana_sdk::instruction::Instruction> =
+            crate::trading::common::compute_budget_instructions(0, 400_000).into_vec();
+        for (mint, amount) in
+            [(params.base_mint, params.base_amount_in), (params.quote_mint, params.quote_amount_in)]
+        {
+            if mint == WSOL_TOKEN_ACCOUNT {
+                instructions.extend(crate::trading::common::handle_wsol(&payer, amount));
+            }
+        }
+        instructions.push(ix);
+        if params.base_mint == WSOL_TOKEN_ACCOUNT || params.quote_mint == WSOL_TOKEN_ACCOUNT {
+            instructions.extend(crate::trading::common::close_wsol(&payer));
+        }
+        self.send_payer_transaction(&instructions).await
+    }
+
+    /// Deposit (`deposit == true`) or withdraw instructions for a PumpSwap pool, including ATA
+    /// setup and WSOL wrap/unwrap.
+    async fn pumpswap_liquidity_instructions(
+        &self,
+        pool_address: &Pubkey,
+        lp_token_amount: u64,
+        slippage_basis_points: u64,
+        deposit: bool,
+    ) -> Result<Vec<solana_sdk::instruction::Instruction>, anyhow::Error> {
+        use crate::instruction::utils::pumpswap;
+        use crate::utils::calc::common::{lp_deposit_amounts, lp_withdraw_amounts};
+
+        let rpc = &self.infrastructure.rpc;
+        let payer = self.payer.pubkey();
+        let pool = pumpswap::fetch_pool(rpc, pool_address).await?;
+        let (base_reserve, quote_reserve) = pumpswap::get_token_balances(&pool, rpc).await?;
+        let (base_token_program, quote_token_program) =
+            pumpswap::get_pool_token_programs(pool_address, &pool);
+        let mut sides =
+            [(pool.base_mint, base_token_program, 0), (pool.quote_mint, quote_token_program, 0)];
+        let ix = if deposit {
+            let amounts = lp_deposit_amounts(
+                lp_token_amount,
+                slippage_basis_points,
+                base_reserve,
+                quote_reserve,
+                pool.lp_supply,
+            )
+            .map_err(|e| anyhow::anyhow!(e))?;
+            (sides[0].2, sides[1].2) = (amounts.max_base, amounts.max_quote);
+            crate::instruction::pumpswap::deposit_instruction(
+                &payer,
+                pool_address,
+                &pool,
+                &base_token_program,
+                &quote_token_program,
+                &amounts,
+            )
+        } else {
+            let amounts = lp_withdraw_amounts(
+                lp_token_amount,
+                slippage_basis_points,
+                base_reserve,
+                quote_reserve,
+                pool.lp_supply,
+            )
+            .map_err(|e| anyhow::anyhow!(e))?;
+            crate::instruction::pumpswap::withdraw_instruction(
+                &payer,
+                pool_address,
+                &pool,
+                &base_token_program,
+                &quote_token_program,
+                &amounts,
+            )
+        };
+        Ok(self.with_liquidity_accounts(
+            ix,
+            deposit,
+            sides,
+            (&pool.lp_mint, &crate::constants::TOKEN_PROGRAM_2022),
+        ))
+    }
+
+    /// Deposit or withdraw instructions for a Raydium CPMM pool.
+    async fn raydium_cpmm_liquidity_instructions(
+        &self,
+        pool_address: &Pubkey,
+        lp_token_amount: u64,
+        slippage_basis_points: u64,
+        deposit: bool,
+    ) -> Result<Vec<solana_sdk::instruction::Instruction>, anyhow::Error> {
+        use crate::utils::calc::common::{lp_deposit_amounts, lp_withdraw_amounts};
+
+        let rpc = &self.infrastructure.rpc;
+        let payer = self.payer.pubkey();
+        let pool =
+            crate::instruction::utils::raydium_cpmm::fetch_pool_state(rpc, pool_address).await?;
+        let (vault0_amount, vault1_amount) =
+            trading::common::get_multi_token_balances(rpc, &pool.token0_vault, &pool.token1_vault)
+                .await?;
+        let (token0_reserve, token1_reserve) =
+            crate::utils::calc::raydium_cpmm::lp_reserves(vault0_amount, vault1_amount, &pool);
+        let mut sides = [
+            (pool.token0_mint, pool.token0_program, 0),
+            (pool.token1_mint, pool.token1_program, 0),
+        ];
+        let ix = if deposit {
+            let amounts = lp_deposit_amounts(
+                lp_token_amount,
+                slippage_basis_points,
+                token0_reserve,
+                token1_reserve,
+                pool.lp_supply,
+            )
+            .map_err(|e| anyhow::anyhow!(e))?;
+            (sides[0].2, sides[1].2) = (amounts.max_base, amounts.max_quote);
+            crate::instruction::raydium_cpmm::deposit_instruction(
+                &payer,
+                pool_address,
+                &pool,
+                &amounts,
+            )
+        } else {
+            let amounts = lp_withdraw_amounts(
+                lp_token_amount,
+                slippage_basis_points,
+                token0_reserve,
+                token1_reserve,
+                pool.lp_supply,
+            )
+            .map_err(|e| anyhow::anyhow!(e))?;
+            crate::instruction::raydium_cpmm::withdraw_instruction(
+                &payer,
+                pool_address,
+                &pool,
+                &amounts,
+            )
+        };
+        Ok(self.with_liquidity_accounts(
+            ix,
+            deposit,
+            sides,
+            (&pool.lp_mint, &crate::constants::TOKEN_PROGRAM),
+        ))
+    }
+
+    /// Deposit or withdraw instructions for a Raydium AMM v4 pool.
+    ///
+    /// Deposits fix the coin side at its current-reserve share of `lp_token_amount`; only the pc
+    /// side carries the slippage bound.
+    async fn raydium_amm_v4_liquidity_instructions(
+        &self,
+        amm: &Pubkey,
+        lp_token_amount: u64,
+        slippage_basis_points: u64,
+        deposit: bool,
+    ) -> Result<Vec<solana_sdk::instruction::Instruction>, anyhow::Error> {
+        use crate::instruction::utils::raydium_amm_v4;
+        use crate::utils::calc::common::{lp_deposit_amounts, lp_withdraw_amounts};
+
+        let rpc = &self.infrastructure.rpc;
+        let payer = self.payer.pubkey();
+        let amm_info = raydium_amm_v4::fetch_amm_info(rpc, *amm).await?;
+        let market = raydium_amm_v4::fetch_market_accounts(rpc, &amm_info).await?;
+        let (coin_vault_amount, pc_vault_amount) = trading::common::get_multi_token_balances(
+            rpc,
+            &amm_info.token_coin,
+            &amm_info.token_pc,
+        )
+        .await?;
+        let (coin_reserve, pc_reserve) = crate::utils::calc::raydium_amm_v4::lp_reserves(
+            coin_vault_amount,
+            pc_vault_amount,
+            &amm_info,
+        );
+        let mut sides = [
+            (amm_info.coin_mint, crate::constants::TOKEN_PROGRAM, 0),
+            (amm_info.pc_mint, crate::constants::TOKEN_PROGRAM, 0),
+        ];
+        let ix = if deposit {
+            let amounts = lp_deposit_amounts(
+                lp_token_amount,
+                slippage_basis_points,
+                coin_reserve,
+                pc_reserve,
+                amm_info.lp_amount,
+            )
+            .map_err(|e| anyhow::anyhow!(e))?;
+            (sides[0].2, sides[1].2) = (amounts.base, amounts.max_quote);
+            crate::instruction::raydium_amm_v4::deposit_instruction(
+                &payer, amm, &amm_info, &market, &amounts,
+            )
+        } else {
+            let amounts = lp_withdraw_amounts(
+                lp_token_amount,
+                slippage_basis_points,
+                coin_reserve,
+                pc_reserve,
+                amm_info.lp_amount,
+            )
+            .map_err(|e| anyhow::anyhow!(e))?;
+            crate::instruction::raydium_amm_v4::withdraw_instruction(
+                &payer, amm, &amm_info, &market, &amounts,
+            )
+        };
+        Ok(self.with_liquidity_accounts(
+            ix,
+            deposit,
+            sides,
+            (&amm_info.lp_mint, &crate::constants::TOKEN_PROGRAM),
+        ))
+    }
+
+    /// Surround a deposit/withdraw instruction with the token account setup it needs.
+    ///
+    /// `sides` are `(mint, token_program, max_amount_in)`. Deposits wrap `max_amount_in` SOL for a
+    /// WSOL side and create the LP ATA; withdrawals create both token ATAs. Any WSOL left over is
+    /// unwrapped afterwards.
+    fn with_liquidity_accounts(
+        &self,
+        ix: solana_sdk::instruction::Instruction,
+        deposit: bool,
+        sides: [(Pubkey, Pubkey, u64); 2],
+        (lp_mint, lp_token_program): (&Pubkey, &Pubkey),
+    ) -> Vec<solana_sdk::instruction::Instruction> {
+        use crate::common::fast_fn::create_associated_token_account_idempotent_fast_use_seed;
+
+        let payer = self.payer.pubkey();
+        let mut instructions = Vec::with_capacity(8);
+        if deposit {
+            for (mint, _, max_amount_in) in sides {
+                if mint == WSOL_TOKEN_ACCOUNT {
+                    instructions.extend(crate::trading::common::handle_wsol(&payer, max_amount_in));
+                }
+            }
+            instructions.extend(create_associated_token_account_idempotent_fast_use_seed(
+                &payer,
+                &payer,
+                lp_mint,
+                lp_token_program,
+                false,
+            ));
+        } else {
+            for (mint, token_program, _) in sides {
+                instructions.extend(create_associated_token_account_idempotent_fast_use_seed(
+                    &payer,
+                    &payer,
+                    &mint,
+                    &token_program,
+                    false,
+                ));
+            }
+        }
+        instructions.push(ix);
+        if sides.iter().any(|(mint, _, _)| *mint == WSOL_TOKEN_ACCOUNT) {
+            instructions.extend(crate::trading::common::close_wsol(&payer));
+        }
+        instructions
+    }
+
+    /// Checks every buy entry point runs before building: the mint safety policy (see
+    /// `trading::mint_safety`), then risk limits (see `trading::risk`). The returned commitment
+    /// holds the SOL the buy committed until it is settled with the outcome. Simulations are
+    /// not checked against the risk limits and commit nothing.
+    async fn pre_buy(
+        &self,
+        params: &TradeBuyParams,
+    ) -> Result<Option<trading::risk::BuyCommitment>, anyhow::Error> {
+        if let Some(policy) = &self.mint_safety {
+            let report =
+                trading::mint_safety::check_mint(&self.infrastructure.rpc, &params.mint).await?;
+            policy.enforce(&report)?;
+        }
+        let Some(risk) = self.risk.as_ref().filter(|_| !params.simulate) else {
+            return Ok(None);
+        };
+        let sol_amount =
+            matches!(params.input_token_type, TradeTokenType::SOL | TradeTokenType::WSOL)
+                .then_some(params.input_token_amount);
+        let tip = trading::risk::max_tip(&params.gas_fee_strategy, TradeType::Buy);
+        risk.check_buy(&params.mint, sol_amount, tip)?;
+        Ok(sol_amount
+            .map(|amount| trading::risk::BuyCommitment::new(risk.clone(), params.mint, amount)))
+    }
+
+    /// Checks every sell entry point runs before building (see `pre_buy`).
+    fn pre_sell(&self, params: &TradeSellParams) -> Result<(), anyhow::Error> {
+        if let Some(risk) = &self.risk {
+            let tip = if params.with_tip {
+                trading::risk::max_tip(&params.gas_fee_strategy, TradeType::Sell)
+            } else {
+                0.0
+            };
+            risk.check_sell(
+                &params.mint,
+                tip,
+                params.input_token_amount,
+                params.close_mint_token_ata,
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn execute_split(
+        &self,
+        table: trading::router::RouteTable,
+        config: &trading::split::SplitConfig,
+        mut swap_params: SwapParams,
+        commitment: Option<trading::risk::BuyCommitment>,
+    ) -> Result<trading::split::SplitTrade, anyhow::Error> {
+        swap_params.landed_route_handler = self.trade_route_handler(
+            table.trade_type,
+            table.mint,
+            table.quote_mint,
+            "Split".to_string(),
+        );
+        let plan = trading::split::plan_within_limits(&table, config, &swap_params).await?;
+        if let Some(min_amount_out) = config.min_amount_out {
+            if plan.min_amount_out < min_amount_out {
+                return Err(anyhow::anyhow!(
+                    "Split min output {} is below the required {}",
+                    plan.min_amount_out,
+                    min_amount_out
+                ));
+            }
+        }
+        let result = trading::split::execute_plan(&plan, swap_params).await;
+        if let Some(commitment) = commitment {
+            commitment.settle(&result);
+        }
+        let (success, signatures, error) = result?;
+        Ok(trading::split::SplitTrade {
+            plan,
+            table,
+            success,
+            signatures,
+            error: error.map(TradeError::from),
+        })
+    }
+
+    /// The client's landed-route handler, recording the trade to the ledger when one is set.
+    fn trade_route_handler(
+        &self,
+        side: TradeType,
+        mint: Pubkey,
+        quote_mint: Pubkey,
+        dex: String,
+    ) -> Option<LandedRouteHandler> {
+        match &self.ledger {
+            Some(ledger) => Some(trading::ledger::recording_handler(
+                ledger.clone(),
+                self.infrastructure.rpc.clone(),
+                self.payer.pubkey(),
+                trading::ledger::TradeRecord { side, mint, quote_mint, dex },
+                self.landed_route_handler.clone(),
+            )),
+            None => self.landed_route_handler.clone(),
+        }
+    }
+
+    /// `SwapParams` for a buy described by `params` (no validation).
+    fn buy_swap_params(&self, params: TradeBuyParams) -> SwapParams {
+        let input_token_mint = if params.input_token_type == TradeTokenType::SOL {
+            SOL_TOKEN_ACCOUNT
+        } else if params.input_token_type == TradeTokenType::WSOL {
+            WSOL_TOKEN_ACCOUNT
+        } else if params.input_token_type == TradeTokenType::USDC {
+            USDC_TOKEN_ACCOUNT
+        } else {
+            USD1_TOKEN_ACCOUNT
+        };
+        SwapParams {
+            rpc: Some(self.infrastructure.rpc.clone()),
+            payer: self.payer.clone(),
+            trade_type: TradeType::Buy,
+            input_mint: input_token_mint,
+            output_mint: params.mint,
+            input_token_program: None,
+            output_token_program: None,
+            input_amount: Some(params.input_token_amount),
+            slippage_basis_points: params.slippage_basis_points,
+            address_lookup_table_account: params.address_lookup_table_account,
+            recent_blockhash: params.recent_blockhash,
+            wait_transaction_confirmed: params.wait_transaction_confirmed,
+            protocol_params: params.extension_params,
+            open_seed_optimize: self.use_seed_optimize, // 使用全局seed优化配置
+            swqos_clients: self.infrastructure.swqos_clients.load_full(),
+            swqos_scoreboard: Some(self.infrastructure.swqos_scoreboard.clone()),
+            swqos_health: Some(self.infrastructure.swqos_health.clone()),
+            middleware_manager: self.middleware_manager.clone(),
+            durable_nonce: params.durable_nonce,
+            with_tip: true,
+            create_input_mint_ata: params.create_input_token_ata,
+            close_input_mint_ata: params.close_input_token_ata,
+            create_output_mint_ata: params.create_mint_ata,
+            close_output_mint_ata: false,
+            fixed_output_amount: params.fixed_output_token_amount,
+            gas_fee_strategy: params.gas_fee_strategy,
+            simulate: params.simulate,
+            log_enabled: self.log_enabled,
+            use_core_affinity: self.use_core_affinity,
+            check_min_tip: self.check_min_tip,
+            grpc_recv_us: params.grpc_recv_us,
+            use_exact_sol_amount: params.use_exact_sol_amount,
+            landed_route_handler: self.trade_route_handler(
+                TradeType::Buy,
+                params.mint,
+                route_quote_mint(&params.input_token_type),
+                format!("{:?}", params.dex_type),
+            ),
+        }
+    }
+
+    /// `SwapParams` for a sell described by `params` (no validation).
+    fn sell_swap_params(&self, params: TradeSellParams) -> SwapParams {
+        let output_token_mint = if params.output_token_type == TradeTokenType::SOL {
+            SOL_TOKEN_ACCOUNT
+        } else if params.output_token_type == TradeTokenType::WSOL {
+            WSOL_TOKEN_ACCOUNT
+        } else if params.output_token_type == TradeTokenType::USDC {
+            USDC_TOKEN_ACCOUNT
+        } else {
+            USD1_TOKEN_ACCOUNT
+        };
+        SwapParams {
+            rpc: Some(self.infrastructure.rpc.clone()),
+            payer: self.payer.clone(),
+            trade_type: TradeType::Sell,
+            input_mint: params.mint,
+            output_mint: output_token_mint,
+            input_token_program: None,
+            output_token_program: None,
+            input_amount: Some(params.input_token_amount),
+            slippage_basis_points: params.slippage_basis_points,
+            address_lookup_table_account: params.address_lookup_table_account,
+            recent_blockhash: params.recent_blockhash,
+            wait_transaction_confirmed: params.wait_transaction_confirmed,
+            protocol_params: params.extension_params,
+            with_tip: params.with_tip,
+            open_seed_optimize: self.use_seed_optimize, // 使用全局seed优化配置
+            swqos_clients: self.infrastructure.swqos_clients.load_full(),
+            swqos_scoreboard: Some(self.infrastructure.swqos_scoreboard.clone()),
+            swqos_health: Some(self.infrastructure.swqos_health.clone()),
+            middleware_manager: self.middleware_manager.clone(),
+            durable_nonce: params.durable_nonce,
+            create_input_mint_ata: false,
+            close_input_mint_ata: params.close_mint_token_ata,
+            create_output_mint_ata: params.create_output_token_ata,
+            close_output_mint_ata: params.close_output_token_ata,
+            fixed_output_amount: params.fixed_output_token_amount,
+            gas_fee_strategy: params.gas_fee_strategy,
+            simulate: params.simulate,
+            log_enabled: self.log_enabled,
+            use_core_affinity: self.use_core_affinity,
+            check_min_tip: self.check_min_tip,
+            grpc_recv_us: params.grpc_recv_us,
+            use_exact_sol_amount: None,
+            landed_route_handler: self.trade_route_handler(
+                TradeType::Sell,
+                params.mint,
+                route_quote_mint(&params.output_token_type),
+                format!("{:?}", params.dex_type),
+            ),
+        }
+    }
+
+    /// Sign `instructions` with the payer only, send and wait for confirmation.
+    async fn send_payer_transaction(
+        &self,
+        instructions: &[solana_sdk::instruction::Instruction],
+    ) -> Result<String, anyhow::Error> {
+        use solana_sdk::transaction::Transaction;
+        let recent_blockhash = self.infrastructure.rpc.get_latest_blockhash().await?;
+        let mut transaction = Transaction::new_with_payer(instructions, Some(&self.payer.pubkey()));
+        transaction.sign(&[&*self.payer], recent_blockhash);
+        let signature = self.infrastructure.rpc.send_and_confirm_transaction(&transaction).await?;
+        Ok(signature.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swqos::SwqosRegion;
+    use solana_commitment_config::CommitmentConfig;
+
+    /// Infrastructure without SWQOS clients; nothing here talks to the RPC node.
+    fn infrastructure() -> TradingInfrastructure {
+        let config = InfrastructureConfig::new(
+            "http://127.0.0.1:8899".to_string(),
+            vec![],
+            CommitmentConfig::processed(),
+        );
+        TradingInfrastructure {
+            rpc: Arc::new(SolanaRpcClient::new(config.rpc_url.clone())),
+            swqos_clients: Arc::new(ArcSwap::from_pointee(Vec::new())),
+            swqos_scoreboard: Arc::new(SwqosScoreboard::default()),
+            swqos_health: Arc::new(SwqosHealthMonitor::default()),
+            config,
+        }
+    }
+
+    /// SOL buy of a fresh mint, ready to send.
+    fn buy_params() -> TradeBuyParams {
+        TradeBuyParams {
+            dex_type: DexType::PumpFun,
+            input_token_type: TradeTokenType::SOL,
+            mint: Pubkey::new_unique(),
+            input_token_amount: 1_000_000,
+            slippage_basis_points: Some(100),
+            recent_blockhash: Some(Hash::default()),
+            extension_params: DexParamEnum::PumpFun(PumpFunParams::immediate_sell(
+                Pubkey::default(),
+                crate::constants::TOKEN_PROGRAM,
+                false,
+            )),
+            address_lookup_table_account: None,
+            wait_transaction_confirmed: true,
+            create_input_token_ata: true,
+            close_input_token_ata: true,
+            create_mint_ata: true,
+            durable_nonce: None,
+            fixed_output_token_amount: None,
+            gas_fee_strategy: GasFeeStrategy::new(),
+            simulate: false,
+            use_exact_sol_amount: None,
+            grpc_recv_us: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn best_route_trades_fail_when_no_route_is_quoted() {
+        let client = TradingClient::from_infrastructure(
+            Arc::new(Keypair::new()),
+            Arc::new(infrastructure()),
+            true,
+        );
+        // Nothing serves the RPC URL, so every DEX is skipped.
+        let params = buy_params();
+        let mint = params.mint;
+        let err = client.buy_best_route(params).await.err().expect("buy without a route");
+        assert!(err.to_string().starts_with("No route"), "{err}");
+        let sell = TradeSellParams {
+            dex_type: DexType::PumpFun,
+            output_token_type: TradeTokenType::SOL,
+            mint,
+            input_token_amount: 1_000_000,
+            slippage_basis_points: Some(100),
+            recent_blockhash: Some(Hash::default()),
+            with_tip: false,
+            extension_params: buy_params().extension_params,
+            address_lookup_table_account: None,
+            wait_transaction_confirmed: true,
+            create_output_token_ata: true,
+            close_output_token_ata: true,
+            close_mint_token_ata: false,
+            durable_nonce: None,
+            fixed_output_token_amount: None,
+            gas_fee_strategy: GasFeeStrategy::new(),
+            simulate: false,
+            grpc_recv_us: None,
+        };
+        let err = client.sell_best_route(sell).await.err().expect("sell without a route");
+        assert!(err.to_string().starts_with("No route"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn swqos_clients_can_be_added_replaced_and_removed() {
+        let infrastructure = infrastructure();
+        let jito_tokyo = SwqosConfig::Jito(String::new(), SwqosRegion::Tokyo, None);
+        infrastructure.add_swqos(jito_tokyo).await.unwrap();
+        let zeroslot = SwqosConfig::ZeroSlot("token".to_string(), SwqosRegion::Frankfurt, None);
+        infrastructure.add_swqos(zeroslot).await.unwrap();
+        // What an in-flight trade holds on to.
+        let snapshot = infrastructure.swqos_clients();
+        assert_eq!(snapshot.len(), 2);
+
+        let jito_new_york = SwqosConfig::Jito(String::new(), SwqosRegion::NewYork, None);
+        infrastructure.replace_swqos(jito_new_york).await.unwrap();
+        let jito_regions: Vec<SwqosRegion> = infrastructure
+            .swqos_clients()
+            .iter()
+            .filter(|c| c.get_swqos_type() == SwqosType::Jito)
+            .map(|c| c.client_key().region)
+            .collect();
+        assert_eq!(jito_regions, vec![SwqosRegion::NewYork]);
+
+        assert_eq!(infrastructure.remove_swqos(SwqosType::ZeroSlot), 1);
+        assert_eq!(infrastructure.remove_swqos(SwqosType::ZeroSlot), 0);
+        assert_eq!(infrastructure.swqos_clients().len(), 1);
+
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].client_key().region, SwqosRegion::Tokyo);
+    }
+
+    #[tokio::test]
+    async fn failed_add_leaves_the_client_set_untouched() {
+        let infrastructure = infrastructure();
+        let blacklisted = SwqosConfig::NextBlock(String::new(), SwqosRegion::Frankfurt, None);
+        assert!(infrastructure.add_swqos(blacklisted.clone()).await.is_err());
+        assert!(infrastructure.replace_swqos(blacklisted).await.is_err());
+        assert!(infrastructure.swqos_clients().is_empty());
+    }
+
+    #[tokio::test]
+    async fn split_and_two_hop_buys_are_checked_against_the_risk_limits() {
+        let risk = Arc::new(trading::risk::RiskEngine::new(trading::risk::RiskLimits {
+            max_open_positions: Some(1),
+            ..Default::default()
+        }));
+        let open = Pubkey::new_unique();
+        risk.check_buy(&open, Some(1), 0.0).unwrap();
+        let client = TradingClient::from_infrastructure(
+            Arc::new(Keypair::new()),
+            Arc::new(infrastructure()),
+            true,
+        )
+        .with_risk_limits(risk.clone());
+        let params = buy_params();
+        let rejection = trading::risk::RiskRejection::OpenPositions { open: 1, max: 1 };
+
+        let err = client
+            .buy_split(params.clone(), trading::split::SplitConfig::default())
+            .await
+            .err()
+            .expect("split buy over the open-position limit");
+        assert_eq!(err.downcast_ref::<trading::risk::RiskRejection>(), Some(&rejection));
+        let err = client.buy_two_hop(params).await.err().expect("two-hop buy over the limit");
+        assert_eq!(err.downcast_ref::<trading::risk::RiskRejection>(), Some(&rejection));
+        assert_eq!(risk.open_positions(), 1);
+    }
+
+    #[tokio::test]
+    async fn simulated_buys_commit_nothing_against_the_risk_limits() {
+        let risk = Arc::new(trading::risk::RiskEngine::new(trading::risk::RiskLimits {
+            max_sol_per_mint: Some(1_000_000),
+            max_open_positions: Some(1),
+            max_trades_per_minute: Some(1),
+            ..Default::default()
+        }));
+        let client = TradingClient::from_infrastructure(
+            Arc::new(Keypair::new()),
+            Arc::new(infrastructure()),
+            true,
+        )
+        .with_risk_limits(risk.clone());
+        let params = TradeBuyParams { simulate: true, ..buy_params() };
+        let mint = params.mint;
+        // What client creation falls back to when the RPC node cannot report rents.
+        crate::common::seed::set_default_rents();
+
+        // Nothing serves the RPC URL, so the simulation itself fails; either way it is a dry run.
+        let _ = client.buy(params.clone()).await;
+        let _ = client.buy(params).await;
+        assert_eq!(risk.committed(&mint), 0);
+        assert_eq!(risk.open_positions(), 0);
+        // The one allowed trade of the minute is still available.
+        assert!(risk.check_buy(&mint, Some(1_000_000), 0.0).is_ok());
+    }
+
+    #[tokio::test]
+    async fn split_and_two_hop_buys_run_the_mint_safety_check_first() {
+        let risk = Arc::new(trading::risk::RiskEngine::new(trading::risk::RiskLimits {
+            max_trades_per_minute: Some(1),
+            ..Default::default()
+        }));
+        let client = TradingClient::from_infrastructure(
+            Arc::new(Keypair::new()),
+            Arc::new(infrastructure()),
+            true,
+        )
+        .with_mint_safety(Default::default())
+        .with_risk_limits(risk.clone());
+
+        // Nothing serves the RPC URL, so the mint cannot be read.
+        let err = client
+            .buy_split(buy_params(), trading::split::SplitConfig::default())
+            .await
+            .err()
+            .expect("split buy of an unreadable mint");
+        assert!(err.downcast_ref::<trading::risk::RiskRejection>().is_none());
+        assert!(client.buy_two_hop(buy_params()).await.is_err());
+        // Neither buy reached the risk check, so the one allowed trade is still available.
+        assert!(risk.check_buy(&Pubkey::new_unique(), None, 0.0).is_ok());
+    }
 }
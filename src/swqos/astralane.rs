@@ -1,4 +1,6 @@
-use crate::swqos::common::{default_http_client_builder, poll_transaction_confirmation};
+use crate::swqos::common::{
+    default_http_client_builder, poll_transaction_confirmation, SubmitResponse,
+};
 use rand::seq::IndexedRandom;
 use reqwest::Client;
 use std::{sync::Arc, time::Instant};
@@ -47,7 +49,7 @@ impl SwqosClientTrait for AstralaneClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         self.send_transaction_impl(trade_type, transaction, wait_confirmation).await
     }
 
@@ -58,7 +60,9 @@ impl SwqosClientTrait for AstralaneClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction_impl(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction_impl(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
@@ -74,6 +78,15 @@ impl SwqosClientTrait for AstralaneClient {
     fn get_swqos_type(&self) -> SwqosType {
         SwqosType::Astralane
     }
+
+    async fn health_check(&self) -> Result<()> {
+        match &self.backend {
+            AstralaneBackend::Http { endpoint, auth_token, http_client, .. } => {
+                Self::send_ping_request(http_client, endpoint, auth_token).await
+            }
+            AstralaneBackend::Quic(_) => Ok(()),
+        }
+    }
 }
 
 impl AstralaneClient {
@@ -175,13 +188,13 @@ impl AstralaneClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let signature = transaction.get_signature();
         let body_bytes = bincode_serialize(transaction)
             .map_err(|e| anyhow::anyhow!("Astralane binary serialize failed: {}", e))?;
 
-        match &self.backend {
+        let submit_response = match &self.backend {
             AstralaneBackend::Http { endpoint, auth_token, http_client, .. } => {
                 let response = http_client
                     .post(endpoint)
@@ -190,19 +203,25 @@ impl AstralaneClient {
                     .body(body_bytes)
                     .send()
                     .await?;
-                let status = response.status();
-                let _ = response.bytes().await;
-                if status.is_success() {
+                let http_status = response.status().as_u16();
+                let response_text = response.text().await.unwrap_or_default();
+                let submit_response =
+                    SubmitResponse::from_http(http_status, &response_text, *signature);
+                if submit_response.is_accepted() {
                     info!(target: "sol_trade_sdk", "[astralane] {} submitted: {:?}", trade_type, start_time.elapsed());
                 } else {
-                    error!(target: "sol_trade_sdk", "[astralane] {} submission failed: status {}", trade_type, status);
-                    return Err(anyhow::anyhow!("Astralane sendTransaction failed: {}", status));
+                    error!(target: "sol_trade_sdk", "[astralane] {} submission failed: {}", trade_type, submit_response);
                 }
+                submit_response
             }
             AstralaneBackend::Quic(quic) => {
                 quic.send_transaction(&body_bytes).await?;
                 info!(target: "sol_trade_sdk", "[astralane-quic] {} submitted: {:?}", trade_type, start_time.elapsed());
+                SubmitResponse::accepted(*signature, None)
             }
+        };
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         let start_time = Instant::now();
@@ -218,7 +237,7 @@ impl AstralaneClient {
             info!(target: "sol_trade_sdk", "signature: {:?}", signature);
             info!(target: "sol_trade_sdk", "[astralane] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
-        Ok(())
+        Ok(submit_response)
     }
 }
 
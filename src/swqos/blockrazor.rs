@@ -1,5 +1,6 @@
 use crate::swqos::common::{
-    default_http_client_builder, poll_transaction_confirmation, serialize_transaction_and_encode,
+    default_http_client_builder, log_submit_response, poll_transaction_confirmation,
+    serialize_transaction_and_encode, SubmitResponse,
 };
 use rand::seq::IndexedRandom;
 use reqwest::Client;
@@ -35,7 +36,7 @@ impl SwqosClientTrait for BlockRazorClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         self.send_transaction(trade_type, transaction, wait_confirmation).await
     }
 
@@ -59,6 +60,10 @@ impl SwqosClientTrait for BlockRazorClient {
     fn get_swqos_type(&self) -> SwqosType {
         SwqosType::BlockRazor
     }
+
+    async fn health_check(&self) -> Result<()> {
+        Self::send_ping_request(&self.http_client, &self.endpoint, &self.auth_token).await
+    }
 }
 
 impl BlockRazorClient {
@@ -154,7 +159,7 @@ impl BlockRazorClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let (content, signature) =
             serialize_transaction_and_encode(transaction, UiTransactionEncoding::Base64)?;
@@ -168,25 +173,12 @@ impl BlockRazorClient {
             .send()
             .await?;
 
-        let status = response.status();
-        if status.is_success() {
-            let _ = response.bytes().await;
-            if crate::common::sdk_log::sdk_log_enabled() {
-                println!(" [blockrazor] {} submitted: {:?}", trade_type, start_time.elapsed());
-            }
-        } else {
-            let body = response.text().await.unwrap_or_default();
-            if crate::common::sdk_log::sdk_log_enabled() {
-                eprintln!(
-                    " [blockrazor] {} submission failed: status {} body: {}",
-                    trade_type, status, body
-                );
-            }
-            return Err(anyhow::anyhow!(
-                "BlockRazor sendTransaction failed: status {} body: {}",
-                status,
-                body
-            ));
+        let http_status = response.status().as_u16();
+        let response_text = response.text().await.unwrap_or_default();
+        let submit_response = SubmitResponse::from_http(http_status, &response_text, signature);
+        log_submit_response("blockrazor", trade_type, &submit_response, start_time.elapsed());
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         let start_time = Instant::now();
@@ -209,7 +201,7 @@ impl BlockRazorClient {
             println!(" [blockrazor] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
 
-        Ok(())
+        Ok(submit_response)
     }
 
     pub async fn send_transactions(
@@ -219,7 +211,9 @@ impl BlockRazorClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
@@ -1,6 +1,9 @@
 use crate::swqos::common::default_http_client_builder;
+use crate::swqos::common::log_submit_response;
 use crate::swqos::common::poll_transaction_confirmation;
+use crate::swqos::common::probe_http_endpoint;
 use crate::swqos::common::serialize_transaction_and_encode;
+use crate::swqos::common::SubmitResponse;
 use crate::swqos::serialization;
 use rand::seq::IndexedRandom;
 use reqwest::Client;
@@ -31,7 +34,7 @@ impl SwqosClientTrait for BloxrouteClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         self.send_transaction(trade_type, transaction, wait_confirmation).await
     }
 
@@ -55,6 +58,10 @@ impl SwqosClientTrait for BloxrouteClient {
     fn get_swqos_type(&self) -> SwqosType {
         SwqosType::Bloxroute
     }
+
+    async fn health_check(&self) -> Result<()> {
+        probe_http_endpoint(&self.http_client, &self.endpoint).await
+    }
 }
 
 impl BloxrouteClient {
@@ -73,7 +80,7 @@ impl BloxrouteClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let (content, signature) =
             serialize_transaction_and_encode(transaction, UiTransactionEncoding::Base64)?;
@@ -85,28 +92,21 @@ impl BloxrouteClient {
         );
 
         let endpoint = format!("{}/api/v2/submit", self.endpoint);
-        let response_text = self
+        let response = self
             .http_client
             .post(&endpoint)
             .body(body)
             .header("Content-Type", "application/json")
             .header("Authorization", self.auth_token.as_str())
             .send()
-            .await?
-            .text()
             .await?;
+        let http_status = response.status().as_u16();
+        let response_text = response.text().await?;
 
-        // Parse with from_str to avoid extra wait from .json().await
-        if let Ok(response_json) = serde_json::from_str::<serde_json::Value>(&response_text) {
-            if crate::common::sdk_log::sdk_log_enabled() {
-                if response_json.get("result").is_some() {
-                    println!(" [bloxroute] {} submitted: {:?}", trade_type, start_time.elapsed());
-                } else if let Some(_error) = response_json.get("error") {
-                    eprintln!(" [bloxroute] {} submission failed: {:?}", trade_type, _error);
-                }
-            }
-        } else if crate::common::sdk_log::sdk_log_enabled() {
-            eprintln!(" [bloxroute] {} submission failed: {:?}", trade_type, response_text);
+        let submit_response = SubmitResponse::from_http(http_status, &response_text, signature);
+        log_submit_response("bloxroute", trade_type, &submit_response, start_time.elapsed());
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         let start_time: Instant = Instant::now();
@@ -129,7 +129,7 @@ impl BloxrouteClient {
             println!(" [bloxroute] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
 
-        Ok(())
+        Ok(submit_response)
     }
 
     pub async fn send_transactions(
@@ -152,26 +152,23 @@ impl BloxrouteClient {
         let body = format!(r#"{{"entries":[{}]}}"#, entries);
 
         let endpoint = format!("{}/api/v2/submit-batch", self.endpoint);
-        let response_text = self
+        let response = self
             .http_client
             .post(&endpoint)
             .body(body)
             .header("Content-Type", "application/json")
             .header("Authorization", self.auth_token.as_str())
             .send()
-            .await?
-            .text()
             .await?;
-
-        if crate::common::sdk_log::sdk_log_enabled() {
-            if let Ok(response_json) = serde_json::from_str::<serde_json::Value>(&response_text) {
-                if response_json.get("result").is_some() {
-                    println!(" bloxroute {} submitted: {:?}", trade_type, start_time.elapsed());
-                } else if let Some(_error) = response_json.get("error") {
-                    eprintln!(" bloxroute {} submission failed: {:?}", trade_type, _error);
-                }
-            }
-        }
+        let http_status = response.status().as_u16();
+        let response_text = response.text().await?;
+
+        let batch_signature =
+            transactions.first().and_then(|tx| tx.signatures.first().copied()).unwrap_or_default();
+        let submit_response =
+            SubmitResponse::from_http(http_status, &response_text, batch_signature);
+        log_submit_response("bloxroute", trade_type, &submit_response, start_time.elapsed());
+        submit_response.into_result()?;
 
         Ok(())
     }
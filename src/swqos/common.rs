@@ -32,6 +32,19 @@ const HTTP_TIMEOUT_MS: u64 = 3000;
 /// Connect timeout (milliseconds). 连接超时（毫秒）。
 const HTTP_CONNECT_TIMEOUT_MS: u64 = 2000;
 
+/// Reachability probe for HTTP SWQOS endpoints: any HTTP response (even 404/405) means the
+/// endpoint is up; only connect errors and timeouts fail. The error names only the host, since
+/// some endpoint URLs carry the provider API key. 端点可达性探测。
+pub async fn probe_http_endpoint(http_client: &Client, endpoint: &str) -> Result<()> {
+    http_client.get(endpoint).send().await.map(|_| ()).map_err(|e| {
+        anyhow::anyhow!(
+            "endpoint {} unreachable: {}",
+            super::endpoint_host(endpoint),
+            e.without_url()
+        )
+    })
+}
+
 /// Shared HTTP client builder for SWQOS clients; call `.build().unwrap()` or override pool first. SWQOS 共用 HTTP 客户端构建器。
 pub fn default_http_client_builder() -> reqwest::ClientBuilder {
     Client::builder()
@@ -71,6 +84,134 @@ impl From<anyhow::Error> for TradeError {
     }
 }
 
+/// Outcome of one SWQOS submit: accepted (with signature) or rejected by the provider.
+/// Transport failures (timeout, connect error) are still returned as `Err`. SWQOS 提交结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubmitResponse {
+    /// Signature the provider accepted; `None` when rejected.
+    pub signature: Option<Signature>,
+    /// HTTP status of the submit request; `None` for QUIC / RPC transports.
+    pub http_status: Option<u16>,
+    /// Provider error code (JSON-RPC `error.code` or equivalent).
+    pub error_code: Option<i64>,
+    /// Provider error message.
+    pub error_message: Option<String>,
+    /// Provider answered with HTTP 429 or a rate-limit error.
+    pub rate_limited: bool,
+}
+
+impl SubmitResponse {
+    pub fn accepted(signature: Signature, http_status: Option<u16>) -> Self {
+        Self { signature: Some(signature), http_status, ..Default::default() }
+    }
+
+    pub fn rejected(
+        http_status: Option<u16>,
+        error_code: Option<i64>,
+        error_message: String,
+    ) -> Self {
+        let msg = error_message.to_ascii_lowercase();
+        let rate_limited = http_status == Some(429)
+            || error_code == Some(429)
+            || msg.contains("rate limit")
+            || msg.contains("too many requests");
+        Self {
+            signature: None,
+            http_status,
+            error_code,
+            error_message: Some(error_message),
+            rate_limited,
+        }
+    }
+
+    /// Provider accepted the transaction (it may still fail to land).
+    #[inline]
+    pub fn is_accepted(&self) -> bool {
+        self.signature.is_some()
+    }
+
+    /// Parse an HTTP submit response. Handles JSON-RPC (`result` / `error{code,message}`),
+    /// `{"signature"}` / `{"reason"}` style bodies and plain text; non-2xx is always a rejection.
+    pub fn from_http(http_status: u16, body: &str, signature: Signature) -> Self {
+        let status = Some(http_status);
+        let json = serde_json::from_str::<serde_json::Value>(body).ok();
+        if let Some(error) = json.as_ref().and_then(|j| j.get("error")).filter(|e| !e.is_null()) {
+            let code = error.get("code").and_then(|c| c.as_i64());
+            let message = match error.get("message").and_then(|m| m.as_str()) {
+                Some(m) => m.to_string(),
+                None => error.as_str().map(str::to_string).unwrap_or_else(|| error.to_string()),
+            };
+            return Self::rejected(status, code, message);
+        }
+        if let Some(reason) = json.as_ref().and_then(|j| j.get("reason")).and_then(|r| r.as_str()) {
+            return Self::rejected(status, None, reason.to_string());
+        }
+        if !(200..300).contains(&http_status) {
+            let message =
+                if body.is_empty() { format!("HTTP {}", http_status) } else { body.to_string() };
+            return Self::rejected(status, None, message);
+        }
+        Self::accepted(signature, status)
+    }
+
+    /// Treat a provider rejection as an error (for call sites that only care about success).
+    pub fn into_result(self) -> Result<Signature> {
+        match self.signature {
+            Some(signature) => Ok(signature),
+            None => Err(anyhow::anyhow!("{}", self)),
+        }
+    }
+}
+
+impl std::fmt::Display for SubmitResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.signature, &self.error_message) {
+            (Some(sig), _) => write!(f, "accepted {}", sig),
+            (None, msg) => {
+                write!(f, "rejected")?;
+                if let Some(status) = self.http_status {
+                    write!(f, " (HTTP {})", status)?;
+                }
+                if let Some(code) = self.error_code {
+                    write!(f, " code {}", code)?;
+                }
+                write!(f, ": {}", msg.as_deref().unwrap_or("unknown error"))
+            }
+        }
+    }
+}
+
+/// Log a submit outcome via `tracing` (target `sol_trade_sdk`, only when SDK logging is on).
+/// Rejections are a `warn` with status and code; the provider's error body, which may echo
+/// request details, is only logged at `debug`.
+pub fn log_submit_response(
+    provider: &str,
+    trade_type: crate::swqos::TradeType,
+    response: &SubmitResponse,
+    elapsed: Duration,
+) {
+    if !crate::common::sdk_log::sdk_log_enabled() {
+        return;
+    }
+    if response.is_accepted() {
+        tracing::info!(target: "sol_trade_sdk", "[{}] {} submitted: {:?}", provider, trade_type, elapsed);
+        return;
+    }
+    tracing::warn!(
+        target: "sol_trade_sdk",
+        "[{}] {} submission rejected (HTTP {:?}, code {:?}, rate limited: {}) after {:?}",
+        provider,
+        trade_type,
+        response.http_status,
+        response.error_code,
+        response.rate_limited,
+        elapsed
+    );
+    if let Some(message) = &response.error_message {
+        tracing::debug!(target: "sol_trade_sdk", "[{}] {} rejection: {}", provider, trade_type, message);
+    }
+}
+
 // High-performance serialization
 
 pub trait FormatBase64VersionedTransaction {
@@ -327,3 +468,46 @@ pub async fn serialize_smart_transaction_and_encode(
     };
     Ok((serialized, *signature))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn submit_response_from_http() {
+        let sig = Signature::new_unique();
+
+        let ok = SubmitResponse::from_http(200, r#"{"jsonrpc":"2.0","result":"abc","id":1}"#, sig);
+        assert_eq!(ok.signature, Some(sig));
+
+        let rejected = SubmitResponse::from_http(
+            200,
+            r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"tip too low"},"id":1}"#,
+            sig,
+        );
+        assert!(!rejected.is_accepted());
+        assert_eq!(rejected.error_code, Some(-32602));
+        assert_eq!(rejected.error_message.as_deref(), Some("tip too low"));
+        assert!(!rejected.rate_limited);
+
+        let limited = SubmitResponse::from_http(429, "Too Many Requests", sig);
+        assert!(limited.rate_limited);
+        assert_eq!(limited.http_status, Some(429));
+
+        let reason = SubmitResponse::from_http(200, r#"{"reason":"invalid auth"}"#, sig);
+        assert_eq!(reason.error_message.as_deref(), Some("invalid auth"));
+        assert!(reason.into_result().is_err());
+    }
+
+    #[tokio::test]
+    async fn probe_errors_do_not_leak_the_endpoint_api_key() {
+        let http_client = default_http_client_builder().build().unwrap();
+        // Nothing listens on port 1.
+        let err = probe_http_endpoint(&http_client, "http://127.0.0.1:1/?api-key=secret-key")
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("endpoint 127.0.0.1:1 unreachable"), "{err}");
+        assert!(!err.contains("secret-key"), "{err}");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::swqos::common::{
-    default_http_client_builder, poll_transaction_confirmation, serialize_transaction_and_encode,
+    default_http_client_builder, log_submit_response, poll_transaction_confirmation,
+    probe_http_endpoint, serialize_transaction_and_encode, SubmitResponse,
 };
 use rand::seq::IndexedRandom;
 use reqwest::Client;
@@ -30,7 +31,7 @@ impl SwqosClientTrait for FlashBlockClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         self.send_transaction(trade_type, transaction, wait_confirmation).await
     }
 
@@ -54,6 +55,10 @@ impl SwqosClientTrait for FlashBlockClient {
     fn get_swqos_type(&self) -> SwqosType {
         SwqosType::FlashBlock
     }
+
+    async fn health_check(&self) -> Result<()> {
+        probe_http_endpoint(&self.http_client, &self.endpoint).await
+    }
 }
 
 impl FlashBlockClient {
@@ -68,7 +73,7 @@ impl FlashBlockClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let (content, signature) =
             serialize_transaction_and_encode(transaction, UiTransactionEncoding::Base64)?;
@@ -81,7 +86,7 @@ impl FlashBlockClient {
         let url = format!("{}/api/v2/submit-batch", self.endpoint);
 
         // Send request to FlashBlock
-        let response_text = self
+        let response = self
             .http_client
             .post(&url)
             .body(request_body)
@@ -90,19 +95,14 @@ impl FlashBlockClient {
             .header("Connection", "keep-alive")
             .header("Keep-Alive", "timeout=30, max=1000")
             .send()
-            .await?
-            .text()
             .await?;
+        let http_status = response.status().as_u16();
+        let response_text = response.text().await?;
 
-        // Parse response
-        if let Ok(response_json) = serde_json::from_str::<serde_json::Value>(&response_text) {
-            if response_json.get("success").is_some() || response_json.get("result").is_some() {
-                println!(" [FlashBlock] {} submitted: {:?}", trade_type, start_time.elapsed());
-            } else if let Some(_error) = response_json.get("error") {
-                eprintln!(" [FlashBlock] {} submission failed: {:?}", trade_type, _error);
-            }
-        } else {
-            eprintln!(" [FlashBlock] {} submission failed: {:?}", trade_type, response_text);
+        let submit_response = SubmitResponse::from_http(http_status, &response_text, signature);
+        log_submit_response("FlashBlock", trade_type, &submit_response, start_time.elapsed());
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         let start_time: Instant = Instant::now();
@@ -123,7 +123,7 @@ impl FlashBlockClient {
             println!(" [FlashBlock] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
 
-        Ok(())
+        Ok(submit_response)
     }
 
     pub async fn send_transactions(
@@ -133,7 +133,9 @@ impl FlashBlockClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
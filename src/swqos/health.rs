@@ -0,0 +1,386 @@
+//! SWQOS health monitoring: periodic probes and a per-provider circuit breaker.
+//!
+//! - **Breaker**: One breaker per client ([`SwqosClientKey`]: provider, region, endpoint host). After
+//!   `failure_threshold` consecutive failures the client is opened and left out of the fan-out. After `cool_down` it
+//!   becomes half-open: the next success re-admits it, the next failure opens it again.
+//! - **Failures**: Failed probes, transport errors (timeout, connect) and provider 5xx answers. A transaction that
+//!   fails to build or that the provider rejects (bad transaction, rate limit) says nothing about the provider's
+//!   health and does not count.
+//! - **Probes**: [`SwqosHealthMonitor::start_probes`] calls [`health_check`](crate::swqos::SwqosClientTrait::health_check) on every client each
+//!   `probe_interval`, so a dead provider is detected even when no trades are sent.
+//! - **Events**: State changes are delivered to the handler set with [`SwqosHealthMonitor::set_event_handler`].
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use arc_swap::ArcSwap;
+use parking_lot::{Mutex, RwLock};
+use tokio::task::JoinHandle;
+
+use crate::swqos::{SwqosClient, SwqosClientKey, SwqosType};
+
+/// Circuit breaker and probe settings.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures before a provider is removed from the fan-out.
+    pub failure_threshold: u32,
+    /// How long an opened provider stays out before it is tried again.
+    pub cool_down: Duration,
+    /// Interval between health probes.
+    pub probe_interval: Duration,
+    /// Max time a single probe may take before it counts as a failure.
+    pub probe_timeout: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            cool_down: Duration::from_secs(30),
+            probe_interval: Duration::from_secs(15),
+            probe_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Health state change of one client.
+#[derive(Debug, Clone)]
+pub enum SwqosHealthEvent {
+    /// Client removed from the fan-out after `consecutive_failures` failures.
+    CircuitOpened { client: SwqosClientKey, consecutive_failures: u32, last_error: String },
+    /// Cool-down elapsed; the client receives trades again and the next result decides.
+    CircuitHalfOpen { client: SwqosClientKey },
+    /// Client re-admitted after a success in half-open state.
+    CircuitClosed { client: SwqosClientKey },
+    /// A health probe failed (the breaker may still be closed).
+    ProbeFailed { client: SwqosClientKey, error: String },
+}
+
+/// Callback receiving [`SwqosHealthEvent`]s.
+pub type SwqosHealthEventHandler = Arc<dyn Fn(&SwqosHealthEvent) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Breaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl Default for Breaker {
+    fn default() -> Self {
+        Self { state: CircuitState::Closed, consecutive_failures: 0, opened_at: None }
+    }
+}
+
+/// Tracks provider health and filters open providers out of the SWQOS fan-out.
+pub struct SwqosHealthMonitor {
+    config: RwLock<CircuitBreakerConfig>,
+    breakers: Mutex<HashMap<SwqosClientKey, Breaker>>,
+    /// Number of providers currently open; lets the hot path skip filtering when 0.
+    open_count: AtomicUsize,
+    event_handler: RwLock<Option<SwqosHealthEventHandler>>,
+    probe_handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl SwqosHealthMonitor {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            breakers: Mutex::new(HashMap::new()),
+            open_count: AtomicUsize::new(0),
+            event_handler: RwLock::new(None),
+            probe_handle: Mutex::new(None),
+        }
+    }
+
+    /// Replace breaker/probe settings; applies from the next result or probe round.
+    pub fn set_config(&self, config: CircuitBreakerConfig) {
+        *self.config.write() = config;
+    }
+
+    pub fn config(&self) -> CircuitBreakerConfig {
+        self.config.read().clone()
+    }
+
+    /// Set the callback that receives health events (replaces any previous one).
+    pub fn set_event_handler<F>(&self, handler: F)
+    where
+        F: Fn(&SwqosHealthEvent) + Send + Sync + 'static,
+    {
+        *self.event_handler.write() = Some(Arc::new(handler));
+    }
+
+    /// Record a successful submit or probe (any answer from the provider).
+    pub fn record_success(&self, client: &SwqosClientKey) {
+        let event = {
+            let mut breakers = self.breakers.lock();
+            let breaker = breakers.entry(client.clone()).or_default();
+            breaker.consecutive_failures = 0;
+            match breaker.state {
+                CircuitState::HalfOpen => {
+                    breaker.state = CircuitState::Closed;
+                    breaker.opened_at = None;
+                    Some(SwqosHealthEvent::CircuitClosed { client: client.clone() })
+                }
+                // Open: stays out until the cool-down elapses, even if a probe succeeds.
+                CircuitState::Closed | CircuitState::Open => None,
+            }
+        };
+        if let Some(event) = event {
+            self.emit(&event);
+        }
+    }
+
+    /// Record a failed probe or a transport / provider-side submit error; opens the breaker at
+    /// the failure threshold.
+    pub fn record_failure(&self, client: &SwqosClientKey, error: &str) {
+        let threshold = self.config.read().failure_threshold.max(1);
+        let event = {
+            let mut breakers = self.breakers.lock();
+            let breaker = breakers.entry(client.clone()).or_default();
+            breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+            let should_open = match breaker.state {
+                CircuitState::Closed => breaker.consecutive_failures >= threshold,
+                CircuitState::HalfOpen => true,
+                CircuitState::Open => false,
+            };
+            if should_open {
+                breaker.state = CircuitState::Open;
+                breaker.opened_at = Some(Instant::now());
+                self.open_count.fetch_add(1, Ordering::Relaxed);
+                Some(SwqosHealthEvent::CircuitOpened {
+                    client: client.clone(),
+                    consecutive_failures: breaker.consecutive_failures,
+                    last_error: error.to_string(),
+                })
+            } else {
+                None
+            }
+        };
+        if let Some(event) = event {
+            self.emit(&event);
+        }
+    }
+
+    /// Forget the breaker state of every client of `swqos_type` (e.g. after they were replaced
+    /// or removed).
+    pub fn reset(&self, swqos_type: SwqosType) {
+        self.breakers.lock().retain(|client, breaker| {
+            if client.swqos_type != swqos_type {
+                return true;
+            }
+            if breaker.state == CircuitState::Open {
+                self.open_count.fetch_sub(1, Ordering::Relaxed);
+            }
+            false
+        });
+    }
+
+    /// Current breaker state of a client (`Closed` if never seen).
+    pub fn state(&self, client: &SwqosClientKey) -> CircuitState {
+        self.refresh();
+        self.breakers.lock().get(client).map(|b| b.state).unwrap_or(CircuitState::Closed)
+    }
+
+    /// Clients whose breaker is not open. Returns `None` when nothing is open (use all clients)
+    /// or when every client is open (better to try than to send nowhere).
+    pub fn filter(&self, swqos_clients: &[Arc<SwqosClient>]) -> Option<Vec<Arc<SwqosClient>>> {
+        if self.open_count.load(Ordering::Relaxed) == 0 {
+            return None;
+        }
+        self.refresh();
+        let breakers = self.breakers.lock();
+        let healthy: Vec<Arc<SwqosClient>> = swqos_clients
+            .iter()
+            .filter(|c| breakers.get(&c.client_key()).is_none_or(|b| b.state != CircuitState::Open))
+            .cloned()
+            .collect();
+        if healthy.is_empty() || healthy.len() == swqos_clients.len() {
+            None
+        } else {
+            Some(healthy)
+        }
+    }
+
+    /// Move providers whose cool-down elapsed from `Open` to `HalfOpen`.
+    fn refresh(&self) {
+        if self.open_count.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let cool_down = self.config.read().cool_down;
+        let now = Instant::now();
+        let mut events = Vec::new();
+        {
+            let mut breakers = self.breakers.lock();
+            for (client, breaker) in breakers.iter_mut() {
+                if breaker.state == CircuitState::Open
+                    && breaker.opened_at.is_none_or(|t| now.duration_since(t) >= cool_down)
+                {
+                    breaker.state = CircuitState::HalfOpen;
+                    self.open_count.fetch_sub(1, Ordering::Relaxed);
+                    events.push(SwqosHealthEvent::CircuitHalfOpen { client: client.clone() });
+                }
+            }
+        }
+        for event in &events {
+            self.emit(event);
+        }
+    }
+
+    fn emit(&self, event: &SwqosHealthEvent) {
+        if crate::common::sdk_log::sdk_log_enabled() {
+            tracing::warn!(target: "sol_trade_sdk", "swqos health: {:?}", event);
+        }
+        let handler = self.event_handler.read().clone();
+        if let Some(handler) = handler {
+            handler(event);
+        }
+    }
+
+    /// Probe every client once, concurrently, with `probe_timeout` each.
+    pub async fn probe_all(&self, swqos_clients: &[Arc<SwqosClient>]) {
+        let probe_timeout = self.config.read().probe_timeout;
+        let results = futures::future::join_all(swqos_clients.iter().map(|client| async move {
+            let result = match tokio::time::timeout(probe_timeout, client.health_check()).await {
+                Ok(r) => r,
+                Err(_) => {
+                    Err(anyhow::anyhow!("probe timed out ({} ms)", probe_timeout.as_millis()))
+                }
+            };
+            (client.client_key(), result)
+        }))
+        .await;
+        for (client, result) in results {
+            match result {
+                Ok(()) => self.record_success(&client),
+                Err(e) => {
+                    let error = e.to_string();
+                    self.record_failure(&client, &error);
+                    self.emit(&SwqosHealthEvent::ProbeFailed { client, error });
+                }
+            }
+        }
+        self.refresh();
+    }
+
+    /// Start the background probe loop (replaces a running one). Each round probes the current
+    /// contents of `swqos_clients`, so clients added or removed at runtime are picked up.
+    /// The loop stops when the monitor is dropped.
+    pub fn start_probes(self: &Arc<Self>, swqos_clients: Arc<ArcSwap<Vec<Arc<SwqosClient>>>>) {
+        let weak: Weak<Self> = Arc::downgrade(self);
+        let handle = tokio::spawn(async move {
+            loop {
+                let interval = match weak.upgrade() {
+                    Some(monitor) => monitor.config.read().probe_interval,
+                    None => return,
+                };
+                tokio::time::sleep(interval).await;
+                let Some(monitor) = weak.upgrade() else { return };
+                let clients = swqos_clients.load_full();
+                monitor.probe_all(&clients).await;
+            }
+        });
+        if let Some(old) = self.probe_handle.lock().replace(handle) {
+            old.abort();
+        }
+    }
+
+    /// Stop the background probe loop.
+    pub fn stop_probes(&self) {
+        if let Some(handle) = self.probe_handle.lock().take() {
+            handle.abort();
+        }
+    }
+}
+
+impl Default for SwqosHealthMonitor {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+impl Drop for SwqosHealthMonitor {
+    fn drop(&mut self) {
+        self.stop_probes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swqos::SwqosRegion;
+
+    fn key(swqos_type: SwqosType, region: SwqosRegion) -> SwqosClientKey {
+        SwqosClientKey { swqos_type, region, host: None }
+    }
+
+    #[test]
+    fn opens_after_threshold_and_recovers_after_cool_down() {
+        let monitor = SwqosHealthMonitor::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            cool_down: Duration::from_millis(20),
+            ..Default::default()
+        });
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        monitor.set_event_handler(move |e| sink.lock().push(format!("{:?}", e)));
+
+        let jito = key(SwqosType::Jito, SwqosRegion::Frankfurt);
+        monitor.record_failure(&jito, "timeout");
+        assert_eq!(monitor.state(&jito), CircuitState::Closed);
+        monitor.record_failure(&jito, "timeout");
+        assert_eq!(monitor.state(&jito), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(monitor.state(&jito), CircuitState::HalfOpen);
+        monitor.record_success(&jito);
+        assert_eq!(monitor.state(&jito), CircuitState::Closed);
+
+        let events = events.lock();
+        assert_eq!(events.len(), 3);
+        assert!(events[0].starts_with("CircuitOpened"));
+        assert!(events[1].starts_with("CircuitHalfOpen"));
+        assert!(events[2].starts_with("CircuitClosed"));
+    }
+
+    #[test]
+    fn half_open_failure_reopens() {
+        let monitor = SwqosHealthMonitor::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            cool_down: Duration::from_millis(20),
+            ..Default::default()
+        });
+        let zeroslot = key(SwqosType::ZeroSlot, SwqosRegion::NewYork);
+        monitor.record_failure(&zeroslot, "500");
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(monitor.state(&zeroslot), CircuitState::HalfOpen);
+        monitor.record_failure(&zeroslot, "500");
+        assert_eq!(monitor.state(&zeroslot), CircuitState::Open);
+    }
+
+    #[test]
+    fn breakers_are_per_client() {
+        let monitor = SwqosHealthMonitor::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            ..Default::default()
+        });
+        let tokyo = key(SwqosType::Jito, SwqosRegion::Tokyo);
+        let new_york = key(SwqosType::Jito, SwqosRegion::NewYork);
+        monitor.record_failure(&tokyo, "connect error");
+        assert_eq!(monitor.state(&tokyo), CircuitState::Open);
+        assert_eq!(monitor.state(&new_york), CircuitState::Closed);
+
+        monitor.reset(SwqosType::Jito);
+        assert_eq!(monitor.state(&tokyo), CircuitState::Closed);
+        assert_eq!(monitor.open_count.load(Ordering::Relaxed), 0);
+    }
+}
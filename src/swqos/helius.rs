@@ -8,7 +8,8 @@
 //! Optional query: api-key (custom TPS only), swqos_only (SWQOS-only routing, lower min tip).
 
 use crate::swqos::common::{
-    default_http_client_builder, poll_transaction_confirmation, serialize_transaction_and_encode,
+    default_http_client_builder, log_submit_response, poll_transaction_confirmation,
+    probe_http_endpoint, serialize_transaction_and_encode, SubmitResponse,
 };
 use anyhow::Result;
 use rand::seq::IndexedRandom;
@@ -73,7 +74,7 @@ impl HeliusClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let (content, signature) =
             serialize_transaction_and_encode(transaction, UiTransactionEncoding::Base64)?;
@@ -100,39 +101,13 @@ impl HeliusClient {
             .send()
             .await?;
 
-        let status = response.status();
+        let http_status = response.status().as_u16();
         let response_text = response.text().await?;
 
-        if !status.is_success() {
-            if crate::common::sdk_log::sdk_log_enabled() {
-                eprintln!(
-                    " [helius] {} submission failed status={} body={}",
-                    trade_type, status, response_text
-                );
-            }
-            return Err(anyhow::anyhow!(
-                "Helius Sender failed: status={} body={}",
-                status,
-                response_text
-            ));
-        }
-
-        if let Ok(response_json) = serde_json::from_str::<serde_json::Value>(&response_text) {
-            if response_json.get("error").is_some() {
-                let err_msg = response_json["error"]
-                    .get("message")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-                if crate::common::sdk_log::sdk_log_enabled() {
-                    eprintln!(" [helius] {} submission error: {}", trade_type, err_msg);
-                }
-                return Err(anyhow::anyhow!("Helius Sender error: {}", err_msg));
-            }
-            if response_json.get("result").is_some() && crate::common::sdk_log::sdk_log_enabled() {
-                println!(" [helius] {} submitted: {:?}", trade_type, start_time.elapsed());
-            }
-        } else if crate::common::sdk_log::sdk_log_enabled() {
-            eprintln!(" [helius] {} submission failed: {:?}", trade_type, response_text);
+        let submit_response = SubmitResponse::from_http(http_status, &response_text, signature);
+        log_submit_response("helius", trade_type, &submit_response, start_time.elapsed());
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         match poll_transaction_confirmation(&self.rpc_client, signature, wait_confirmation).await {
@@ -152,7 +127,7 @@ impl HeliusClient {
             println!(" signature: {:?}", signature);
             println!(" [helius] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
-        Ok(())
+        Ok(submit_response)
     }
 }
 
@@ -163,7 +138,7 @@ impl SwqosClientTrait for HeliusClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         HeliusClient::send_transaction(self, trade_type, transaction, wait_confirmation).await
     }
 
@@ -174,7 +149,9 @@ impl SwqosClientTrait for HeliusClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
@@ -191,6 +168,10 @@ impl SwqosClientTrait for HeliusClient {
         SwqosType::Helius
     }
 
+    async fn health_check(&self) -> Result<()> {
+        probe_http_endpoint(&self.http_client, &self.submit_url).await
+    }
+
     #[inline(always)]
     fn min_tip_sol(&self) -> f64 {
         if self.swqos_only {
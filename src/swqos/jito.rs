@@ -1,6 +1,7 @@
 use crate::swqos::common::{
-    default_http_client_builder, poll_transaction_confirmation, serialize_transaction_and_encode,
-    FormatBase64VersionedTransaction,
+    default_http_client_builder, log_submit_response, poll_transaction_confirmation,
+    probe_http_endpoint, serialize_transaction_and_encode, FormatBase64VersionedTransaction,
+    SubmitResponse,
 };
 use rand::seq::IndexedRandom;
 use reqwest::Client;
@@ -30,7 +31,7 @@ impl SwqosClientTrait for JitoClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         self.send_transaction_impl(trade_type, transaction, wait_confirmation).await
     }
 
@@ -54,6 +55,10 @@ impl SwqosClientTrait for JitoClient {
     fn get_swqos_type(&self) -> SwqosType {
         SwqosType::Jito
     }
+
+    async fn health_check(&self) -> Result<()> {
+        probe_http_endpoint(&self.http_client, &self.endpoint).await
+    }
 }
 
 impl JitoClient {
@@ -68,7 +73,7 @@ impl JitoClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let (content, signature) =
             serialize_transaction_and_encode(transaction, UiTransactionEncoding::Base64)?;
@@ -95,22 +100,15 @@ impl JitoClient {
         } else {
             self.http_client.post(&endpoint).header("x-jito-auth", &self.auth_token)
         };
-        let response_text = response
-            .body(request_body)
-            .header("Content-Type", "application/json")
-            .send()
-            .await?
-            .text()
-            .await?;
-
-        if let Ok(response_json) = serde_json::from_str::<serde_json::Value>(&response_text) {
-            if response_json.get("result").is_some() {
-                println!(" [jito] {} submitted: {:?}", trade_type, start_time.elapsed());
-            } else if let Some(_error) = response_json.get("error") {
-                eprintln!(" [jito] {} submission failed: {:?}", trade_type, _error);
-            }
-        } else {
-            eprintln!(" [jito] {} submission failed: {:?}", trade_type, response_text);
+        let response =
+            response.body(request_body).header("Content-Type", "application/json").send().await?;
+        let http_status = response.status().as_u16();
+        let response_text = response.text().await?;
+
+        let submit_response = SubmitResponse::from_http(http_status, &response_text, signature);
+        log_submit_response("jito", trade_type, &submit_response, start_time.elapsed());
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         let start_time: Instant = Instant::now();
@@ -127,7 +125,7 @@ impl JitoClient {
             println!(" [jito] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
 
-        Ok(())
+        Ok(submit_response)
     }
 
     pub async fn send_transactions_impl(
@@ -159,21 +157,20 @@ impl JitoClient {
         } else {
             self.http_client.post(&endpoint).header("x-jito-auth", &self.auth_token)
         };
-        let response_text = response
+        let response = response
             .body(body.to_string())
             .header("Content-Type", "application/json")
             .send()
-            .await?
-            .text()
             .await?;
-
-        if let Ok(response_json) = serde_json::from_str::<serde_json::Value>(&response_text) {
-            if response_json.get("result").is_some() {
-                println!(" jito {} submitted: {:?}", trade_type, start_time.elapsed());
-            } else if let Some(_error) = response_json.get("error") {
-                eprintln!(" jito {} submission failed: {:?}", trade_type, _error);
-            }
-        }
+        let http_status = response.status().as_u16();
+        let response_text = response.text().await?;
+
+        let bundle_signature =
+            transactions.first().and_then(|tx| tx.signatures.first().copied()).unwrap_or_default();
+        let submit_response =
+            SubmitResponse::from_http(http_status, &response_text, bundle_signature);
+        log_submit_response("jito", trade_type, &submit_response, start_time.elapsed());
+        submit_response.into_result()?;
 
         Ok(())
     }
@@ -4,12 +4,14 @@ pub mod blockrazor;
 pub mod bloxroute;
 pub mod common;
 pub mod flashblock;
+pub mod health;
 pub mod helius;
 pub mod jito;
 pub mod lightspeed;
 pub mod nextblock;
 pub mod node1;
 pub mod node1_quic;
+pub mod scoreboard;
 pub mod serialization;
 pub mod solana_rpc;
 pub mod soyas;
@@ -20,12 +22,15 @@ pub mod zeroslot;
 
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
 use solana_commitment_config::CommitmentConfig;
 use solana_sdk::transaction::VersionedTransaction;
 use tokio::sync::RwLock;
 
 use anyhow::Result;
 
+pub use common::SubmitResponse;
+
 use crate::{
     common::SolanaRpcClient,
     constants::swqos::{
@@ -71,7 +76,7 @@ pub enum SwqosTransport {
     Quic,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TradeType {
     Create,
     CreateAndBuy,
@@ -136,12 +141,14 @@ pub type SwqosClient = dyn SwqosClientTrait + Send + Sync + 'static;
 
 #[async_trait::async_trait]
 pub trait SwqosClientTrait {
+    /// Submit one transaction. `Ok` carries the provider's verdict ([`SubmitResponse::is_accepted`]);
+    /// `Err` means the request itself failed (timeout, connect error) or confirmation failed.
     async fn send_transaction(
         &self,
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()>;
+    ) -> Result<SubmitResponse>;
     async fn send_transactions(
         &self,
         trade_type: TradeType,
@@ -150,6 +157,20 @@ pub trait SwqosClientTrait {
     ) -> Result<()>;
     fn get_tip_account(&self) -> Result<String>;
     fn get_swqos_type(&self) -> SwqosType;
+    /// Lightweight liveness probe used by [`health::SwqosHealthMonitor`]. Default: always healthy
+    /// (QUIC clients reconnect on send, so their breaker relies on submit results only).
+    async fn health_check(&self) -> Result<()> {
+        Ok(())
+    }
+    /// Provider, region and endpoint this client was created for. Clients built through
+    /// [`SwqosConfig::get_swqos_client`] carry the key of their config.
+    fn client_key(&self) -> SwqosClientKey {
+        SwqosClientKey {
+            swqos_type: self.get_swqos_type(),
+            region: SwqosRegion::Default,
+            host: None,
+        }
+    }
     /// Minimum tip in SOL required by this provider. Helius returns lower value when swqos_only is true.
     #[inline]
     fn min_tip_sol(&self) -> f64 {
@@ -173,7 +194,7 @@ pub trait SwqosClientTrait {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SwqosRegion {
     NewYork,
     Frankfurt,
@@ -243,6 +264,53 @@ impl SwqosConfig {
         }
     }
 
+    /// Region this config was created with (`Default` for the RPC config).
+    pub fn region(&self) -> SwqosRegion {
+        match self {
+            SwqosConfig::Default(_) => SwqosRegion::Default,
+            SwqosConfig::Jito(_, region, _)
+            | SwqosConfig::NextBlock(_, region, _)
+            | SwqosConfig::Bloxroute(_, region, _)
+            | SwqosConfig::Temporal(_, region, _)
+            | SwqosConfig::ZeroSlot(_, region, _)
+            | SwqosConfig::Node1(_, region, _, _)
+            | SwqosConfig::FlashBlock(_, region, _)
+            | SwqosConfig::BlockRazor(_, region, _)
+            | SwqosConfig::Astralane(_, region, _, _)
+            | SwqosConfig::Stellium(_, region, _)
+            | SwqosConfig::Lightspeed(_, region, _)
+            | SwqosConfig::Soyas(_, region, _)
+            | SwqosConfig::Speedlanding(_, region, _)
+            | SwqosConfig::Helius(_, region, _, _) => *region,
+        }
+    }
+
+    /// Key of the client built from this config (see [`SwqosClientKey`]).
+    pub fn client_key(&self) -> SwqosClientKey {
+        let custom_url = match self {
+            SwqosConfig::Default(endpoint) => Some(endpoint),
+            SwqosConfig::Jito(_, _, url)
+            | SwqosConfig::NextBlock(_, _, url)
+            | SwqosConfig::Bloxroute(_, _, url)
+            | SwqosConfig::Temporal(_, _, url)
+            | SwqosConfig::ZeroSlot(_, _, url)
+            | SwqosConfig::Node1(_, _, url, _)
+            | SwqosConfig::FlashBlock(_, _, url)
+            | SwqosConfig::BlockRazor(_, _, url)
+            | SwqosConfig::Astralane(_, _, url, _)
+            | SwqosConfig::Stellium(_, _, url)
+            | SwqosConfig::Lightspeed(_, _, url)
+            | SwqosConfig::Soyas(_, _, url)
+            | SwqosConfig::Speedlanding(_, _, url)
+            | SwqosConfig::Helius(_, _, url, _) => url.as_ref(),
+        };
+        SwqosClientKey {
+            swqos_type: self.swqos_type(),
+            region: self.region(),
+            host: custom_url.map(|url| endpoint_host(url).to_string()),
+        }
+    }
+
     /// Check if current config is in the blacklist
     pub fn is_blacklisted(&self) -> bool {
         SWQOS_BLACKLIST.contains(&self.swqos_type())
@@ -276,6 +344,16 @@ impl SwqosConfig {
         rpc_url: String,
         commitment: CommitmentConfig,
         swqos_config: SwqosConfig,
+    ) -> Result<Arc<SwqosClient>> {
+        let key = swqos_config.client_key();
+        let inner = Self::new_swqos_client(rpc_url, commitment, swqos_config).await?;
+        Ok(Arc::new(KeyedSwqosClient { inner, key }))
+    }
+
+    async fn new_swqos_client(
+        rpc_url: String,
+        commitment: CommitmentConfig,
+        swqos_config: SwqosConfig,
     ) -> Result<Arc<SwqosClient>> {
         match swqos_config {
             SwqosConfig::Jito(auth_token, region, url) => {
@@ -393,3 +471,101 @@ impl SwqosConfig {
         }
     }
 }
+
+/// Identifies one SWQOS client: provider, region and, for custom URLs and the RPC client, the
+/// endpoint host (never the path or query, which may carry an API key).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SwqosClientKey {
+    pub swqos_type: SwqosType,
+    pub region: SwqosRegion,
+    pub host: Option<String>,
+}
+
+impl std::fmt::Display for SwqosClientKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}/{:?}", self.swqos_type, self.region)?;
+        if let Some(host) = &self.host {
+            write!(f, "@{}", host)?;
+        }
+        Ok(())
+    }
+}
+
+/// `host[:port]` of an endpoint URL, without scheme, credentials, path or query.
+fn endpoint_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    authority.rsplit_once('@').map_or(authority, |(_, host)| host)
+}
+
+/// Client built from a `SwqosConfig`, labelled with the config's key.
+struct KeyedSwqosClient {
+    inner: Arc<SwqosClient>,
+    key: SwqosClientKey,
+}
+
+#[async_trait::async_trait]
+impl SwqosClientTrait for KeyedSwqosClient {
+    async fn send_transaction(
+        &self,
+        trade_type: TradeType,
+        transaction: &VersionedTransaction,
+        wait_confirmation: bool,
+    ) -> Result<SubmitResponse> {
+        self.inner.send_transaction(trade_type, transaction, wait_confirmation).await
+    }
+
+    async fn send_transactions(
+        &self,
+        trade_type: TradeType,
+        transactions: &Vec<VersionedTransaction>,
+        wait_confirmation: bool,
+    ) -> Result<()> {
+        self.inner.send_transactions(trade_type, transactions, wait_confirmation).await
+    }
+
+    fn get_tip_account(&self) -> Result<String> {
+        self.inner.get_tip_account()
+    }
+
+    fn get_swqos_type(&self) -> SwqosType {
+        self.key.swqos_type
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.inner.health_check().await
+    }
+
+    fn client_key(&self) -> SwqosClientKey {
+        self.key.clone()
+    }
+
+    #[inline]
+    fn min_tip_sol(&self) -> f64 {
+        self.inner.min_tip_sol()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_key_keeps_only_the_endpoint_host() {
+        let config = SwqosConfig::Lightspeed(
+            "key".to_string(),
+            SwqosRegion::Frankfurt,
+            Some("https://basic.rpc.solanavibestation.com/lightspeed?api_key=secret".to_string()),
+        );
+        let key = config.client_key();
+        assert_eq!(key.host.as_deref(), Some("basic.rpc.solanavibestation.com"));
+        assert_eq!(key.to_string(), "Lightspeed/Frankfurt@basic.rpc.solanavibestation.com");
+
+        let jito = SwqosConfig::Jito("".to_string(), SwqosRegion::Tokyo, None).client_key();
+        assert_eq!(
+            (jito.swqos_type, jito.region, jito.host),
+            (SwqosType::Jito, SwqosRegion::Tokyo, None)
+        );
+        assert_eq!(endpoint_host("http://user:pw@127.0.0.1:8899/"), "127.0.0.1:8899");
+    }
+}
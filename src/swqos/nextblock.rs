@@ -1,5 +1,6 @@
 use crate::swqos::common::{
-    default_http_client_builder, poll_transaction_confirmation, serialize_transaction_and_encode,
+    default_http_client_builder, log_submit_response, poll_transaction_confirmation,
+    probe_http_endpoint, serialize_transaction_and_encode, SubmitResponse,
 };
 use rand::seq::IndexedRandom;
 use reqwest::Client;
@@ -30,7 +31,7 @@ impl SwqosClientTrait for NextBlockClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         self.send_transaction(trade_type, transaction, wait_confirmation).await
     }
 
@@ -54,6 +55,10 @@ impl SwqosClientTrait for NextBlockClient {
     fn get_swqos_type(&self) -> SwqosType {
         SwqosType::NextBlock
     }
+
+    async fn health_check(&self) -> Result<()> {
+        probe_http_endpoint(&self.http_client, &self.endpoint).await
+    }
 }
 
 impl NextBlockClient {
@@ -74,7 +79,7 @@ impl NextBlockClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let (content, signature) =
             serialize_transaction_and_encode(transaction, UiTransactionEncoding::Base64)?;
@@ -86,25 +91,21 @@ impl NextBlockClient {
             "frontRunningProtection": false
         }))?;
 
-        let response_text = self
+        let response = self
             .http_client
             .post(&self.endpoint)
             .body(request_body)
             .header("Authorization", &self.auth_token)
             .header("Content-Type", "application/json")
             .send()
-            .await?
-            .text()
             .await?;
+        let http_status = response.status().as_u16();
+        let response_text = response.text().await?;
 
-        if let Ok(response_json) = serde_json::from_str::<serde_json::Value>(&response_text) {
-            if response_json.get("result").is_some() {
-                println!(" [nextblock] {} submitted: {:?}", trade_type, start_time.elapsed());
-            } else if let Some(_error) = response_json.get("error") {
-                eprintln!(" [nextblock] {} submission failed: {:?}", trade_type, _error);
-            }
-        } else {
-            eprintln!(" [nextblock] {} submission failed: {:?}", trade_type, response_text);
+        let submit_response = SubmitResponse::from_http(http_status, &response_text, signature);
+        log_submit_response("nextblock", trade_type, &submit_response, start_time.elapsed());
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         let start_time: Instant = Instant::now();
@@ -125,7 +126,7 @@ impl NextBlockClient {
             println!(" [nextblock] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
 
-        Ok(())
+        Ok(submit_response)
     }
 
     pub async fn send_transactions(
@@ -135,7 +136,9 @@ impl NextBlockClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
@@ -1,5 +1,6 @@
 use crate::swqos::common::{
-    default_http_client_builder, poll_transaction_confirmation, serialize_transaction_and_encode,
+    default_http_client_builder, log_submit_response, poll_transaction_confirmation,
+    serialize_transaction_and_encode, SubmitResponse,
 };
 use rand::seq::IndexedRandom;
 use reqwest::Client;
@@ -36,7 +37,7 @@ impl SwqosClientTrait for Node1Client {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         self.send_transaction(trade_type, transaction, wait_confirmation).await
     }
 
@@ -60,6 +61,10 @@ impl SwqosClientTrait for Node1Client {
     fn get_swqos_type(&self) -> SwqosType {
         SwqosType::Node1
     }
+
+    async fn health_check(&self) -> Result<()> {
+        Self::send_ping_request(&self.http_client, &self.endpoint, &self.auth_token).await
+    }
 }
 
 impl Node1Client {
@@ -153,7 +158,7 @@ impl Node1Client {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let (content, signature) =
             serialize_transaction_and_encode(transaction, UiTransactionEncoding::Base64)?;
@@ -169,28 +174,21 @@ impl Node1Client {
         }))?;
 
         // Node1 uses api-key header instead of URL parameter
-        let response_text = self
+        let response = self
             .http_client
             .post(&self.endpoint)
             .body(request_body)
             .header("Content-Type", "application/json")
             .header("api-key", &self.auth_token)
             .send()
-            .await?
-            .text()
             .await?;
+        let http_status = response.status().as_u16();
+        let response_text = response.text().await?;
 
-        // Parse JSON response
-        if let Ok(response_json) = serde_json::from_str::<serde_json::Value>(&response_text) {
-            if crate::common::sdk_log::sdk_log_enabled() {
-                if response_json.get("result").is_some() {
-                    println!(" [node1] {} submitted: {:?}", trade_type, start_time.elapsed());
-                } else if let Some(_error) = response_json.get("error") {
-                    eprintln!(" [node1] {} submission failed: {:?}", trade_type, _error);
-                }
-            }
-        } else if crate::common::sdk_log::sdk_log_enabled() {
-            eprintln!(" [node1] {} submission failed: {:?}", trade_type, response_text);
+        let submit_response = SubmitResponse::from_http(http_status, &response_text, signature);
+        log_submit_response("node1", trade_type, &submit_response, start_time.elapsed());
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         let start_time: Instant = Instant::now();
@@ -213,7 +211,7 @@ impl Node1Client {
             println!(" [node1] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
 
-        Ok(())
+        Ok(submit_response)
     }
 
     pub async fn send_transactions(
@@ -223,7 +221,9 @@ impl Node1Client {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
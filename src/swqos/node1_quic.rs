@@ -16,7 +16,7 @@ use uuid::Uuid;
 
 use crate::common::SolanaRpcClient;
 use crate::constants::swqos::NODE1_TIP_ACCOUNTS;
-use crate::swqos::common::poll_transaction_confirmation;
+use crate::swqos::common::{log_submit_response, poll_transaction_confirmation, SubmitResponse};
 use crate::swqos::{SwqosClientTrait, SwqosType, TradeType};
 use rand::seq::IndexedRandom;
 use solana_sdk::transaction::VersionedTransaction;
@@ -182,7 +182,7 @@ impl SwqosClientTrait for Node1QuicClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start = Instant::now();
         let signature = transaction.signatures.first().copied().unwrap_or_default();
         let tx_bytes = bincode::serialize(transaction).context("Node1 QUIC: bincode serialize")?;
@@ -191,18 +191,14 @@ impl SwqosClientTrait for Node1QuicClient {
             .await
             .context("Node1 QUIC send timeout")??;
 
-        if status != 200 {
-            if crate::common::sdk_log::sdk_log_enabled() {
-                eprintln!(
-                    " [node1-quic] {} submit failed: status={} msg={}",
-                    trade_type, status, msg
-                );
-            }
-            anyhow::bail!("Node1 QUIC submit failed: status={} msg={}", status, msg);
-        }
-
-        if crate::common::sdk_log::sdk_log_enabled() {
-            println!(" [node1-quic] {} submitted: {:?}", trade_type, start.elapsed());
+        let submit_response = if status == 200 {
+            SubmitResponse::accepted(signature, None)
+        } else {
+            SubmitResponse::rejected(None, Some(status as i64), msg)
+        };
+        log_submit_response("node1-quic", trade_type, &submit_response, start.elapsed());
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         let start = Instant::now();
@@ -211,7 +207,7 @@ impl SwqosClientTrait for Node1QuicClient {
                 if wait_confirmation && crate::common::sdk_log::sdk_log_enabled() {
                     println!(" [node1-quic] {} confirmed: {:?}", trade_type, start.elapsed());
                 }
-                Ok(())
+                Ok(submit_response)
             }
             Err(e) => {
                 if crate::common::sdk_log::sdk_log_enabled() {
@@ -233,7 +229,7 @@ impl SwqosClientTrait for Node1QuicClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for tx in transactions {
-            self.send_transaction(trade_type, tx, wait_confirmation).await?;
+            self.send_transaction(trade_type, tx, wait_confirmation).await?.into_result()?;
         }
         Ok(())
     }
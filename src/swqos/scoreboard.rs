@@ -0,0 +1,273 @@
+//! Per-provider SWQOS statistics and adaptive provider selection.
+//!
+//! - **Window**: Submit latency, submit errors and landing wins are kept per `SwqosType` and region over a sliding
+//!   time window. Each submit is recorded when its own provider answers; transactions that failed to build locally
+//!   are not counted against the provider.
+//! - **Snapshot**: [`SwqosScoreboard::snapshot`] returns per-provider stats ranked best-first (landing rate, error rate, latency).
+//! - **Adaptive**: When `adaptive_top_k` is set, only the top-K providers receive the trade; `Default` (RPC) is always kept,
+//!   and providers with too few samples are kept so they can still earn a score.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::swqos::{SwqosClient, SwqosRegion, SwqosType};
+
+/// Default sliding window for provider statistics (10 minutes).
+pub const DEFAULT_SCOREBOARD_WINDOW: Duration = Duration::from_secs(600);
+/// Providers with fewer submits than this in the window are never dropped by adaptive selection.
+pub const DEFAULT_MIN_SAMPLES: u32 = 10;
+/// Max events kept per provider, bounds memory when the window is long and trade rate is high.
+const MAX_EVENTS_PER_PROVIDER: usize = 4096;
+
+/// Stats are kept per provider and region.
+type ScoreKey = (SwqosType, SwqosRegion);
+
+#[derive(Debug, Clone, Copy)]
+enum ScoreEvent {
+    Submitted { latency_us: i64 },
+    SubmitError,
+    Win,
+}
+
+/// Stats of one provider in one region over the current window.
+#[derive(Debug, Clone)]
+pub struct SwqosScoreSnapshot {
+    pub swqos_type: SwqosType,
+    /// Region of the `SwqosConfig` the client was created with (`Default` for the RPC client).
+    pub region: SwqosRegion,
+    /// Successful submits (provider accepted the transaction).
+    pub submits: u32,
+    pub submit_errors: u32,
+    /// Confirmed trades that landed via this provider.
+    pub wins: u32,
+    /// `wins / submits` (0.0 when no submits).
+    pub landing_rate: f64,
+    /// `submit_errors / (submits + submit_errors)` (0.0 when no attempts).
+    pub error_rate: f64,
+    /// Average submit latency in microseconds over successful submits.
+    pub avg_submit_latency_us: f64,
+}
+
+/// Sliding-window scoreboard of SWQOS providers, fed by the executor after each submit/confirmation.
+pub struct SwqosScoreboard {
+    window: Duration,
+    min_samples: u32,
+    events: Mutex<HashMap<ScoreKey, VecDeque<(Instant, ScoreEvent)>>>,
+    /// 0 = adaptive selection disabled.
+    adaptive_top_k: AtomicUsize,
+}
+
+impl SwqosScoreboard {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            min_samples: DEFAULT_MIN_SAMPLES,
+            events: Mutex::new(HashMap::new()),
+            adaptive_top_k: AtomicUsize::new(0),
+        }
+    }
+
+    /// Set the minimum number of submits before a provider can be dropped by adaptive selection.
+    pub fn with_min_samples(mut self, min_samples: u32) -> Self {
+        self.min_samples = min_samples;
+        self
+    }
+
+    /// Enable adaptive selection (fan out only to the top `k` providers, plus `Default`), or disable with `None`.
+    pub fn set_adaptive_top_k(&self, top_k: Option<usize>) {
+        self.adaptive_top_k.store(top_k.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// Current adaptive top-K, `None` when disabled.
+    #[inline]
+    pub fn adaptive_top_k(&self) -> Option<usize> {
+        match self.adaptive_top_k.load(Ordering::Relaxed) {
+            0 => None,
+            k => Some(k),
+        }
+    }
+
+    /// Record one submit attempt; `latency_us` is the time the provider took to answer.
+    pub fn record_submit(
+        &self,
+        swqos_type: SwqosType,
+        region: SwqosRegion,
+        latency_us: i64,
+        ok: bool,
+    ) {
+        let event = if ok { ScoreEvent::Submitted { latency_us } } else { ScoreEvent::SubmitError };
+        self.push((swqos_type, region), event);
+    }
+
+    /// Record that a confirmed trade landed via `swqos_type` in `region`.
+    pub fn record_win(&self, swqos_type: SwqosType, region: SwqosRegion) {
+        self.push((swqos_type, region), ScoreEvent::Win);
+    }
+
+    fn push(&self, key: ScoreKey, event: ScoreEvent) {
+        let now = Instant::now();
+        let mut events = self.events.lock();
+        let queue = events.entry(key).or_default();
+        Self::prune(queue, now, self.window);
+        if queue.len() >= MAX_EVENTS_PER_PROVIDER {
+            queue.pop_front();
+        }
+        queue.push_back((now, event));
+    }
+
+    #[inline]
+    fn prune(queue: &mut VecDeque<(Instant, ScoreEvent)>, now: Instant, window: Duration) {
+        while let Some((t, _)) = queue.front() {
+            if now.duration_since(*t) > window {
+                queue.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Per-provider, per-region stats over the current window, best first.
+    pub fn snapshot(&self) -> Vec<SwqosScoreSnapshot> {
+        let now = Instant::now();
+        let mut events = self.events.lock();
+        let mut result = Vec::with_capacity(events.len());
+        for (&(swqos_type, region), queue) in events.iter_mut() {
+            Self::prune(queue, now, self.window);
+            let (mut submits, mut submit_errors, mut wins, mut latency_sum) =
+                (0u32, 0u32, 0u32, 0i64);
+            for (_, event) in queue.iter() {
+                match event {
+                    ScoreEvent::Submitted { latency_us } => {
+                        submits += 1;
+                        latency_sum += latency_us.max(&0);
+                    }
+                    ScoreEvent::SubmitError => submit_errors += 1,
+                    ScoreEvent::Win => wins += 1,
+                }
+            }
+            let attempts = submits + submit_errors;
+            result.push(SwqosScoreSnapshot {
+                swqos_type,
+                region,
+                submits,
+                submit_errors,
+                wins,
+                landing_rate: if submits > 0 { wins as f64 / submits as f64 } else { 0.0 },
+                error_rate: if attempts > 0 { submit_errors as f64 / attempts as f64 } else { 0.0 },
+                avg_submit_latency_us: if submits > 0 {
+                    latency_sum as f64 / submits as f64
+                } else {
+                    0.0
+                },
+            });
+        }
+        drop(events);
+        result.sort_by(Self::rank);
+        result
+    }
+
+    /// Higher landing rate first, then lower error rate, then lower latency.
+    fn rank(a: &SwqosScoreSnapshot, b: &SwqosScoreSnapshot) -> std::cmp::Ordering {
+        b.landing_rate
+            .total_cmp(&a.landing_rate)
+            .then(a.error_rate.total_cmp(&b.error_rate))
+            .then(a.avg_submit_latency_us.total_cmp(&b.avg_submit_latency_us))
+    }
+
+    /// Providers allowed by adaptive selection: `Default`, providers still below `min_samples`,
+    /// and the top-K ranked provider/region pairs. Returns `None` when adaptive mode is off (use
+    /// all clients).
+    pub fn select(&self, swqos_clients: &[Arc<SwqosClient>]) -> Option<Vec<Arc<SwqosClient>>> {
+        let top_k = self.adaptive_top_k()?;
+        let snapshot = self.snapshot();
+        let mut ranked: Vec<ScoreKey> = Vec::with_capacity(top_k);
+        for s in snapshot.iter() {
+            if s.swqos_type != SwqosType::Default && s.submits + s.submit_errors >= self.min_samples
+            {
+                if ranked.len() >= top_k {
+                    break;
+                }
+                ranked.push((s.swqos_type, s.region));
+            }
+        }
+        let well_sampled = |key: ScoreKey| {
+            snapshot.iter().any(|s| {
+                (s.swqos_type, s.region) == key && s.submits + s.submit_errors >= self.min_samples
+            })
+        };
+        Some(
+            swqos_clients
+                .iter()
+                .filter(|c| {
+                    let key = c.client_key();
+                    let key = (key.swqos_type, key.region);
+                    key.0 == SwqosType::Default || ranked.contains(&key) || !well_sampled(key)
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Drop all recorded events.
+    pub fn reset(&self) {
+        self.events.lock().clear();
+    }
+}
+
+impl Default for SwqosScoreboard {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCOREBOARD_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_ranks_by_landing_rate() {
+        let board = SwqosScoreboard::new(DEFAULT_SCOREBOARD_WINDOW);
+        for _ in 0..4 {
+            board.record_submit(SwqosType::Jito, SwqosRegion::Frankfurt, 40_000, true);
+            board.record_submit(SwqosType::ZeroSlot, SwqosRegion::Frankfurt, 20_000, true);
+        }
+        board.record_submit(SwqosType::ZeroSlot, SwqosRegion::Frankfurt, 0, false);
+        board.record_win(SwqosType::Jito, SwqosRegion::Frankfurt);
+        board.record_win(SwqosType::Jito, SwqosRegion::Frankfurt);
+
+        let snapshot = board.snapshot();
+        assert_eq!(snapshot[0].swqos_type, SwqosType::Jito);
+        assert_eq!(snapshot[0].region, SwqosRegion::Frankfurt);
+        assert_eq!(snapshot[0].wins, 2);
+        assert!((snapshot[0].landing_rate - 0.5).abs() < 1e-9);
+        assert_eq!(snapshot[1].swqos_type, SwqosType::ZeroSlot);
+        assert_eq!(snapshot[1].submit_errors, 1);
+        assert!((snapshot[1].avg_submit_latency_us - 20_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn regions_of_one_provider_are_scored_separately() {
+        let board = SwqosScoreboard::new(DEFAULT_SCOREBOARD_WINDOW);
+        board.record_submit(SwqosType::Jito, SwqosRegion::Tokyo, 10_000, true);
+        board.record_submit(SwqosType::Jito, SwqosRegion::NewYork, 30_000, true);
+        board.record_win(SwqosType::Jito, SwqosRegion::NewYork);
+
+        let snapshot = board.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!((snapshot[0].region, snapshot[0].wins), (SwqosRegion::NewYork, 1));
+        assert_eq!((snapshot[1].region, snapshot[1].wins), (SwqosRegion::Tokyo, 0));
+    }
+
+    #[test]
+    fn window_expires_old_events() {
+        let board = SwqosScoreboard::new(Duration::from_millis(1));
+        board.record_submit(SwqosType::Jito, SwqosRegion::Default, 1_000, true);
+        std::thread::sleep(Duration::from_millis(5));
+        let snapshot = board.snapshot();
+        assert!(snapshot.iter().all(|s| s.submits == 0));
+    }
+}
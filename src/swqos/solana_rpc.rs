@@ -1,6 +1,8 @@
 use std::{sync::Arc, time::Instant};
 
+use solana_client::client_error::ClientErrorKind;
 use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_client::rpc_request::RpcError;
 use solana_commitment_config::CommitmentLevel;
 use solana_sdk::transaction::VersionedTransaction;
 use solana_transaction_status::UiTransactionEncoding;
@@ -8,7 +10,10 @@ use solana_transaction_status::UiTransactionEncoding;
 use crate::swqos::SwqosClientTrait;
 use crate::{
     common::SolanaRpcClient,
-    swqos::{common::poll_transaction_confirmation, SwqosType, TradeType},
+    swqos::{
+        common::{poll_transaction_confirmation, SubmitResponse},
+        SwqosType, TradeType,
+    },
 };
 use anyhow::Result;
 
@@ -24,8 +29,8 @@ impl SwqosClientTrait for SolRpcClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
-        let signature = self
+    ) -> Result<SubmitResponse> {
+        let signature = match self
             .rpc_client
             .send_transaction_with_config(
                 transaction,
@@ -37,7 +42,17 @@ impl SwqosClientTrait for SolRpcClient {
                     min_context_slot: Some(0),
                 },
             )
-            .await?;
+            .await
+        {
+            Ok(signature) => signature,
+            // JSON-RPC error from the node = rejected; transport errors stay `Err`
+            Err(e) => match e.kind() {
+                ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, .. }) => {
+                    return Ok(SubmitResponse::rejected(None, Some(*code), message.clone()));
+                }
+                _ => return Err(e.into()),
+            },
+        };
 
         let start_time = Instant::now();
         match poll_transaction_confirmation(&self.rpc_client, signature, wait_confirmation).await {
@@ -53,7 +68,7 @@ impl SwqosClientTrait for SolRpcClient {
             println!(" [rpc] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
 
-        Ok(())
+        Ok(SubmitResponse::accepted(signature, None))
     }
 
     async fn send_transactions(
@@ -63,7 +78,9 @@ impl SwqosClientTrait for SolRpcClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
@@ -75,6 +92,11 @@ impl SwqosClientTrait for SolRpcClient {
     fn get_swqos_type(&self) -> SwqosType {
         SwqosType::Default
     }
+
+    async fn health_check(&self) -> Result<()> {
+        self.rpc_client.get_health().await?;
+        Ok(())
+    }
 }
 
 impl SolRpcClient {
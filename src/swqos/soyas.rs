@@ -18,7 +18,7 @@ use std::{
 use tokio::sync::Mutex;
 
 use crate::common::SolanaRpcClient;
-use crate::swqos::common::poll_transaction_confirmation;
+use crate::swqos::common::{poll_transaction_confirmation, SubmitResponse};
 use crate::swqos::SwqosClientTrait;
 use crate::{
     constants::swqos::SOYAS_TIP_ACCOUNTS,
@@ -103,7 +103,7 @@ impl SwqosClientTrait for SoyasClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let signature = transaction.get_signature();
         let serialized_tx = bincode::serialize(transaction)?;
@@ -129,7 +129,7 @@ impl SwqosClientTrait for SoyasClient {
             println!(" signature: {:?}", signature);
             println!(" [soyas] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
-        Ok(())
+        Ok(SubmitResponse::accepted(*signature, None))
     }
 
     async fn send_transactions(
@@ -139,7 +139,9 @@ impl SwqosClientTrait for SoyasClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
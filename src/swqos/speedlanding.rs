@@ -18,7 +18,7 @@ use tokio::sync::Mutex;
 use tokio::time::timeout;
 
 use crate::common::SolanaRpcClient;
-use crate::swqos::common::poll_transaction_confirmation;
+use crate::swqos::common::{poll_transaction_confirmation, SubmitResponse};
 use crate::swqos::serialization::serialize_transaction_bincode_sync;
 use crate::swqos::SwqosClientTrait;
 use crate::{
@@ -149,7 +149,7 @@ impl SwqosClientTrait for SpeedlandingClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let (buf_guard, signature) = serialize_transaction_bincode_sync(transaction)?;
         let connection = self.ensure_connected().await?;
@@ -186,7 +186,8 @@ impl SwqosClientTrait for SpeedlandingClient {
             println!(" signature: {:?}", signature);
             println!(" [speedlanding] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
-        Ok(())
+        // QUIC is fire-and-forget: no provider verdict, sent = accepted
+        Ok(SubmitResponse::accepted(signature, None))
     }
 
     async fn send_transactions(
@@ -196,7 +197,9 @@ impl SwqosClientTrait for SpeedlandingClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
@@ -1,5 +1,6 @@
 use crate::swqos::common::{
-    default_http_client_builder, poll_transaction_confirmation, serialize_transaction_and_encode,
+    default_http_client_builder, log_submit_response, poll_transaction_confirmation,
+    probe_http_endpoint, serialize_transaction_and_encode, SubmitResponse,
 };
 use rand::seq::IndexedRandom;
 use reqwest::Client;
@@ -30,7 +31,7 @@ impl SwqosClientTrait for ZeroSlotClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         self.send_transaction(trade_type, transaction, wait_confirmation).await
     }
 
@@ -54,6 +55,10 @@ impl SwqosClientTrait for ZeroSlotClient {
     fn get_swqos_type(&self) -> SwqosType {
         SwqosType::ZeroSlot
     }
+
+    async fn health_check(&self) -> Result<()> {
+        probe_http_endpoint(&self.http_client, &self.endpoint).await
+    }
 }
 
 impl ZeroSlotClient {
@@ -68,7 +73,7 @@ impl ZeroSlotClient {
         trade_type: TradeType,
         transaction: &VersionedTransaction,
         wait_confirmation: bool,
-    ) -> Result<()> {
+    ) -> Result<SubmitResponse> {
         let start_time = Instant::now();
         let (content, signature) =
             serialize_transaction_and_encode(transaction, UiTransactionEncoding::Base64)?;
@@ -89,25 +94,20 @@ impl ZeroSlotClient {
         url.push_str(&self.auth_token);
 
         // 4. Use `text().await?` directly, avoiding async JSON parsing from `json().await?`
-        let response_text = self
+        let response = self
             .http_client
             .post(&url)
             .body(request_body) // Pass string directly, avoiding `json()` overhead
             .header("Content-Type", "application/json") // Explicitly specify JSON header
             .send()
-            .await?
-            .text()
             .await?;
+        let http_status = response.status().as_u16();
+        let response_text = response.text().await?;
 
-        // 5. Use `serde_json::from_str()` to parse JSON, reducing extra wait from `.json().await?`
-        if let Ok(response_json) = serde_json::from_str::<serde_json::Value>(&response_text) {
-            if response_json.get("result").is_some() {
-                println!(" [0slot] {} submitted: {:?}", trade_type, start_time.elapsed());
-            } else if let Some(_error) = response_json.get("error") {
-                eprintln!(" [0slot] {} submission failed: {:?}", trade_type, _error);
-            }
-        } else {
-            eprintln!(" [0slot] {} submission failed: {:?}", trade_type, response_text);
+        let submit_response = SubmitResponse::from_http(http_status, &response_text, signature);
+        log_submit_response("0slot", trade_type, &submit_response, start_time.elapsed());
+        if !submit_response.is_accepted() {
+            return Ok(submit_response);
         }
 
         let start_time: Instant = Instant::now();
@@ -124,7 +124,7 @@ impl ZeroSlotClient {
             println!(" [0slot] {} confirmed: {:?}", trade_type, start_time.elapsed());
         }
 
-        Ok(())
+        Ok(submit_response)
     }
 
     pub async fn send_transactions(
@@ -134,7 +134,9 @@ impl ZeroSlotClient {
         wait_confirmation: bool,
     ) -> Result<()> {
         for transaction in transactions {
-            self.send_transaction(trade_type, transaction, wait_confirmation).await?;
+            self.send_transaction(trade_type, transaction, wait_confirmation)
+                .await?
+                .into_result()?;
         }
         Ok(())
     }
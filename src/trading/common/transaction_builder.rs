@@ -76,6 +76,46 @@ pub async fn build_transaction(
     .await
 }
 
+/// Max serialized transaction size accepted by the network (one packet).
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Serialized size of the transaction `build_transaction` produces for `business_instructions`,
+/// including the nonce advance, tip transfer and compute budget instructions it adds.
+/// Middlewares are not applied.
+pub fn estimate_transaction_size(
+    payer: &Pubkey,
+    business_instructions: &[Instruction],
+    address_lookup_table_account: Option<&AddressLookupTableAccount>,
+    with_tip: bool,
+    durable_nonce: Option<&DurableNonceInfo>,
+) -> usize {
+    let mut instructions = Vec::with_capacity(business_instructions.len() + 4);
+    if let Some(nonce_account) = durable_nonce.and_then(|n| n.nonce_account) {
+        instructions.push(solana_system_interface::instruction::advance_nonce_account(
+            &nonce_account,
+            payer,
+        ));
+    }
+    if with_tip {
+        // Any account outside the message stands in for the SWQOS tip account.
+        instructions.push(transfer(payer, &Pubkey::new_from_array([1; 32]), 1));
+    }
+    super::compute_budget_manager::extend_compute_budget_instructions(&mut instructions, 1, 1);
+    instructions.extend_from_slice(business_instructions);
+
+    let mut builder = acquire_builder();
+    let message = builder.build_zero_alloc(
+        payer,
+        &instructions,
+        address_lookup_table_account,
+        Hash::default(),
+    );
+    // shortvec signature count + one signature
+    let size = 1 + 64 + message.serialize().len();
+    release_builder(builder);
+    size
+}
+
 async fn build_versioned_transaction(
     payer: &Arc<Keypair>,
     instructions: Vec<Instruction>,
@@ -2,6 +2,9 @@
 //!
 //! - **Pool**: Pre-spawned workers; hot path only enqueues jobs (no per-call tokio::spawn).
 //! - **Arc**: Shared data is behind `Arc` so "clone" is just a refcount increment (no data copy).
+//! - **Stats**: Each job records its submit on the scoreboard and the circuit breaker as soon as its provider
+//!   answers, so slow providers are counted even when the batch returns early; local build failures are not
+//!   counted against the provider.
 //! - **Refs**: `build_transaction` takes `&Arc<..>`, `Option<&DurableNonceInfo>`, `Option<&AddressLookupTableAccount>` so the worker passes refs only (zero clone on worker path).
 
 use anyhow::{anyhow, Result};
@@ -24,8 +27,11 @@ type FnvHashMap<K, V> = HashMap<K, V, BuildHasherDefault<FnvHasher>>;
 
 use crate::{
     common::nonce_cache::DurableNonceInfo,
-    common::{GasFeeStrategy, SolanaRpcClient},
-    swqos::{SwqosClient, SwqosType, TradeType},
+    common::{GasFeeStrategy, GasFeeStrategyType, SolanaRpcClient},
+    swqos::{
+        health::SwqosHealthMonitor, scoreboard::SwqosScoreboard, SubmitResponse, SwqosClient,
+        SwqosClientKey, SwqosRegion, SwqosType, TradeType,
+    },
     trading::{common::build_transaction, MiddlewareManager},
 };
 
@@ -45,6 +51,8 @@ struct SwqosSharedContext {
     is_buy: bool,
     wait_transaction_confirmed: bool,
     with_tip: bool,
+    swqos_scoreboard: Option<Arc<SwqosScoreboard>>,
+    swqos_health: Option<Arc<SwqosHealthMonitor>>,
     collector: Arc<ResultCollector>,
 }
 
@@ -56,7 +64,8 @@ struct SwqosJob {
     unit_price: u64,
     tip_account: Arc<Pubkey>,
     swqos_client: Arc<SwqosClient>,
-    swqos_type: SwqosType,
+    client_key: SwqosClientKey,
+    gas_fee_strategy_type: GasFeeStrategyType,
     core_id: Option<core_affinity::CoreId>,
     use_affinity: bool,
 }
@@ -82,7 +91,7 @@ async fn run_one_swqos_job(job: SwqosJob) {
         s.middleware_manager.as_ref(),
         s.protocol_name,
         s.is_buy,
-        job.swqos_type != SwqosType::Default,
+        job.client_key.swqos_type != SwqosType::Default,
         &job.tip_account,
         tip_amount,
         s.durable_nonce.as_ref(),
@@ -93,16 +102,18 @@ async fn run_one_swqos_job(job: SwqosJob) {
         Err(e) => {
             s.collector.submit(TaskResult {
                 success: false,
-                signature: Signature::default(),
                 error: Some(e),
-                swqos_type: job.swqos_type,
                 landed_on_chain: false,
-                submit_done_us: crate::common::clock::now_micros(),
+                route: job.route(Signature::default(), tip_amount, false, 0),
             });
             return;
         }
     };
 
+    let send_start_us = crate::common::clock::now_micros();
+    let mut rejection: Option<SubmitResponse> = None;
+    // Transport errors and provider-side (5xx) failures; what the circuit breaker counts.
+    let mut provider_error: Option<String> = None;
     let (success, err, landed_on_chain) = match job
         .swqos_client
         .send_transaction(
@@ -112,22 +123,73 @@ async fn run_one_swqos_job(job: SwqosJob) {
         )
         .await
     {
-        Ok(()) => (true, None, true),
+        Ok(response) if response.is_accepted() => (true, None, true),
+        Ok(response) => {
+            let e = anyhow!("{} {}", job.client_key, response);
+            if response.http_status.is_some_and(|status| status >= 500) {
+                provider_error = Some(response.to_string());
+            }
+            rejection = Some(response);
+            (false, Some(e), false)
+        }
         Err(e) => {
             let landed = is_landed_error(&e);
+            if !landed {
+                provider_error = Some(e.to_string());
+            }
             (false, Some(e), landed)
         }
     };
 
+    let send_latency_us = crate::common::clock::now_micros() - send_start_us;
+    if let Some(board) = s.swqos_scoreboard.as_ref() {
+        let key = &job.client_key;
+        board.record_submit(
+            key.swqos_type,
+            key.region,
+            send_latency_us,
+            success || landed_on_chain,
+        );
+    }
+    if let Some(health) = s.swqos_health.as_ref() {
+        match provider_error {
+            Some(error) => health.record_failure(&job.client_key, &error),
+            None => health.record_success(&job.client_key),
+        }
+    }
     let sig = transaction.signatures.first().copied().unwrap_or_default();
-    s.collector.submit(TaskResult {
-        success,
-        signature: sig,
-        error: err,
-        swqos_type: job.swqos_type,
-        landed_on_chain,
-        submit_done_us: crate::common::clock::now_micros(),
-    });
+    let route = SwqosRoute {
+        provider_rejected: rejection.is_some(),
+        rate_limited: rejection.as_ref().is_some_and(|r| r.rate_limited),
+        ..job.route(sig, tip_amount, success || landed_on_chain, send_latency_us)
+    };
+    s.collector.submit(TaskResult { success, error: err, landed_on_chain, route });
+}
+
+impl SwqosJob {
+    /// Route record for this job's transaction; stamps `submit_done_us` with the current time.
+    #[inline]
+    fn route(
+        &self,
+        signature: Signature,
+        tip: f64,
+        submit_ok: bool,
+        submit_latency_us: i64,
+    ) -> SwqosRoute {
+        SwqosRoute {
+            signature,
+            swqos_type: self.client_key.swqos_type,
+            region: self.client_key.region,
+            gas_fee_strategy_type: self.gas_fee_strategy_type,
+            tip,
+            cu_price: self.unit_price,
+            submit_ok,
+            provider_rejected: false,
+            rate_limited: false,
+            submit_latency_us,
+            submit_done_us: crate::common::clock::now_micros(),
+        }
+    }
 }
 
 async fn swqos_worker_loop(queue: Arc<ArrayQueue<SwqosJob>>, notify: Arc<Notify>) {
@@ -154,15 +216,48 @@ fn ensure_swqos_pool(queue: Arc<ArrayQueue<SwqosJob>>) {
     }
 }
 
+/// Which SWQOS route produced a given signature.
+///
+/// With durable nonces every job signs a different transaction (different tip account / fees),
+/// so the signature alone identifies the provider and fee config that landed.
+#[derive(Debug, Clone, Copy)]
+pub struct SwqosRoute {
+    pub signature: Signature,
+    pub swqos_type: SwqosType,
+    /// Region of the client that submitted (`Default` for the RPC client).
+    pub region: SwqosRegion,
+    pub gas_fee_strategy_type: GasFeeStrategyType,
+    /// Tip actually attached to the transaction (SOL); 0.0 when submitted without tip.
+    pub tip: f64,
+    /// Compute unit price (micro-lamports).
+    pub cu_price: u64,
+    /// Whether the provider accepted the transaction (false on build or send error).
+    pub submit_ok: bool,
+    /// Provider answered and rejected the transaction (vs. transport error / timeout).
+    pub provider_rejected: bool,
+    /// Provider rejected the transaction because of rate limiting.
+    pub rate_limited: bool,
+    /// Time spent in the provider's `send_transaction` (microseconds); 0 if the build failed.
+    pub submit_latency_us: i64,
+    /// Microsecond timestamp when this task finished (SWQOS returned); for per-SWQOS event→submit timing.
+    pub submit_done_us: i64,
+}
+
+/// Callback invoked with the winning route once a multi-SWQOS submit is confirmed on-chain.
+pub type LandedRouteHandler = Arc<dyn Fn(&SwqosRoute) + Send + Sync>;
+
+/// Find the route whose transaction carries `signature` (e.g. the one returned by confirmation polling).
+#[inline]
+pub fn find_route<'a>(routes: &'a [SwqosRoute], signature: &Signature) -> Option<&'a SwqosRoute> {
+    routes.iter().find(|r| r.signature == *signature)
+}
+
 #[repr(align(64))]
 struct TaskResult {
     success: bool,
-    signature: Signature,
     error: Option<anyhow::Error>,
-    swqos_type: SwqosType,
     landed_on_chain: bool,
-    /// Microsecond timestamp when this task finished (SWQOS returned); for per-SWQOS event→submit timing.
-    submit_done_us: i64,
+    route: SwqosRoute,
 }
 
 /// Check if an error indicates the transaction landed on-chain (vs network/timeout error)
@@ -189,6 +284,30 @@ fn is_landed_error(error: &anyhow::Error) -> bool {
     false
 }
 
+/// Whether a trade outcome proves its transaction did not execute: nothing was submitted (build
+/// or submit error), or the transaction landed and failed on-chain. A confirmation timeout or an
+/// unconfirmed submit proves nothing.
+pub(crate) fn trade_not_executed(
+    outcome: &Result<(bool, Vec<Signature>, Option<anyhow::Error>)>,
+) -> bool {
+    use crate::swqos::common::TradeError;
+
+    match outcome {
+        Err(_) => true,
+        Ok((true, _, _)) => false,
+        Ok((false, signatures, error)) => {
+            nothing_submitted(signatures)
+                || error.as_ref().is_some_and(|e| e.downcast_ref::<TradeError>().is_some())
+        }
+    }
+}
+
+/// No signature in `signatures` belongs to a built transaction.
+#[inline]
+pub(crate) fn nothing_submitted(signatures: &[Signature]) -> bool {
+    signatures.iter().all(|s| *s == Signature::default())
+}
+
 struct ResultCollector {
     results: Arc<ArrayQueue<TaskResult>>,
     success_flag: Arc<AtomicBool>,
@@ -227,7 +346,7 @@ impl ResultCollector {
 
     async fn wait_for_success(
         &self,
-    ) -> Option<(bool, Vec<Signature>, Option<anyhow::Error>, Vec<(SwqosType, i64)>)> {
+    ) -> Option<(bool, Vec<Signature>, Option<anyhow::Error>, Vec<SwqosRoute>)> {
         let start = Instant::now();
         let timeout = std::time::Duration::from_secs(5);
         let poll_interval = std::time::Duration::from_millis(1000);
@@ -236,16 +355,16 @@ impl ResultCollector {
             if self.success_flag.load(Ordering::Acquire) {
                 let mut signatures = Vec::new();
                 let mut has_success = false;
-                let mut submit_timings = Vec::new();
+                let mut routes = Vec::new();
                 while let Some(result) = self.results.pop() {
-                    signatures.push(result.signature);
-                    submit_timings.push((result.swqos_type, result.submit_done_us));
+                    signatures.push(result.route.signature);
+                    routes.push(result.route);
                     if result.success {
                         has_success = true;
                     }
                 }
                 if has_success && !signatures.is_empty() {
-                    return Some((true, signatures, None, submit_timings));
+                    return Some((true, signatures, None, routes));
                 }
             }
 
@@ -254,17 +373,17 @@ impl ResultCollector {
             if self.landed_failed_flag.load(Ordering::Acquire) {
                 let mut signatures = Vec::new();
                 let mut landed_error = None;
-                let mut submit_timings = Vec::new();
+                let mut routes = Vec::new();
                 while let Some(result) = self.results.pop() {
-                    signatures.push(result.signature);
-                    submit_timings.push((result.swqos_type, result.submit_done_us));
+                    signatures.push(result.route.signature);
+                    routes.push(result.route);
                     // Prefer the error from the tx that actually landed
                     if result.landed_on_chain && result.error.is_some() {
                         landed_error = result.error;
                     }
                 }
                 if !signatures.is_empty() {
-                    return Some((false, signatures, landed_error, submit_timings));
+                    return Some((false, signatures, landed_error, routes));
                 }
             }
 
@@ -273,10 +392,10 @@ impl ResultCollector {
                 let mut signatures = Vec::new();
                 let mut last_error = None;
                 let mut any_success = false;
-                let mut submit_timings = Vec::new();
+                let mut routes = Vec::new();
                 while let Some(result) = self.results.pop() {
-                    signatures.push(result.signature);
-                    submit_timings.push((result.swqos_type, result.submit_done_us));
+                    signatures.push(result.route.signature);
+                    routes.push(result.route);
                     if result.success {
                         any_success = true;
                     }
@@ -285,7 +404,7 @@ impl ResultCollector {
                     }
                 }
                 if !signatures.is_empty() {
-                    return Some((any_success, signatures, last_error, submit_timings));
+                    return Some((any_success, signatures, last_error, routes));
                 }
                 return None;
             }
@@ -297,17 +416,15 @@ impl ResultCollector {
         }
     }
 
-    fn get_first(
-        &self,
-    ) -> Option<(bool, Vec<Signature>, Option<anyhow::Error>, Vec<(SwqosType, i64)>)> {
+    fn get_first(&self) -> Option<(bool, Vec<Signature>, Option<anyhow::Error>, Vec<SwqosRoute>)> {
         let mut signatures = Vec::new();
         let mut has_success = false;
         let mut last_error = None;
-        let mut submit_timings = Vec::new();
+        let mut routes = Vec::new();
 
         while let Some(result) = self.results.pop() {
-            signatures.push(result.signature);
-            submit_timings.push((result.swqos_type, result.submit_done_us));
+            signatures.push(result.route.signature);
+            routes.push(result.route);
             if result.success {
                 has_success = true;
             }
@@ -317,7 +434,7 @@ impl ResultCollector {
         }
 
         if !signatures.is_empty() {
-            Some((has_success, signatures, last_error, submit_timings))
+            Some((has_success, signatures, last_error, routes))
         } else {
             None
         }
@@ -328,7 +445,7 @@ impl ResultCollector {
     async fn wait_for_all_submitted(
         &self,
         timeout_secs: u64,
-    ) -> Option<(bool, Vec<Signature>, Option<anyhow::Error>, Vec<(SwqosType, i64)>)> {
+    ) -> Option<(bool, Vec<Signature>, Option<anyhow::Error>, Vec<SwqosRoute>)> {
         let start = Instant::now();
         let timeout = std::time::Duration::from_secs(timeout_secs);
         let poll_interval = std::time::Duration::from_millis(2);
@@ -342,7 +459,8 @@ impl ResultCollector {
     }
 }
 
-/// Execute trade on multiple SWQOS clients in parallel; returns success flag, all signatures, and last error.
+/// Execute trade on multiple SWQOS clients in parallel; returns success flag, all signatures, last error,
+/// and the per-signature [`SwqosRoute`] (provider, fee strategy, tip, cu_price, submit time).
 pub async fn execute_parallel(
     swqos_clients: &[Arc<SwqosClient>],
     payer: Arc<Keypair>,
@@ -359,7 +477,9 @@ pub async fn execute_parallel(
     gas_fee_strategy: GasFeeStrategy,
     use_core_affinity: bool,
     check_min_tip: bool,
-) -> Result<(bool, Vec<Signature>, Option<anyhow::Error>, Vec<(SwqosType, i64)>)> {
+    swqos_scoreboard: Option<Arc<SwqosScoreboard>>,
+    swqos_health: Option<Arc<SwqosHealthMonitor>>,
+) -> Result<(bool, Vec<Signature>, Option<anyhow::Error>, Vec<SwqosRoute>)> {
     let _exec_start = Instant::now();
 
     if swqos_clients.is_empty() {
@@ -436,6 +556,8 @@ pub async fn execute_parallel(
         is_buy,
         wait_transaction_confirmed,
         with_tip,
+        swqos_scoreboard,
+        swqos_health,
         collector: collector.clone(),
     });
 
@@ -448,7 +570,7 @@ pub async fn execute_parallel(
             FnvHashMap::with_capacity_and_hasher(task_configs.len(), BuildHasherDefault::default());
         for (i, swqos_client, gas_fee_strategy_config) in task_configs {
             let core_id = cores.get(i % cores.len().max(1)).copied();
-            let swqos_type = swqos_client.get_swqos_type();
+            let client_key = swqos_client.client_key();
             let key = Arc::as_ptr(&swqos_client) as *const ();
             let tip_account = match tip_cache.get(&key) {
                 Some(tip) => tip.clone(),
@@ -471,7 +593,8 @@ pub async fn execute_parallel(
                 unit_price,
                 tip_account,
                 swqos_client,
-                swqos_type,
+                client_key,
+                gas_fee_strategy_type: gas_fee_strategy_config.1,
                 core_id,
                 use_affinity: use_core_affinity,
             };
@@ -494,13 +617,13 @@ pub async fn execute_parallel(
             Some(anyhow!("No SWQOS result within {}s", SUBMIT_TIMEOUT_SECS)),
             vec![],
         ));
-        let (success, signatures, last_error, submit_timings) = ret;
-        return Ok((success, signatures, last_error, submit_timings));
+        let (success, signatures, last_error, routes) = ret;
+        return Ok((success, signatures, last_error, routes));
     }
 
     if let Some(result) = collector.wait_for_success().await {
-        let (success, signatures, last_error, submit_timings) = result;
-        Ok((success, signatures, last_error, submit_timings))
+        let (success, signatures, last_error, routes) = result;
+        Ok((success, signatures, last_error, routes))
     } else {
         Err(anyhow!("All transactions failed"))
     }
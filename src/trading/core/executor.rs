@@ -18,7 +18,7 @@ use crate::{
     perf::syscall_bypass::SystemCallBypassManager,
     swqos::common::poll_any_transaction_confirmation,
     trading::core::{
-        async_executor::execute_parallel,
+        async_executor::{execute_parallel, find_route},
         execution::{InstructionProcessor, Prefetch},
         traits::TradeExecutor,
     },
@@ -144,8 +144,18 @@ impl TradeExecutor for GenericTradeExecutor {
         }
 
         let need_confirm = params.wait_transaction_confirmed;
+        // Circuit breaker: skip providers that are currently open.
+        let healthy_clients =
+            params.swqos_health.as_ref().and_then(|health| health.filter(&params.swqos_clients));
+        let candidate_clients = healthy_clients.as_deref().unwrap_or(&params.swqos_clients);
+        // Adaptive mode: fan out only to the scoreboard's top-K providers (Default always kept).
+        let selected_clients = params
+            .swqos_scoreboard
+            .as_ref()
+            .and_then(|board| board.select(candidate_clients))
+            .filter(|clients| !clients.is_empty());
         let result = execute_parallel(
-            &params.swqos_clients,
+            selected_clients.as_deref().unwrap_or(candidate_clients),
             params.payer,
             params.rpc.clone(),
             final_instructions,
@@ -160,18 +170,19 @@ impl TradeExecutor for GenericTradeExecutor {
             params.gas_fee_strategy,
             params.use_core_affinity,
             params.check_min_tip,
+            params.swqos_scoreboard.clone(),
+            params.swqos_health.clone(),
         )
         .await;
 
         let log_enabled = params.log_enabled && crate::common::sdk_log::sdk_log_enabled();
 
-        let (ok, signatures, err, submit_timings) = match result {
-            Ok((success, sigs, last_error, timings)) => {
-                (success, sigs, last_error.map(|e| anyhow::anyhow!("{}", e)), timings)
+        let (ok, signatures, err, routes) = match result {
+            Ok((success, sigs, last_error, routes)) => {
+                (success, sigs, last_error.map(|e| anyhow::anyhow!("{}", e)), routes)
             }
             Err(e) => (false, vec![], Some(anyhow::anyhow!("{}", e)), vec![]),
         };
-        let submit_timings_ref: &[(crate::swqos::SwqosType, i64)] = submit_timings.as_slice();
 
         let result = if need_confirm {
             let confirm_result = if let Some(rpc) = params.rpc.as_ref() {
@@ -199,18 +210,39 @@ impl TradeExecutor for GenericTradeExecutor {
                             }
                             if let Some(confirm_us) = confirm_done_us {
                                 let total_ms = (confirm_us - start_us) as f64 / 1000.0;
-                                for (swqos_type, submit_done_us) in submit_timings_ref {
+                                for route in &routes {
                                     let submit_ms =
-                                        (*submit_done_us - start_us).max(0) as f64 / 1000.0;
+                                        (route.submit_done_us - start_us).max(0) as f64 / 1000.0;
                                     let confirmed_ms =
-                                        (confirm_us - *submit_done_us).max(0) as f64 / 1000.0;
-                                    println!(" [SDK] {} {:?} submit: {:.4} ms, confirmed: {:.4} ms, total: {:.4} ms", dir, swqos_type, submit_ms, confirmed_ms, total_ms);
+                                        (confirm_us - route.submit_done_us).max(0) as f64 / 1000.0;
+                                    println!(" [SDK] {} {:?} submit: {:.4} ms, confirmed: {:.4} ms, total: {:.4} ms", dir, route.swqos_type, submit_ms, confirmed_ms, total_ms);
                                 }
                             }
                         }
                     }
                     match poll_res {
-                        Ok(_) => (true, signatures, None),
+                        Ok(landed_sig) => {
+                            // Win attribution: the confirmed signature identifies the SWQOS route that landed.
+                            if let Some(route) = find_route(&routes, &landed_sig) {
+                                if log_enabled {
+                                    println!(
+                                        " [SDK] {} landed via {:?} ({:?}, tip: {} SOL, cu_price: {})",
+                                        if is_buy { "Buy" } else { "Sell" },
+                                        route.swqos_type,
+                                        route.gas_fee_strategy_type,
+                                        route.tip,
+                                        route.cu_price
+                                    );
+                                }
+                                if let Some(board) = params.swqos_scoreboard.as_ref() {
+                                    board.record_win(route.swqos_type, route.region);
+                                }
+                                if let Some(handler) = params.landed_route_handler.as_ref() {
+                                    handler(route);
+                                }
+                            }
+                            (true, signatures, None)
+                        }
                         Err(e) => (false, signatures, Some(e)),
                     }
                 }
@@ -236,11 +268,11 @@ impl TradeExecutor for GenericTradeExecutor {
                             (end_us - start_us) as f64 / 1000.0
                         );
                     }
-                    for (swqos_type, submit_done_us) in submit_timings_ref {
-                        let submit_ms = (*submit_done_us - start_us).max(0) as f64 / 1000.0;
+                    for route in &routes {
+                        let submit_ms = (route.submit_done_us - start_us).max(0) as f64 / 1000.0;
                         println!(
                             " [SDK] {} {:?} submit: {:.4} ms, confirmed: -, total: {:.4} ms",
-                            dir, swqos_type, submit_ms, submit_ms
+                            dir, route.swqos_type, submit_ms, submit_ms
                         );
                     }
                 }
@@ -1,12 +1,17 @@
 use crate::common::bonding_curve::BondingCurveAccount;
 use crate::common::nonce_cache::DurableNonceInfo;
 use crate::common::spl_associated_token_account::get_associated_token_address_with_program_id;
+use crate::common::spl_token_2022::{TransferFee, TransferFeeConfig};
 use crate::common::{GasFeeStrategy, SolanaRpcClient};
-use crate::constants::TOKEN_PROGRAM;
+use crate::constants::{TOKEN_PROGRAM, TOKEN_PROGRAM_2022};
+use crate::instruction::utils::bonk_types::{AmmFeeOn, CurveParams, MintParams, VestingParams};
 use crate::instruction::utils::pumpfun::global_constants::MAYHEM_FEE_RECIPIENT;
 use crate::instruction::utils::pumpswap::accounts::MAYHEM_FEE_RECIPIENT as MAYHEM_FEE_RECIPIENT_SWAP;
+use crate::swqos::health::SwqosHealthMonitor;
+use crate::swqos::scoreboard::SwqosScoreboard;
 use crate::swqos::{SwqosClient, TradeType};
 use crate::trading::common::get_multi_token_balances;
+use crate::trading::core::async_executor::LandedRouteHandler;
 use crate::trading::MiddlewareManager;
 use solana_hash::Hash;
 use solana_sdk::message::AddressLookupTableAccount;
@@ -37,6 +42,62 @@ impl DexParamEnum {
             DexParamEnum::MeteoraDammV2(p) => p,
         }
     }
+
+    /// DEX these params are for.
+    pub fn dex_type(&self) -> crate::trading::factory::DexType {
+        use crate::trading::factory::DexType;
+        match self {
+            DexParamEnum::PumpFun(_) => DexType::PumpFun,
+            DexParamEnum::PumpSwap(_) => DexType::PumpSwap,
+            DexParamEnum::Bonk(_) => DexType::Bonk,
+            DexParamEnum::RaydiumCpmm(_) => DexType::RaydiumCpmm,
+            DexParamEnum::RaydiumAmmV4(_) => DexType::RaydiumAmmV4,
+            DexParamEnum::MeteoraDammV2(_) => DexType::MeteoraDammV2,
+        }
+    }
+
+    /// Token program these params record for `mint`; `None` when `mint` is not traded by them.
+    /// Launchpad quote tokens (WSOL, USD1) are classic SPL tokens.
+    pub fn token_program_of(&self, mint: &Pubkey) -> Option<Pubkey> {
+        use crate::constants::{SOL_TOKEN_ACCOUNT, USD1_TOKEN_ACCOUNT, WSOL_TOKEN_ACCOUNT};
+        let side = |(a, program_a): (Pubkey, Pubkey), (b, program_b): (Pubkey, Pubkey)| {
+            if a == *mint {
+                Some(program_a)
+            } else if b == *mint {
+                Some(program_b)
+            } else {
+                None
+            }
+        };
+        match self {
+            DexParamEnum::PumpFun(p) => {
+                if *mint == WSOL_TOKEN_ACCOUNT || *mint == SOL_TOKEN_ACCOUNT {
+                    Some(TOKEN_PROGRAM)
+                } else {
+                    Some(p.token_program)
+                }
+            }
+            DexParamEnum::PumpSwap(p) => {
+                side((p.base_mint, p.base_token_program), (p.quote_mint, p.quote_token_program))
+            }
+            DexParamEnum::Bonk(p) => {
+                if *mint == WSOL_TOKEN_ACCOUNT || *mint == USD1_TOKEN_ACCOUNT {
+                    Some(TOKEN_PROGRAM)
+                } else {
+                    Some(p.mint_token_program)
+                }
+            }
+            DexParamEnum::RaydiumCpmm(p) => {
+                side((p.base_mint, p.base_token_program), (p.quote_mint, p.quote_token_program))
+            }
+            DexParamEnum::RaydiumAmmV4(p) => {
+                side((p.coin_mint, TOKEN_PROGRAM), (p.pc_mint, TOKEN_PROGRAM))
+            }
+            DexParamEnum::MeteoraDammV2(p) => {
+                side((p.token_a_mint, p.token_a_program), (p.token_b_mint, p.token_b_program))
+            }
+        }
+    }
 }
 
 /// Swap parameters
@@ -56,7 +117,8 @@ pub struct SwapParams {
     pub wait_transaction_confirmed: bool,
     pub protocol_params: DexParamEnum,
     pub open_seed_optimize: bool,
-    pub swqos_clients: Vec<Arc<SwqosClient>>,
+    /// Snapshot of the infrastructure's SWQOS client set taken when the trade started.
+    pub swqos_clients: Arc<Vec<Arc<SwqosClient>>>,
     pub middleware_manager: Option<Arc<MiddlewareManager>>,
     pub durable_nonce: Option<DurableNonceInfo>,
     pub with_tip: bool,
@@ -80,6 +142,60 @@ pub struct SwapParams {
     /// When Some(false), uses regular buy instruction where slippage is applied to SOL/quote input.
     /// This option only applies to PumpFun and PumpSwap DEXes; it is ignored for other DEXes.
     pub use_exact_sol_amount: Option<bool>,
+    /// Optional callback receiving the SWQOS route (provider, fee strategy, tip, cu_price) whose
+    /// transaction landed. Only invoked when `wait_transaction_confirmed` is true.
+    pub landed_route_handler: Option<LandedRouteHandler>,
+    /// Scoreboard fed with submit/landing results; also filters providers when adaptive mode is on.
+    pub swqos_scoreboard: Option<Arc<SwqosScoreboard>>,
+    /// Circuit breaker: open providers are skipped and submit results feed the breaker.
+    pub swqos_health: Option<Arc<SwqosHealthMonitor>>,
+}
+
+#[cfg(test)]
+impl SwapParams {
+    /// Params for building instructions offline: no RPC, SWQOS clients or handlers.
+    pub(crate) fn for_test(
+        trade_type: TradeType,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        protocol_params: DexParamEnum,
+    ) -> Self {
+        Self {
+            rpc: None,
+            payer: Arc::new(Keypair::new()),
+            trade_type,
+            input_mint,
+            input_token_program: None,
+            output_mint,
+            output_token_program: None,
+            input_amount: Some(1_000_000),
+            slippage_basis_points: Some(100),
+            address_lookup_table_account: None,
+            recent_blockhash: None,
+            wait_transaction_confirmed: false,
+            protocol_params,
+            open_seed_optimize: true,
+            swqos_clients: Arc::new(Vec::new()),
+            middleware_manager: None,
+            durable_nonce: None,
+            with_tip: true,
+            create_input_mint_ata: true,
+            close_input_mint_ata: true,
+            create_output_mint_ata: true,
+            close_output_mint_ata: true,
+            fixed_output_amount: None,
+            gas_fee_strategy: GasFeeStrategy::new(),
+            simulate: false,
+            log_enabled: false,
+            use_core_affinity: false,
+            check_min_tip: false,
+            grpc_recv_us: None,
+            use_exact_sol_amount: None,
+            landed_route_handler: None,
+            swqos_scoreboard: None,
+            swqos_health: None,
+        }
+    }
 }
 
 impl std::fmt::Debug for SwapParams {
@@ -239,6 +355,36 @@ impl PumpFunParams {
     }
 }
 
+/// Transfer fee in force for each `(mint, token_program)`; only Token-2022 mints are read.
+pub(crate) async fn transfer_fees_by_rpc<const N: usize>(
+    rpc: &SolanaRpcClient,
+    mints: [(Pubkey, Pubkey); N],
+) -> Result<[Option<TransferFee>; N], anyhow::Error> {
+    let mut fees = [None; N];
+    let keys: Vec<Pubkey> = mints
+        .iter()
+        .filter(|(_, program)| *program == TOKEN_PROGRAM_2022)
+        .map(|(mint, _)| *mint)
+        .collect();
+    if keys.is_empty() {
+        return Ok(fees);
+    }
+    let epoch = rpc.get_epoch_info().await?.epoch;
+    let mut accounts = rpc.get_multiple_accounts(&keys).await?.into_iter();
+    for (fee, (_, program)) in fees.iter_mut().zip(mints.iter()) {
+        if *program != TOKEN_PROGRAM_2022 {
+            continue;
+        }
+        *fee = accounts
+            .next()
+            .flatten()
+            .and_then(|account| TransferFeeConfig::from_mint(&account.data))
+            .map(|config| *config.epoch_fee(epoch))
+            .filter(|fee| fee.transfer_fee_basis_points > 0);
+    }
+    Ok(fees)
+}
+
 /// PumpSwap Protocol Specific Parameters
 ///
 /// Parameters for configuring PumpSwap trading protocol, including liquidity pool information,
@@ -277,6 +423,10 @@ pub struct PumpSwapParams {
     pub is_mayhem_mode: bool,
     /// Whether the pool's coin has cashback enabled
     pub is_cashback_coin: bool,
+    /// Token-2022 transfer fee of the base mint in force when the params were built
+    pub base_transfer_fee: Option<TransferFee>,
+    /// Token-2022 transfer fee of the quote mint in force when the params were built
+    pub quote_transfer_fee: Option<TransferFee>,
 }
 
 impl PumpSwapParams {
@@ -310,9 +460,23 @@ impl PumpSwapParams {
             quote_token_program,
             is_mayhem_mode,
             is_cashback_coin,
+            base_transfer_fee: None,
+            quote_transfer_fee: None,
         }
     }
 
+    /// Set the Token-2022 transfer fees of the base and quote mints, for params built with
+    /// `new` / `from_trade` (the RPC constructors read them from the mints).
+    pub fn with_transfer_fees(
+        mut self,
+        base_transfer_fee: Option<TransferFee>,
+        quote_transfer_fee: Option<TransferFee>,
+    ) -> Self {
+        self.base_transfer_fee = base_transfer_fee;
+        self.quote_transfer_fee = quote_transfer_fee;
+        self
+    }
+
     /// Fast-path constructor for building PumpSwap parameters directly from decoded
     /// trade/event data and the accompanying instruction accounts, avoiding RPC
     /// lookups and associated latency. Token program IDs should be sourced from
@@ -397,6 +561,25 @@ impl PumpSwapParams {
             &crate::constants::TOKEN_PROGRAM,
         );
 
+        let base_token_program = if pool_data.pool_base_token_account == base_token_program_ata {
+            crate::constants::TOKEN_PROGRAM
+        } else {
+            crate::constants::TOKEN_PROGRAM_2022
+        };
+        let quote_token_program = if pool_data.pool_quote_token_account == quote_token_program_ata {
+            crate::constants::TOKEN_PROGRAM
+        } else {
+            crate::constants::TOKEN_PROGRAM_2022
+        };
+        let [base_transfer_fee, quote_transfer_fee] = transfer_fees_by_rpc(
+            rpc,
+            [
+                (pool_data.base_mint, base_token_program),
+                (pool_data.quote_mint, quote_token_program),
+            ],
+        )
+        .await?;
+
         Ok(Self {
             pool: *pool_address,
             base_mint: pool_data.base_mint,
@@ -407,22 +590,58 @@ impl PumpSwapParams {
             pool_quote_token_reserves: pool_quote_token_reserves,
             coin_creator_vault_ata: coin_creator_vault_ata,
             coin_creator_vault_authority: coin_creator_vault_authority,
-            base_token_program: if pool_data.pool_base_token_account == base_token_program_ata {
-                crate::constants::TOKEN_PROGRAM
-            } else {
-                crate::constants::TOKEN_PROGRAM_2022
-            },
+            base_token_program,
             is_cashback_coin: pool_data.is_cashback_coin,
-            quote_token_program: if pool_data.pool_quote_token_account == quote_token_program_ata {
-                crate::constants::TOKEN_PROGRAM
-            } else {
-                crate::constants::TOKEN_PROGRAM_2022
-            },
+            quote_token_program,
             is_mayhem_mode: pool_data.is_mayhem_mode,
+            base_transfer_fee,
+            quote_transfer_fee,
         })
     }
 }
 
+/// Parameters for creating a PumpSwap pool, see `TradingClient::create_pool`.
+#[derive(Clone, Debug)]
+pub struct PumpSwapCreatePoolParams {
+    /// Pool index, lets one creator own several pools for the same pair.
+    pub index: u16,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+    /// Initial base liquidity.
+    pub base_amount_in: u64,
+    /// Initial quote liquidity (lamports when quote is WSOL; wrapped in the same transaction).
+    pub quote_amount_in: u64,
+    /// Receiver of coin creator fees (`Pubkey::default()` disables creator fees).
+    pub coin_creator: Pubkey,
+    pub is_mayhem_mode: bool,
+    pub is_cashback_coin: bool,
+}
+
+impl PumpSwapCreatePoolParams {
+    /// Pool quoted in WSOL with SPL Token programs, index 0, no mayhem / cashback.
+    pub fn new(
+        base_mint: Pubkey,
+        base_amount_in: u64,
+        quote_amount_in: u64,
+        coin_creator: Pubkey,
+    ) -> Self {
+        Self {
+            index: 0,
+            base_mint,
+            quote_mint: crate::constants::WSOL_TOKEN_ACCOUNT,
+            base_token_program: TOKEN_PROGRAM,
+            quote_token_program: TOKEN_PROGRAM,
+            base_amount_in,
+            quote_amount_in,
+            coin_creator,
+            is_mayhem_mode: false,
+            is_cashback_coin: false,
+        }
+    }
+}
+
 /// Bonk protocol specific parameters
 /// Configuration parameters specific to Bonk trading protocol
 #[derive(Clone, Default)]
@@ -599,6 +818,55 @@ impl BonkParams {
     }
 }
 
+/// Parameters for launching a token on Bonk (Raydium LaunchLab), see `TradingClient::create_bonk_token`.
+#[derive(Clone, Debug)]
+pub struct BonkCreateTokenParams {
+    pub mint_params: MintParams,
+    /// Bonding curve type and its supply / fund-raising target.
+    pub curve_params: CurveParams,
+    /// Platform the pool is launched under (platform fee rate and fee vault come from it).
+    pub platform_config: Pubkey,
+    /// `GLOBAL_CONFIG` for a SOL quote pool, `USD1_GLOBAL_CONFIG` for a USD1 quote pool.
+    pub global_config: Pubkey,
+    /// Tokens locked for vesting; `None` launches without a vesting schedule.
+    pub vesting_params: Option<VestingParams>,
+    pub amm_fee_on: AmmFeeOn,
+    /// Quote amount (lamports, or USD1 base units for USD1 pools) the creator buys in the launch transaction.
+    pub initial_buy_amount: Option<u64>,
+    /// Minimum tokens out for the initial buy. Nothing can trade between `initialize` and the buy
+    /// in the same transaction, so `0` only gives up protection against a wrong curve setup.
+    pub initial_buy_minimum_amount_out: u64,
+    pub compute_unit_limit: u32,
+    pub compute_unit_price: u64,
+}
+
+impl BonkCreateTokenParams {
+    /// SOL quote pool, no vesting, no initial buy.
+    pub fn new(
+        mint_params: MintParams,
+        curve_params: CurveParams,
+        platform_config: Pubkey,
+    ) -> Self {
+        Self {
+            mint_params,
+            curve_params,
+            platform_config,
+            global_config: crate::instruction::utils::bonk::accounts::GLOBAL_CONFIG,
+            vesting_params: None,
+            amm_fee_on: AmmFeeOn::QuoteToken,
+            initial_buy_amount: None,
+            initial_buy_minimum_amount_out: 0,
+            compute_unit_limit: 400_000,
+            compute_unit_price: 0,
+        }
+    }
+
+    #[inline]
+    pub fn is_usd1_pool(&self) -> bool {
+        self.global_config == crate::instruction::utils::bonk::accounts::USD1_GLOBAL_CONFIG
+    }
+}
+
 /// RaydiumCpmm protocol specific parameters
 /// Configuration parameters specific to Raydium CPMM trading protocol
 #[derive(Clone)]
@@ -625,6 +893,10 @@ pub struct RaydiumCpmmParams {
     pub quote_token_program: Pubkey,
     /// Observation state account
     pub observation_state: Pubkey,
+    /// Token-2022 transfer fee of the base mint in force when the params were built
+    pub base_transfer_fee: Option<TransferFee>,
+    /// Token-2022 transfer fee of the quote mint in force when the params were built
+    pub quote_transfer_fee: Option<TransferFee>,
 }
 
 impl RaydiumCpmmParams {
@@ -653,9 +925,23 @@ impl RaydiumCpmmParams {
             base_token_program: input_token_program,
             quote_token_program: output_token_program,
             observation_state: observation_state,
+            base_transfer_fee: None,
+            quote_transfer_fee: None,
         }
     }
 
+    /// Set the Token-2022 transfer fees of the base and quote mints, for params built with
+    /// `from_trade` (`from_pool_address_by_rpc` reads them from the mints).
+    pub fn with_transfer_fees(
+        mut self,
+        base_transfer_fee: Option<TransferFee>,
+        quote_transfer_fee: Option<TransferFee>,
+    ) -> Self {
+        self.base_transfer_fee = base_transfer_fee;
+        self.quote_transfer_fee = quote_transfer_fee;
+        self
+    }
+
     pub async fn from_pool_address_by_rpc(
         rpc: &SolanaRpcClient,
         pool_address: &Pubkey,
@@ -670,6 +956,11 @@ impl RaydiumCpmmParams {
                 &pool.token1_mint,
             )
             .await?;
+        let [base_transfer_fee, quote_transfer_fee] = transfer_fees_by_rpc(
+            rpc,
+            [(pool.token0_mint, pool.token0_program), (pool.token1_mint, pool.token1_program)],
+        )
+        .await?;
         Ok(Self {
             pool_state: *pool_address,
             amm_config: pool.amm_config,
@@ -682,6 +973,8 @@ impl RaydiumCpmmParams {
             base_token_program: pool.token0_program,
             quote_token_program: pool.token1_program,
             observation_state: pool.observation_key,
+            base_transfer_fee,
+            quote_transfer_fee,
         })
     }
 }
@@ -748,6 +1041,10 @@ pub struct MeteoraDammV2Params {
     pub token_b_mint: Pubkey,
     pub token_a_program: Pubkey,
     pub token_b_program: Pubkey,
+    /// Token-2022 transfer fee of token A in force when the params were built
+    pub token_a_transfer_fee: Option<TransferFee>,
+    /// Token-2022 transfer fee of token B in force when the params were built
+    pub token_b_transfer_fee: Option<TransferFee>,
 }
 
 impl MeteoraDammV2Params {
@@ -768,23 +1065,48 @@ impl MeteoraDammV2Params {
             token_b_mint,
             token_a_program,
             token_b_program,
+            token_a_transfer_fee: None,
+            token_b_transfer_fee: None,
         }
     }
 
+    /// Set the Token-2022 transfer fees of tokens A and B, for params built with `new`
+    /// (`from_pool_address_by_rpc` reads them from the mints).
+    pub fn with_transfer_fees(
+        mut self,
+        token_a_transfer_fee: Option<TransferFee>,
+        token_b_transfer_fee: Option<TransferFee>,
+    ) -> Self {
+        self.token_a_transfer_fee = token_a_transfer_fee;
+        self.token_b_transfer_fee = token_b_transfer_fee;
+        self
+    }
+
     pub async fn from_pool_address_by_rpc(
         rpc: &SolanaRpcClient,
         pool_address: &Pubkey,
     ) -> Result<Self, anyhow::Error> {
         let pool_data =
             crate::instruction::utils::meteora_damm_v2::fetch_pool(rpc, pool_address).await?;
+        // token_*_flag: 0 = SPL Token, 1 = Token-2022
+        let token_program = |flag: u8| if flag == 1 { TOKEN_PROGRAM_2022 } else { TOKEN_PROGRAM };
+        let token_a_program = token_program(pool_data.token_a_flag);
+        let token_b_program = token_program(pool_data.token_b_flag);
+        let [token_a_transfer_fee, token_b_transfer_fee] = transfer_fees_by_rpc(
+            rpc,
+            [(pool_data.token_a_mint, token_a_program), (pool_data.token_b_mint, token_b_program)],
+        )
+        .await?;
         Ok(Self {
             pool: *pool_address,
             token_a_vault: pool_data.token_a_vault,
             token_b_vault: pool_data.token_b_vault,
             token_a_mint: pool_data.token_a_mint,
             token_b_mint: pool_data.token_b_mint,
-            token_a_program: TOKEN_PROGRAM,
-            token_b_program: TOKEN_PROGRAM,
+            token_a_program,
+            token_b_program,
+            token_a_transfer_fee,
+            token_b_transfer_fee,
         })
     }
 }
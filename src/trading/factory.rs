@@ -1,15 +1,20 @@
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
+
 use crate::instruction::{
     bonk::BonkInstructionBuilder, meteora_damm_v2::MeteoraDammV2InstructionBuilder,
     pumpfun::PumpFunInstructionBuilder, pumpswap::PumpSwapInstructionBuilder,
     raydium_amm_v4::RaydiumAmmV4InstructionBuilder, raydium_cpmm::RaydiumCpmmInstructionBuilder,
 };
 
-use super::core::{executor::GenericTradeExecutor, traits::TradeExecutor};
+use super::core::{
+    executor::GenericTradeExecutor,
+    traits::{InstructionBuilder, TradeExecutor},
+};
 
 /// 支持的交易协议
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DexType {
     PumpFun,
     PumpSwap,
@@ -35,6 +40,18 @@ impl TradeFactory {
         }
     }
 
+    /// 创建指定协议的指令构建器（用于组合多个协议的指令，如拆单）
+    pub fn create_instruction_builder(dex_type: DexType) -> Arc<dyn InstructionBuilder> {
+        match dex_type {
+            DexType::PumpFun => Arc::new(PumpFunInstructionBuilder),
+            DexType::PumpSwap => Arc::new(PumpSwapInstructionBuilder),
+            DexType::Bonk => Arc::new(BonkInstructionBuilder),
+            DexType::RaydiumCpmm => Arc::new(RaydiumCpmmInstructionBuilder),
+            DexType::RaydiumAmmV4 => Arc::new(RaydiumAmmV4InstructionBuilder),
+            DexType::MeteoraDammV2 => Arc::new(MeteoraDammV2InstructionBuilder),
+        }
+    }
+
     // Static instances created at compile time - zero runtime overhead
     #[inline]
     fn pumpfun_executor() -> Arc<dyn TradeExecutor> {
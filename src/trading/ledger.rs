@@ -0,0 +1,500 @@
+//! Trade ledger: every confirmed trade of a wallet, with per-mint cost basis and PnL.
+//!
+//! - **Recording**: a `TradingClient` built `with_ledger` records each buy / sell that waited for
+//!   confirmation. Amounts are read from the landed transaction (token balance changes of the
+//!   payer and its SOL change net of fee, tip and token account rent), the tip from the SWQOS
+//!   route that landed.
+//! - **Storage**: pluggable through `LedgerStore`; `JsonlLedgerStore` appends one JSON line per
+//!   trade, `MemoryLedgerStore` keeps nothing on disk.
+//! - **PnL**: average cost per (mint, quote mint). Fees and tips are in lamports and reported
+//!   apart from the quote-denominated PnL. Tokens sold beyond what the ledger saw bought carry no
+//!   cost basis.
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{UiTransactionEncoding, UiTransactionTokenBalance};
+
+use crate::common::SolanaRpcClient;
+use crate::constants::WSOL_TOKEN_ACCOUNT;
+use crate::swqos::TradeType;
+use crate::trading::core::async_executor::{LandedRouteHandler, SwqosRoute};
+
+/// One confirmed trade.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Unix seconds (block time when available).
+    pub timestamp: u64,
+    /// Landed signature.
+    pub signature: Signature,
+    /// `Buy` or `Sell`.
+    pub side: TradeType,
+    pub mint: Pubkey,
+    /// WSOL for SOL trades.
+    pub quote_mint: Pubkey,
+    /// DEX (or composed route, e.g. `Split`) the trade went through.
+    pub dex: String,
+    /// Tokens bought or sold.
+    pub token_amount: u64,
+    /// Quote spent (buy) or received (sell), excluding fee, tip and rent.
+    pub quote_amount: u64,
+    /// Transaction fee in lamports (base + priority).
+    pub network_fee: u64,
+    /// Tip in lamports.
+    pub tip: u64,
+    /// SWQOS provider that landed the trade.
+    pub swqos: Option<String>,
+}
+
+/// Ledger persistence.
+pub trait LedgerStore: Send + Sync {
+    fn append(&self, entry: &LedgerEntry) -> Result<(), anyhow::Error>;
+
+    /// Every entry stored so far, oldest first.
+    fn load(&self) -> Result<Vec<LedgerEntry>, anyhow::Error>;
+}
+
+/// Keeps entries in memory only.
+#[derive(Default)]
+pub struct MemoryLedgerStore;
+
+impl LedgerStore for MemoryLedgerStore {
+    fn append(&self, _entry: &LedgerEntry) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<LedgerEntry>, anyhow::Error> {
+        Ok(Vec::new())
+    }
+}
+
+/// Append-only JSON Lines file, one entry per line.
+pub struct JsonlLedgerStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonlLedgerStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf(), lock: Mutex::new(()) }
+    }
+}
+
+impl LedgerStore for JsonlLedgerStore {
+    fn append(&self, entry: &LedgerEntry) -> Result<(), anyhow::Error> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        let _guard = self.lock.lock();
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(&line)?;
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<LedgerEntry>, anyhow::Error> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries = Vec::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(serde_json::from_str(&line).map_err(|e| {
+                anyhow!("{}:{}: invalid ledger entry: {}", self.path.display(), i + 1, e)
+            })?);
+        }
+        Ok(entries)
+    }
+}
+
+/// Cost basis and PnL of one mint against one quote token.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MintPnl {
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub trades: usize,
+    pub tokens_bought: u64,
+    pub tokens_sold: u64,
+    /// Tokens held according to the ledger.
+    pub tokens_held: u64,
+    /// Quote cost of `tokens_held` (average cost).
+    pub cost_basis: u64,
+    /// Proceeds minus cost basis of the tokens sold (quote units).
+    pub realized_pnl: i128,
+    /// Transaction fees in lamports.
+    pub network_fees: u64,
+    /// Tips in lamports.
+    pub tips: u64,
+}
+
+impl MintPnl {
+    /// Average quote cost per held token.
+    pub fn average_cost(&self) -> f64 {
+        if self.tokens_held == 0 {
+            return 0.0;
+        }
+        self.cost_basis as f64 / self.tokens_held as f64
+    }
+
+    /// Value of `tokens_held` at `price` (quote per token) minus its cost basis.
+    pub fn unrealized_pnl(&self, price: f64) -> f64 {
+        self.tokens_held as f64 * price - self.cost_basis as f64
+    }
+
+    /// Fees and tips paid, in lamports.
+    #[inline]
+    pub fn total_fees(&self) -> u64 {
+        self.network_fees + self.tips
+    }
+
+    fn apply(&mut self, entry: &LedgerEntry) {
+        self.trades += 1;
+        self.network_fees += entry.network_fee;
+        self.tips += entry.tip;
+        match entry.side {
+            TradeType::Sell => {
+                let matched = entry.token_amount.min(self.tokens_held);
+                let cost = if self.tokens_held == 0 {
+                    0
+                } else {
+                    (self.cost_basis as u128 * matched as u128 / self.tokens_held as u128) as u64
+                };
+                self.tokens_sold += entry.token_amount;
+                self.tokens_held -= matched;
+                self.cost_basis -= cost;
+                self.realized_pnl += entry.quote_amount as i128 - cost as i128;
+            }
+            _ => {
+                self.tokens_bought += entry.token_amount;
+                self.tokens_held += entry.token_amount;
+                self.cost_basis += entry.quote_amount;
+            }
+        }
+    }
+}
+
+/// Trades recorded for a wallet, loaded from and appended to a `LedgerStore`.
+pub struct Ledger {
+    store: Box<dyn LedgerStore>,
+    entries: RwLock<Vec<LedgerEntry>>,
+}
+
+impl Ledger {
+    /// Ledger over `store`, loading the entries it already holds.
+    pub fn new(store: impl LedgerStore + 'static) -> Result<Self, anyhow::Error> {
+        let entries = store.load()?;
+        Ok(Self { store: Box::new(store), entries: RwLock::new(entries) })
+    }
+
+    /// Ledger appending to the JSONL file at `path`.
+    pub fn open_jsonl(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        Self::new(JsonlLedgerStore::new(path))
+    }
+
+    pub fn in_memory() -> Self {
+        Self { store: Box::new(MemoryLedgerStore), entries: RwLock::new(Vec::new()) }
+    }
+
+    /// Store `entry`; entries whose signature is already recorded are ignored.
+    pub fn record(&self, entry: LedgerEntry) -> Result<(), anyhow::Error> {
+        let mut entries = self.entries.write();
+        if entries.iter().any(|e| e.signature == entry.signature) {
+            return Ok(());
+        }
+        self.store.append(&entry)?;
+        entries.push(entry);
+        Ok(())
+    }
+
+    pub fn entries(&self) -> Vec<LedgerEntry> {
+        self.entries.read().clone()
+    }
+
+    pub fn entries_for(&self, mint: &Pubkey) -> Vec<LedgerEntry> {
+        self.entries.read().iter().filter(|e| e.mint == *mint).cloned().collect()
+    }
+
+    /// PnL of every (mint, quote mint) traded.
+    pub fn summary(&self) -> Vec<MintPnl> {
+        let mut pnl: HashMap<(Pubkey, Pubkey), MintPnl> = HashMap::new();
+        for entry in self.entries.read().iter() {
+            pnl.entry((entry.mint, entry.quote_mint))
+                .or_insert_with(|| MintPnl {
+                    mint: entry.mint,
+                    quote_mint: entry.quote_mint,
+                    ..Default::default()
+                })
+                .apply(entry);
+        }
+        pnl.into_values().collect()
+    }
+
+    /// PnL of `mint`, one per quote token it was traded against.
+    pub fn pnl(&self, mint: &Pubkey) -> Vec<MintPnl> {
+        self.summary().into_iter().filter(|p| p.mint == *mint).collect()
+    }
+
+    /// Realized PnL of trades against `quote_mint` made since `since` (unix seconds), net of
+    /// their fees and tips; cost basis comes from the whole history. Meaningful in lamports for
+    /// WSOL.
+    pub fn realized_pnl_since(&self, quote_mint: &Pubkey, since: u64) -> i128 {
+        let mut pnl: HashMap<Pubkey, MintPnl> = HashMap::new();
+        let mut total = 0i128;
+        for entry in self.entries.read().iter().filter(|e| e.quote_mint == *quote_mint) {
+            let mint_pnl = pnl.entry(entry.mint).or_default();
+            let before = mint_pnl.realized_pnl;
+            mint_pnl.apply(entry);
+            if entry.timestamp >= since {
+                total += mint_pnl.realized_pnl - before - (entry.network_fee + entry.tip) as i128;
+            }
+        }
+        total
+    }
+
+    /// Fees and tips paid over all trades, in lamports.
+    pub fn total_fees(&self) -> u64 {
+        self.entries.read().iter().map(|e| e.network_fee + e.tip).sum()
+    }
+}
+
+/// Amounts a landed trade moved for its payer.
+#[derive(Clone, Copy, Debug)]
+pub struct Fill {
+    /// Tokens received (buy) or sent (sell).
+    pub token_amount: u64,
+    /// Quote sent (buy) or received (sell).
+    pub quote_amount: u64,
+    pub network_fee: u64,
+    /// Block time, when the RPC returned one.
+    pub block_time: Option<i64>,
+}
+
+fn owned_by<'a>(
+    balances: &'a OptionSerializer<Vec<UiTransactionTokenBalance>>,
+    owner: &'a str,
+) -> impl Iterator<Item = &'a UiTransactionTokenBalance> {
+    let balances: &[UiTransactionTokenBalance] = match balances {
+        OptionSerializer::Some(balances) => balances,
+        _ => &[],
+    };
+    balances.iter().filter(move |b| matches!(&b.owner, OptionSerializer::Some(o) if o == owner))
+}
+
+/// Change of `owner`'s total `mint` balance.
+fn token_delta(
+    pre: &OptionSerializer<Vec<UiTransactionTokenBalance>>,
+    post: &OptionSerializer<Vec<UiTransactionTokenBalance>>,
+    owner: &str,
+    mint: &str,
+) -> i128 {
+    let total = |balances| {
+        owned_by(balances, owner)
+            .filter(|b| b.mint == mint)
+            .map(|b| b.ui_token_amount.amount.parse::<i128>().unwrap_or(0))
+            .sum::<i128>()
+    };
+    total(post) - total(pre)
+}
+
+/// Read the fill of `signature` for `owner` (the fee payer). For SOL-quoted trades the quote is
+/// the payer's SOL change plus its token accounts' lamport changes (rent moved into / out of
+/// created or closed accounts, wrapped SOL), net of the fee and `tip`.
+pub async fn fetch_fill(
+    rpc: &SolanaRpcClient,
+    signature: &Signature,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    quote_mint: &Pubkey,
+    tip: u64,
+) -> Result<Fill, anyhow::Error> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        max_supported_transaction_version: Some(0),
+        commitment: Some(solana_commitment_config::CommitmentConfig::confirmed()),
+    };
+    let mut attempt = 0;
+    let tx = loop {
+        match rpc.get_transaction_with_config(signature, config).await {
+            Ok(tx) => break tx,
+            Err(_) if attempt < 5 => {
+                attempt += 1;
+                tokio::time::sleep(Duration::from_millis(400)).await;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+    let meta =
+        tx.transaction.meta.ok_or_else(|| anyhow!("Transaction {} has no meta", signature))?;
+    if meta.err.is_some() {
+        return Err(anyhow!("Transaction {} failed", signature));
+    }
+    let owner = owner.to_string();
+    let token_delta_of = |mint: &Pubkey| {
+        token_delta(&meta.pre_token_balances, &meta.post_token_balances, &owner, &mint.to_string())
+    };
+
+    let token_delta = token_delta_of(mint);
+    let quote_delta = if *quote_mint == WSOL_TOKEN_ACCOUNT {
+        let lamport_delta = |i: usize| {
+            meta.post_balances.get(i).copied().unwrap_or(0) as i128
+                - meta.pre_balances.get(i).copied().unwrap_or(0) as i128
+        };
+        // The fee payer is account 0.
+        let mut accounts: Vec<u8> = owned_by(&meta.pre_token_balances, &owner)
+            .chain(owned_by(&meta.post_token_balances, &owner))
+            .map(|b| b.account_index)
+            .collect();
+        accounts.sort_unstable();
+        accounts.dedup();
+        lamport_delta(0)
+            + accounts.iter().map(|i| lamport_delta(*i as usize)).sum::<i128>()
+            + meta.fee as i128
+            + tip as i128
+    } else {
+        token_delta_of(quote_mint)
+    };
+    Ok(Fill {
+        token_amount: token_delta.unsigned_abs().min(u64::MAX as u128) as u64,
+        quote_amount: quote_delta.unsigned_abs().min(u64::MAX as u128) as u64,
+        network_fee: meta.fee,
+        block_time: tx.block_time,
+    })
+}
+
+/// What a trade is recorded as, fixed before it is sent.
+pub(crate) struct TradeRecord {
+    pub side: TradeType,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub dex: String,
+}
+
+/// Landed-route handler that records the trade in `ledger` (the fill is read in the background)
+/// and then calls `inner`.
+pub(crate) fn recording_handler(
+    ledger: Arc<Ledger>,
+    rpc: Arc<SolanaRpcClient>,
+    owner: Pubkey,
+    record: TradeRecord,
+    inner: Option<LandedRouteHandler>,
+) -> LandedRouteHandler {
+    let record = Arc::new(record);
+    Arc::new(move |route: &SwqosRoute| {
+        let (ledger, rpc, record, route) = (ledger.clone(), rpc.clone(), record.clone(), *route);
+        tokio::spawn(async move {
+            let tip = (route.tip * 1e9).round() as u64;
+            let fill =
+                fetch_fill(&rpc, &route.signature, &owner, &record.mint, &record.quote_mint, tip)
+                    .await;
+            let result = fill.and_then(|fill| {
+                ledger.record(LedgerEntry {
+                    timestamp: fill.block_time.map(|t| t as u64).unwrap_or_else(unix_now),
+                    signature: route.signature,
+                    side: record.side,
+                    mint: record.mint,
+                    quote_mint: record.quote_mint,
+                    dex: record.dex.clone(),
+                    token_amount: fill.token_amount,
+                    quote_amount: fill.quote_amount,
+                    network_fee: fill.network_fee,
+                    tip,
+                    swqos: Some(format!("{:?}", route.swqos_type)),
+                })
+            });
+            if let Err(e) = result {
+                tracing::warn!(
+                    target: "sol_trade_sdk",
+                    "Ledger: failed to record {}: {}",
+                    route.signature,
+                    e
+                );
+            }
+        });
+        if let Some(inner) = inner.as_ref() {
+            inner(&route);
+        }
+    })
+}
+
+/// Unix seconds now.
+#[inline]
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(side: TradeType, mint: Pubkey, token_amount: u64, quote_amount: u64) -> LedgerEntry {
+        LedgerEntry {
+            timestamp: 0,
+            signature: Signature::new_unique(),
+            side,
+            mint,
+            quote_mint: WSOL_TOKEN_ACCOUNT,
+            dex: "PumpFun".to_string(),
+            token_amount,
+            quote_amount,
+            network_fee: 5_000,
+            tip: 1_000,
+            swqos: None,
+        }
+    }
+
+    #[test]
+    fn average_cost_pnl() {
+        let mint = Pubkey::new_unique();
+        let ledger = Ledger::in_memory();
+        ledger.record(entry(TradeType::Buy, mint, 1_000, 100)).unwrap();
+        ledger.record(entry(TradeType::Buy, mint, 1_000, 300)).unwrap();
+        // Average cost 0.2; half sold for 250 -> realized 250 - 200.
+        ledger.record(entry(TradeType::Sell, mint, 1_000, 250)).unwrap();
+
+        let pnl = ledger.pnl(&mint).pop().unwrap();
+        assert_eq!(pnl.trades, 3);
+        assert_eq!(pnl.tokens_held, 1_000);
+        assert_eq!(pnl.cost_basis, 200);
+        assert_eq!(pnl.realized_pnl, 50);
+        assert_eq!(pnl.unrealized_pnl(0.3), 100.0);
+        assert_eq!(pnl.total_fees(), 18_000);
+        assert_eq!(ledger.total_fees(), 18_000);
+
+        // Selling tokens the ledger never saw bought: no cost basis.
+        ledger.record(entry(TradeType::Sell, mint, 1_500, 300)).unwrap();
+        let pnl = ledger.pnl(&mint).pop().unwrap();
+        assert_eq!((pnl.tokens_held, pnl.cost_basis, pnl.realized_pnl), (0, 0, 150));
+    }
+
+    #[test]
+    fn jsonl_store_round_trip() {
+        let path = std::env::temp_dir().join(format!("ledger-{}.jsonl", Pubkey::new_unique()));
+        let mint = Pubkey::new_unique();
+        let first = entry(TradeType::Buy, mint, 10, 20);
+        {
+            let ledger = Ledger::open_jsonl(&path).unwrap();
+            ledger.record(first.clone()).unwrap();
+            ledger.record(first.clone()).unwrap();
+            ledger.record(entry(TradeType::Sell, mint, 5, 15)).unwrap();
+        }
+        let ledger = Ledger::open_jsonl(&path).unwrap();
+        let entries = ledger.entries();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], first);
+    }
+}
@@ -0,0 +1,581 @@
+//! Limit orders fired against reserve updates fed in by the application.
+//!
+//! - **Orders**: `place_buy` / `place_sell` take the `TradeBuyParams` / `TradeSellParams` the
+//!   order trades with and a limit price in quote per token, raw units (as implied by pool
+//!   reserves). A buy fires once the price is at or below its limit, a sell at or above it.
+//! - **Prices**: `update_reserves` / `update_params` (e.g. from parsed gRPC trade events) price
+//!   the orders of one mint on one DEX; `update_params` also replaces the orders'
+//!   `extension_params` so they trade against the reserves that triggered them.
+//! - **Limit price**: a fired order sets `fixed_output_token_amount` to the output its limit
+//!   guarantees (tokens for the quote spent, quote for the tokens sold) with zero slippage, so
+//!   the swap reverts on chain rather than fill at a worse price. Orders trigger on the spot
+//!   price, so a large order only fills once the spot price is past its limit by the order's
+//!   price impact and pool fees.
+//! - **Lifecycle**: orders are removed once filled, cancelled or past `expires_at`. A fired order
+//!   goes back into the book for the next price only when its trade provably did not execute
+//!   (nothing was submitted, or it failed on-chain); when the outcome is unknown (e.g. the
+//!   confirmation timed out) it stays `Pending` and does not fire again until `resolve`d.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::anyhow;
+use futures::future::join_all;
+use parking_lot::Mutex;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::swqos::common::TradeError;
+use crate::swqos::TradeType;
+use crate::trading::core::async_executor::nothing_submitted;
+use crate::trading::core::params::DexParamEnum;
+use crate::trading::factory::DexType;
+use crate::trading::ledger::unix_now;
+use crate::trading::price_guard;
+use crate::{TradeBuyParams, TradeSellParams, TradingClient};
+
+/// Trade an order fires.
+#[derive(Clone)]
+pub enum LimitOrderParams {
+    Buy(TradeBuyParams),
+    Sell(TradeSellParams),
+}
+
+/// Where an order is in its lifecycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitOrderState {
+    /// Waiting for its price.
+    Resting,
+    /// Its trade is in flight.
+    Firing,
+    /// Its trade was submitted but did not confirm; it may still have landed. Settle it with
+    /// `LimitOrderBook::resolve`.
+    Pending,
+}
+
+/// A limit order in the book.
+#[derive(Clone)]
+pub struct LimitOrder {
+    pub id: u64,
+    pub mint: Pubkey,
+    pub dex_type: DexType,
+    /// Quote per token, raw units.
+    pub limit_price: f64,
+    /// Unix seconds after which the order is dropped.
+    pub expires_at: Option<u64>,
+    pub params: LimitOrderParams,
+    state: LimitOrderState,
+}
+
+impl LimitOrder {
+    pub fn state(&self) -> LimitOrderState {
+        self.state
+    }
+
+    pub fn side(&self) -> TradeType {
+        match self.params {
+            LimitOrderParams::Buy(_) => TradeType::Buy,
+            LimitOrderParams::Sell(_) => TradeType::Sell,
+        }
+    }
+
+    /// `price` reached the limit.
+    pub fn is_triggered(&self, price: f64) -> bool {
+        is_triggered(self.side(), self.limit_price, price)
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Output the limit price guarantees: tokens for a buy (rounded down), quote for a sell
+    /// (rounded up).
+    pub fn limit_output(&self) -> u64 {
+        let amount_in = match &self.params {
+            LimitOrderParams::Buy(p) => p.input_token_amount,
+            LimitOrderParams::Sell(p) => p.input_token_amount,
+        };
+        limit_output(self.side(), amount_in, self.limit_price)
+    }
+}
+
+#[inline]
+fn is_triggered(side: TradeType, limit_price: f64, price: f64) -> bool {
+    match side {
+        TradeType::Sell => price >= limit_price,
+        _ => price > 0.0 && price <= limit_price,
+    }
+}
+
+/// State of a fired order that did not fill: `Resting` again only when its trade was never
+/// submitted or failed on-chain, `Pending` otherwise. `From<anyhow::Error>` gives code 500 to
+/// every `TradeError` that did not come from a landed transaction.
+fn unfilled_state(signatures: &[Signature], error: Option<&TradeError>) -> LimitOrderState {
+    if nothing_submitted(signatures) || error.is_some_and(|e| e.code != 500) {
+        LimitOrderState::Resting
+    } else {
+        LimitOrderState::Pending
+    }
+}
+
+#[inline]
+fn limit_output(side: TradeType, amount_in: u64, limit_price: f64) -> u64 {
+    match side {
+        TradeType::Sell => (amount_in as f64 * limit_price).ceil() as u64,
+        _ => (amount_in as f64 / limit_price) as u64,
+    }
+}
+
+/// Outcome of one fired order.
+pub struct LimitOrderOutcome {
+    pub id: u64,
+    pub mint: Pubkey,
+    pub side: TradeType,
+    pub limit_price: f64,
+    /// Price that triggered the order.
+    pub price: f64,
+    pub success: bool,
+    /// The trade was submitted but its outcome is unknown; the order is `Pending`.
+    pub pending: bool,
+    pub signatures: Vec<Signature>,
+    pub error: Option<TradeError>,
+}
+
+/// Limit orders per mint and DEX, fired through a `TradingClient`.
+pub struct LimitOrderBook {
+    client: TradingClient,
+    orders: Mutex<HashMap<u64, LimitOrder>>,
+    next_id: AtomicU64,
+}
+
+impl LimitOrderBook {
+    pub fn new(client: TradingClient) -> Self {
+        Self { client, orders: Mutex::new(HashMap::new()), next_id: AtomicU64::new(1) }
+    }
+
+    /// Buy with `params` once the price of `params.mint` on `params.dex_type` is at or below
+    /// `limit_price`. Returns the order id.
+    pub fn place_buy(
+        &self,
+        params: TradeBuyParams,
+        limit_price: f64,
+        expires_at: Option<u64>,
+    ) -> Result<u64, anyhow::Error> {
+        let (mint, dex_type) = (params.mint, params.dex_type);
+        self.place(mint, dex_type, limit_price, expires_at, LimitOrderParams::Buy(params))
+    }
+
+    /// Sell with `params` once the price of `params.mint` on `params.dex_type` is at or above
+    /// `limit_price`. Returns the order id.
+    pub fn place_sell(
+        &self,
+        params: TradeSellParams,
+        limit_price: f64,
+        expires_at: Option<u64>,
+    ) -> Result<u64, anyhow::Error> {
+        let (mint, dex_type) = (params.mint, params.dex_type);
+        self.place(mint, dex_type, limit_price, expires_at, LimitOrderParams::Sell(params))
+    }
+
+    fn place(
+        &self,
+        mint: Pubkey,
+        dex_type: DexType,
+        limit_price: f64,
+        expires_at: Option<u64>,
+        params: LimitOrderParams,
+    ) -> Result<u64, anyhow::Error> {
+        if !limit_price.is_finite() || limit_price <= 0.0 {
+            return Err(anyhow!("Limit price must be positive, got {}", limit_price));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let order = LimitOrder {
+            id,
+            mint,
+            dex_type,
+            limit_price,
+            expires_at,
+            params,
+            state: LimitOrderState::Resting,
+        };
+        if order.limit_output() == 0 {
+            return Err(anyhow!("Limit price {} leaves no output for the order", limit_price));
+        }
+        self.orders.lock().insert(id, order);
+        Ok(id)
+    }
+
+    /// Remove order `id`; an order whose trade is in flight cannot be cancelled.
+    pub fn cancel(&self, id: u64) -> Option<LimitOrder> {
+        let mut orders = self.orders.lock();
+        if orders.get(&id)?.state == LimitOrderState::Firing {
+            return None;
+        }
+        orders.remove(&id)
+    }
+
+    /// Remove every order of `mint` whose trade is not in flight.
+    pub fn cancel_mint(&self, mint: &Pubkey) -> Vec<LimitOrder> {
+        let mut orders = self.orders.lock();
+        let ids: Vec<u64> = orders
+            .values()
+            .filter(|o| o.mint == *mint && o.state != LimitOrderState::Firing)
+            .map(|o| o.id)
+            .collect();
+        ids.iter().filter_map(|id| orders.remove(id)).collect()
+    }
+
+    /// Remove and return the orders past their expiry.
+    pub fn purge_expired(&self) -> Vec<LimitOrder> {
+        let now = unix_now();
+        let mut orders = self.orders.lock();
+        let ids: Vec<u64> = orders
+            .values()
+            .filter(|o| o.is_expired(now) && o.state != LimitOrderState::Firing)
+            .map(|o| o.id)
+            .collect();
+        ids.iter().filter_map(|id| orders.remove(id)).collect()
+    }
+
+    /// Settle `Pending` order `id` once its trade's outcome is known: a fill removes it, otherwise
+    /// it rests again. Returns the order, or `None` when `id` is not pending.
+    pub fn resolve(&self, id: u64, filled: bool) -> Option<LimitOrder> {
+        let mut orders = self.orders.lock();
+        if orders.get(&id)?.state != LimitOrderState::Pending {
+            return None;
+        }
+        if filled {
+            return orders.remove(&id);
+        }
+        let order = orders.get_mut(&id)?;
+        order.state = LimitOrderState::Resting;
+        Some(order.clone())
+    }
+
+    /// Snapshot of the resting orders.
+    pub fn orders(&self) -> Vec<LimitOrder> {
+        self.orders.lock().values().cloned().collect()
+    }
+
+    pub fn order(&self, id: u64) -> Option<LimitOrder> {
+        self.orders.lock().get(&id).cloned()
+    }
+
+    /// Feed pool reserves of `mint` on `dex_type` (`token_reserve` of the mint, `quote_reserve`
+    /// of its quote token; virtual reserves for bonding curves) and fire the orders they trigger.
+    pub async fn update_reserves(
+        &self,
+        mint: &Pubkey,
+        dex_type: DexType,
+        token_reserve: u64,
+        quote_reserve: u64,
+    ) -> Vec<LimitOrderOutcome> {
+        if token_reserve == 0 {
+            return Vec::new();
+        }
+        self.update_price(mint, dex_type, quote_reserve as f64 / token_reserve as f64).await
+    }
+
+    /// Feed fresh protocol params of `mint` (e.g. built from a trade event received at
+    /// `grpc_recv_us`): they replace the `extension_params` of the mint's orders on that DEX,
+    /// and their reserves give the price.
+    pub async fn update_params(
+        &self,
+        mint: &Pubkey,
+        params: DexParamEnum,
+        grpc_recv_us: Option<i64>,
+    ) -> Result<Vec<LimitOrderOutcome>, anyhow::Error> {
+        let price = price_guard::snapshot_price(&params, mint)?;
+        let dex_type = params.dex_type();
+        for order in self.orders.lock().values_mut() {
+            if order.mint != *mint
+                || order.dex_type != dex_type
+                || order.state == LimitOrderState::Firing
+            {
+                continue;
+            }
+            match &mut order.params {
+                LimitOrderParams::Buy(p) => {
+                    p.extension_params = params.clone();
+                    p.grpc_recv_us = grpc_recv_us;
+                }
+                LimitOrderParams::Sell(p) => {
+                    p.extension_params = params.clone();
+                    p.grpc_recv_us = grpc_recv_us;
+                }
+            }
+        }
+        Ok(self.update_price(mint, dex_type, price).await)
+    }
+
+    /// Fire the orders of `mint` on `dex_type` that `price` triggers; expired orders are dropped.
+    pub async fn update_price(
+        &self,
+        mint: &Pubkey,
+        dex_type: DexType,
+        price: f64,
+    ) -> Vec<LimitOrderOutcome> {
+        let now = unix_now();
+        let triggered: Vec<LimitOrder> = {
+            let mut orders = self.orders.lock();
+            orders.retain(|_, o| o.state == LimitOrderState::Firing || !o.is_expired(now));
+            orders
+                .values_mut()
+                .filter(|o| {
+                    o.mint == *mint
+                        && o.dex_type == dex_type
+                        && o.state == LimitOrderState::Resting
+                        && o.is_triggered(price)
+                })
+                .map(|o| {
+                    o.state = LimitOrderState::Firing;
+                    o.clone()
+                })
+                .collect()
+        };
+        if triggered.is_empty() {
+            return Vec::new();
+        }
+        join_all(triggered.into_iter().map(|order| self.fire(order, price))).await
+    }
+
+    async fn fire(&self, order: LimitOrder, price: f64) -> LimitOrderOutcome {
+        let (success, signatures, error) = match self.execute(&order).await {
+            Ok(result) => result,
+            Err(e) => (false, Vec::new(), Some(TradeError::from(e))),
+        };
+        let state = if success { None } else { Some(unfilled_state(&signatures, error.as_ref())) };
+        {
+            let mut orders = self.orders.lock();
+            match state {
+                None => {
+                    orders.remove(&order.id);
+                }
+                Some(state) => {
+                    if let Some(fired) = orders.get_mut(&order.id) {
+                        fired.state = state;
+                    }
+                }
+            }
+        }
+        LimitOrderOutcome {
+            id: order.id,
+            mint: order.mint,
+            side: order.side(),
+            limit_price: order.limit_price,
+            price,
+            success,
+            pending: state == Some(LimitOrderState::Pending),
+            signatures,
+            error,
+        }
+    }
+
+    async fn execute(
+        &self,
+        order: &LimitOrder,
+    ) -> Result<(bool, Vec<Signature>, Option<TradeError>), anyhow::Error> {
+        let limit_output = Some(order.limit_output());
+        let rpc = &self.client.infrastructure.rpc;
+        match order.params.clone() {
+            LimitOrderParams::Buy(mut params) => {
+                if params.durable_nonce.is_none() {
+                    params.recent_blockhash = Some(rpc.get_latest_blockhash().await?);
+                }
+                params.fixed_output_token_amount = limit_output;
+                params.slippage_basis_points = Some(0);
+                self.client.buy(params).await
+            }
+            LimitOrderParams::Sell(mut params) => {
+                if params.durable_nonce.is_none() {
+                    params.recent_blockhash = Some(rpc.get_latest_blockhash().await?);
+                }
+                params.fixed_output_token_amount = limit_output;
+                params.slippage_basis_points = Some(0);
+                self.client.sell(params).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{GasFeeStrategy, InfrastructureConfig, SolanaRpcClient};
+    use crate::swqos::{health::SwqosHealthMonitor, scoreboard::SwqosScoreboard};
+    use crate::trading::core::params::BonkParams;
+    use crate::{TradeTokenType, TradingInfrastructure};
+    use arc_swap::ArcSwap;
+    use solana_commitment_config::CommitmentConfig;
+    use solana_sdk::signature::Keypair;
+    use std::sync::Arc;
+
+    /// Book over a client whose RPC node is not running: every fired order fails before sending.
+    fn book() -> LimitOrderBook {
+        let config = InfrastructureConfig::new(
+            "http://127.0.0.1:8899".to_string(),
+            vec![],
+            CommitmentConfig::processed(),
+        );
+        let infrastructure = TradingInfrastructure {
+            rpc: Arc::new(SolanaRpcClient::new(config.rpc_url.clone())),
+            swqos_clients: Arc::new(ArcSwap::from_pointee(Vec::new())),
+            swqos_scoreboard: Arc::new(SwqosScoreboard::default()),
+            swqos_health: Arc::new(SwqosHealthMonitor::default()),
+            config,
+        };
+        LimitOrderBook::new(TradingClient::from_infrastructure(
+            Arc::new(Keypair::new()),
+            Arc::new(infrastructure),
+            false,
+        ))
+    }
+
+    fn buy(mint: Pubkey) -> TradeBuyParams {
+        TradeBuyParams {
+            dex_type: DexType::Bonk,
+            input_token_type: TradeTokenType::SOL,
+            mint,
+            input_token_amount: 1_000_000,
+            slippage_basis_points: Some(100),
+            recent_blockhash: None,
+            extension_params: DexParamEnum::Bonk(BonkParams::default()),
+            address_lookup_table_account: None,
+            wait_transaction_confirmed: true,
+            create_input_token_ata: true,
+            close_input_token_ata: true,
+            create_mint_ata: true,
+            durable_nonce: None,
+            fixed_output_token_amount: None,
+            gas_fee_strategy: GasFeeStrategy::new(),
+            simulate: false,
+            use_exact_sol_amount: None,
+            grpc_recv_us: None,
+        }
+    }
+
+    /// Bonk curve params pricing the token at 0.05 quote.
+    fn curve_at_five_cents() -> DexParamEnum {
+        DexParamEnum::Bonk(BonkParams {
+            virtual_base: 1_000_000,
+            virtual_quote: 50_000,
+            ..Default::default()
+        })
+    }
+
+    fn set_state(book: &LimitOrderBook, id: u64, state: LimitOrderState) {
+        book.orders.lock().get_mut(&id).unwrap().state = state;
+    }
+
+    #[test]
+    fn triggers_and_limit_output() {
+        assert!(!is_triggered(TradeType::Buy, 0.5, 0.51));
+        assert!(is_triggered(TradeType::Buy, 0.5, 0.5));
+        assert!(!is_triggered(TradeType::Buy, 0.5, 0.0));
+        assert_eq!(limit_output(TradeType::Buy, 1_000_000, 0.5), 2_000_000);
+
+        assert!(!is_triggered(TradeType::Sell, 0.5, 0.49));
+        assert!(is_triggered(TradeType::Sell, 0.5, 0.6));
+        assert_eq!(limit_output(TradeType::Sell, 3, 0.5), 2);
+        assert_eq!(limit_output(TradeType::Buy, 1, 2.0), 0);
+    }
+
+    #[test]
+    fn placed_orders_rest_and_cannot_be_cancelled_while_firing() {
+        let book = book();
+        let mint = Pubkey::new_unique();
+        assert!(book.place_buy(buy(mint), 0.0, None).is_err());
+        assert!(book.place_buy(buy(mint), f64::NAN, None).is_err());
+        // 1_000_000 quote at 2e6 per token buys nothing.
+        assert!(book.place_buy(buy(mint), 2_000_000.0, None).is_err());
+
+        let id = book.place_buy(buy(mint), 0.01, None).unwrap();
+        let order = book.order(id).unwrap();
+        assert_eq!(order.state(), LimitOrderState::Resting);
+        assert_eq!(
+            (order.mint, order.dex_type, order.side()),
+            (mint, DexType::Bonk, TradeType::Buy)
+        );
+        assert_eq!(order.limit_output(), 100_000_000);
+
+        set_state(&book, id, LimitOrderState::Firing);
+        assert!(book.cancel(id).is_none());
+        assert!(book.cancel_mint(&mint).is_empty());
+        set_state(&book, id, LimitOrderState::Pending);
+        assert_eq!(book.cancel(id).map(|o| o.id), Some(id));
+        assert!(book.orders().is_empty());
+    }
+
+    #[test]
+    fn purge_expired_keeps_live_and_firing_orders() {
+        let book = book();
+        let mint = Pubkey::new_unique();
+        let expired = book.place_buy(buy(mint), 0.01, Some(1)).unwrap();
+        let firing = book.place_buy(buy(mint), 0.01, Some(1)).unwrap();
+        let live = book.place_buy(buy(mint), 0.01, Some(unix_now() + 60)).unwrap();
+        let open = book.place_buy(buy(mint), 0.01, None).unwrap();
+        set_state(&book, firing, LimitOrderState::Firing);
+
+        let purged: Vec<u64> = book.purge_expired().iter().map(|o| o.id).collect();
+        assert_eq!(purged, vec![expired]);
+        let mut left: Vec<u64> = book.orders().iter().map(|o| o.id).collect();
+        left.sort_unstable();
+        assert_eq!(left, vec![firing, live, open]);
+    }
+
+    #[tokio::test]
+    async fn update_params_replaces_the_extension_params_of_matching_orders() {
+        let book = book();
+        let (mint, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // Limits below the 0.05 the new params price the token at, so nothing fires.
+        let matching = book.place_buy(buy(mint), 0.01, None).unwrap();
+        let firing = book.place_buy(buy(mint), 0.01, None).unwrap();
+        let other_mint = book.place_buy(buy(other), 0.01, None).unwrap();
+        set_state(&book, firing, LimitOrderState::Firing);
+
+        let outcomes = book.update_params(&mint, curve_at_five_cents(), Some(42)).await.unwrap();
+        assert!(outcomes.is_empty());
+        let virtual_quote = |id| match book.order(id).unwrap().params {
+            LimitOrderParams::Buy(TradeBuyParams {
+                extension_params: DexParamEnum::Bonk(p),
+                grpc_recv_us,
+                ..
+            }) => (p.virtual_quote, grpc_recv_us),
+            _ => unreachable!(),
+        };
+        assert_eq!(virtual_quote(matching), (50_000, Some(42)));
+        assert_eq!(virtual_quote(firing), (0, None));
+        assert_eq!(virtual_quote(other_mint), (0, None));
+    }
+
+    #[tokio::test]
+    async fn fired_orders_rearm_only_when_the_trade_did_not_execute() {
+        let submitted = [Signature::new_unique()];
+        let on_chain =
+            TradeError { code: 6004, message: "slippage".to_string(), instruction: None };
+        let timeout = TradeError::from(anyhow!("Transaction confirmation timed out after 15s"));
+        assert_eq!(unfilled_state(&[], Some(&timeout)), LimitOrderState::Resting);
+        assert_eq!(unfilled_state(&[Signature::default()], None), LimitOrderState::Resting);
+        assert_eq!(unfilled_state(&submitted, Some(&on_chain)), LimitOrderState::Resting);
+        assert_eq!(unfilled_state(&submitted, Some(&timeout)), LimitOrderState::Pending);
+        assert_eq!(unfilled_state(&submitted, None), LimitOrderState::Pending);
+
+        // No blockhash can be fetched, so nothing is sent and the order rests again.
+        let book = book();
+        let mint = Pubkey::new_unique();
+        let id = book.place_buy(buy(mint), 0.1, None).unwrap();
+        let outcomes = book.update_price(&mint, DexType::Bonk, 0.05).await;
+        assert_eq!(outcomes.len(), 1);
+        assert!(!outcomes[0].success && !outcomes[0].pending);
+        assert_eq!(book.order(id).unwrap().state(), LimitOrderState::Resting);
+
+        // A pending order does not fire again until resolved.
+        set_state(&book, id, LimitOrderState::Pending);
+        assert!(book.update_price(&mint, DexType::Bonk, 0.05).await.is_empty());
+        assert_eq!(book.resolve(id, false).unwrap().state(), LimitOrderState::Resting);
+        assert!(book.resolve(id, true).is_none());
+        set_state(&book, id, LimitOrderState::Pending);
+        assert!(book.resolve(id, true).is_some());
+        assert!(book.order(id).is_none());
+    }
+}
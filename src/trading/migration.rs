@@ -0,0 +1,196 @@
+//! Migration-aware venue resolution.
+//!
+//! - **PumpFun**: while the bonding curve is not `complete` trades go to the curve; afterwards to
+//!   the canonical PumpSwap pool (`get_canonical_pool_pda`).
+//! - **Bonk**: while the LaunchLab pool is funding trades go to the curve; after migration to the
+//!   Raydium CPMM or AMM v4 pool selected by `PoolState::migrate_type`. Only the curve trades
+//!   USD1: a USD1 pool that migrated cannot be routed to (`ensure_usd1_venue`).
+
+use anyhow::anyhow;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::SolanaRpcClient;
+use crate::instruction::utils::{bonk, pumpfun, pumpswap, raydium_amm_v4, raydium_cpmm};
+use crate::trading::core::params::{
+    BonkParams, DexParamEnum, PumpFunParams, PumpSwapParams, RaydiumAmmV4Params, RaydiumCpmmParams,
+};
+use crate::trading::factory::DexType;
+
+/// Venue a mint currently trades on, with ready-to-use protocol params.
+#[derive(Clone)]
+pub struct ResolvedVenue {
+    pub dex_type: DexType,
+    pub params: DexParamEnum,
+    /// `true` when the launchpad curve has migrated and the trade was moved to the AMM.
+    pub migrated: bool,
+}
+
+/// Resolve a PumpFun mint to its bonding curve, or to the canonical PumpSwap pool once complete.
+pub async fn resolve_pumpfun(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+) -> Result<ResolvedVenue, anyhow::Error> {
+    let curve_error = match pumpfun::fetch_bonding_curve_account(rpc, mint).await {
+        Ok((curve, _)) if !curve.complete => {
+            let params = PumpFunParams::from_mint_by_rpc(rpc, mint).await?;
+            return Ok(ResolvedVenue {
+                dex_type: DexType::PumpFun,
+                params: DexParamEnum::PumpFun(params),
+                migrated: false,
+            });
+        }
+        Ok(_) => None,
+        Err(e) => Some(e),
+    };
+    let pool = pumpswap::get_canonical_pool_pda(mint);
+    match PumpSwapParams::from_pool_address_by_rpc(rpc, &pool).await {
+        Ok(params) => Ok(ResolvedVenue {
+            dex_type: DexType::PumpSwap,
+            params: DexParamEnum::PumpSwap(params),
+            migrated: true,
+        }),
+        Err(e) => match curve_error {
+            Some(curve_error) => Err(anyhow!(
+                "No PumpFun bonding curve ({}) or PumpSwap pool ({}) for mint {}",
+                curve_error,
+                e,
+                mint
+            )),
+            None => Err(anyhow!(
+                "Bonding curve of {} is complete but PumpSwap pool {} is not available yet: {}",
+                mint,
+                pool,
+                e
+            )),
+        },
+    }
+}
+
+/// Resolve a Bonk mint (quoted in WSOL, or USD1 when `usd1_pool`) to its LaunchLab curve, or to
+/// the Raydium CPMM / AMM v4 pool it migrated to.
+pub async fn resolve_bonk(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+    usd1_pool: bool,
+) -> Result<ResolvedVenue, anyhow::Error> {
+    let quote_mint = if usd1_pool {
+        crate::constants::USD1_TOKEN_ACCOUNT
+    } else {
+        crate::constants::WSOL_TOKEN_ACCOUNT
+    };
+    let pool_address = bonk::get_pool_pda(mint, &quote_mint)
+        .ok_or_else(|| anyhow!("Failed to derive Bonk pool for mint {}", mint))?;
+    let pool = bonk::fetch_pool_state(rpc, &pool_address).await?;
+    match bonk_venue(pool.status, pool.migrate_type, mint)? {
+        DexType::Bonk => {
+            let params = BonkParams::from_mint_by_rpc(rpc, mint, usd1_pool).await?;
+            Ok(ResolvedVenue {
+                dex_type: DexType::Bonk,
+                params: DexParamEnum::Bonk(params),
+                migrated: false,
+            })
+        }
+        DexType::RaydiumCpmm => {
+            let (pool_address, _) =
+                raydium_cpmm::find_pool_by_mints(rpc, mint, &quote_mint).await?;
+            let params = RaydiumCpmmParams::from_pool_address_by_rpc(rpc, &pool_address).await?;
+            Ok(ResolvedVenue {
+                dex_type: DexType::RaydiumCpmm,
+                params: DexParamEnum::RaydiumCpmm(params),
+                migrated: true,
+            })
+        }
+        _ => {
+            let (amm, _) = raydium_amm_v4::find_amm_by_mints(rpc, mint, &quote_mint).await?;
+            let params = RaydiumAmmV4Params::from_amm_address_by_rpc(rpc, amm).await?;
+            Ok(ResolvedVenue {
+                dex_type: DexType::RaydiumAmmV4,
+                params: DexParamEnum::RaydiumAmmV4(params),
+                migrated: true,
+            })
+        }
+    }
+}
+
+/// DEX a Bonk mint trades on for a LaunchLab pool in `status` with `migrate_type`.
+fn bonk_venue(status: u8, migrate_type: u8, mint: &Pubkey) -> Result<DexType, anyhow::Error> {
+    match status {
+        bonk::pool_status::FUND => Ok(DexType::Bonk),
+        bonk::pool_status::MIGRATE => {
+            Err(anyhow!("Bonk curve of {} is complete and waiting for migration", mint))
+        }
+        _ if migrate_type == bonk::migrate_type::CPMM => Ok(DexType::RaydiumCpmm),
+        _ => Ok(DexType::RaydiumAmmV4),
+    }
+}
+
+/// `Err` when a USD1 trade resolved to `dex_type`: the Raydium CPMM / AMM v4 builders do not
+/// trade USD1, so only a Bonk mint still on its curve can be bought or sold with it.
+pub(crate) fn ensure_usd1_venue(dex_type: DexType, mint: &Pubkey) -> Result<(), anyhow::Error> {
+    if dex_type == DexType::Bonk {
+        return Ok(());
+    }
+    Err(anyhow!(
+        "Bonk mint {} migrated from its USD1 pool to {:?}, which does not support USD1 trading",
+        mint,
+        dex_type
+    ))
+}
+
+/// Resolve `mint` for a trade requested on a launchpad `dex_type` (`PumpFun`, `Bonk`); other DEX
+/// types have no migration and return an error.
+pub async fn resolve_venue(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+    dex_type: DexType,
+    usd1_pool: bool,
+) -> Result<ResolvedVenue, anyhow::Error> {
+    match dex_type {
+        DexType::PumpFun => resolve_pumpfun(rpc, mint).await,
+        DexType::Bonk => resolve_bonk(rpc, mint, usd1_pool).await,
+        _ => Err(anyhow!("resolve_venue not implemented for {:?}", dex_type)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonk_routes_to_the_curve_until_migrated() {
+        let mint = Pubkey::new_unique();
+        let venue = |status, migrate_type| bonk_venue(status, migrate_type, &mint).ok();
+
+        assert_eq!(venue(bonk::pool_status::FUND, bonk::migrate_type::CPMM), Some(DexType::Bonk));
+        assert_eq!(venue(bonk::pool_status::MIGRATE, bonk::migrate_type::CPMM), None);
+        assert_eq!(
+            venue(bonk::pool_status::TRADE, bonk::migrate_type::CPMM),
+            Some(DexType::RaydiumCpmm)
+        );
+        assert_eq!(
+            venue(bonk::pool_status::TRADE, bonk::migrate_type::AMM_V4),
+            Some(DexType::RaydiumAmmV4)
+        );
+    }
+
+    #[test]
+    fn migrated_usd1_bonk_pools_are_not_routed() {
+        let mint = Pubkey::new_unique();
+        assert!(ensure_usd1_venue(DexType::Bonk, &mint).is_ok());
+        for migrate_type in [bonk::migrate_type::CPMM, bonk::migrate_type::AMM_V4] {
+            let dex_type = bonk_venue(bonk::pool_status::TRADE, migrate_type, &mint).unwrap();
+            let err = ensure_usd1_venue(dex_type, &mint).unwrap_err();
+            assert!(err.to_string().contains("does not support USD1"), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn venues_without_migration_are_rejected() {
+        let rpc = SolanaRpcClient::new("http://127.0.0.1:8899".to_string());
+        let err = resolve_venue(&rpc, &Pubkey::new_unique(), DexType::PumpSwap, false)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("PumpSwap"));
+    }
+}
@@ -0,0 +1,353 @@
+//! Pre-buy mint safety checks.
+//!
+//! `inspect_mint` reads a mint account (SPL Token or Token-2022) and lists what lets its
+//! authorities take tokens back or stop them from being sold: a freeze authority, an active mint
+//! authority and the Token-2022 transfer hook, permanent delegate, transfer fee, non-transferable
+//! and default-frozen extensions. A `MintSafetyPolicy` blocks, warns about or allows each of
+//! them; set on a `TradingClient` (`with_mint_safety`) it runs before every buy (`buy`,
+//! `buy_split`, `buy_two_hop` and the helpers built on them), at the cost of one `get_account`.
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::spl_token_2022::{self, extension, TransferFeeConfig, MINT_LEN};
+use crate::common::SolanaRpcClient;
+use crate::constants::{TOKEN_PROGRAM, TOKEN_PROGRAM_2022};
+
+/// A mint property that can hurt a holder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MintFlag {
+    /// Token accounts can be frozen.
+    FreezeAuthority(Pubkey),
+    /// Supply can still be minted.
+    MintAuthority(Pubkey),
+    /// Every transfer runs `program`, which may refuse sells.
+    TransferHook { program: Pubkey },
+    /// `delegate` can transfer or burn from any account.
+    PermanentDelegate(Pubkey),
+    /// Transfers pay a fee (the higher of the current and the scheduled one).
+    TransferFee { basis_points: u16, maximum_fee: u64 },
+    /// Tokens cannot be moved at all.
+    NonTransferable,
+    /// New token accounts start frozen.
+    DefaultFrozen,
+}
+
+impl MintFlag {
+    pub fn kind(&self) -> MintFlagKind {
+        match self {
+            Self::FreezeAuthority(_) => MintFlagKind::FreezeAuthority,
+            Self::MintAuthority(_) => MintFlagKind::MintAuthority,
+            Self::TransferHook { .. } => MintFlagKind::TransferHook,
+            Self::PermanentDelegate(_) => MintFlagKind::PermanentDelegate,
+            Self::TransferFee { .. } => MintFlagKind::TransferFee,
+            Self::NonTransferable => MintFlagKind::NonTransferable,
+            Self::DefaultFrozen => MintFlagKind::DefaultFrozen,
+        }
+    }
+}
+
+impl std::fmt::Display for MintFlag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FreezeAuthority(authority) => write!(f, "freeze authority {}", authority),
+            Self::MintAuthority(authority) => write!(f, "mint authority {}", authority),
+            Self::TransferHook { program } => write!(f, "transfer hook {}", program),
+            Self::PermanentDelegate(delegate) => write!(f, "permanent delegate {}", delegate),
+            Self::TransferFee { basis_points, maximum_fee } => {
+                write!(f, "transfer fee {} bps (max {})", basis_points, maximum_fee)
+            }
+            Self::NonTransferable => write!(f, "non-transferable"),
+            Self::DefaultFrozen => write!(f, "accounts frozen by default"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MintFlagKind {
+    FreezeAuthority,
+    MintAuthority,
+    TransferHook,
+    PermanentDelegate,
+    TransferFee,
+    NonTransferable,
+    DefaultFrozen,
+}
+
+/// What a policy does with a flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MintCheckAction {
+    Block,
+    Warn,
+    Allow,
+}
+
+/// Mint account facts relevant to a buy.
+#[derive(Clone, Debug)]
+pub struct MintReport {
+    pub mint: Pubkey,
+    /// SPL Token or Token-2022.
+    pub token_program: Pubkey,
+    pub decimals: u8,
+    pub flags: Vec<MintFlag>,
+}
+
+impl MintReport {
+    pub fn has(&self, kind: MintFlagKind) -> bool {
+        self.flags.iter().any(|flag| flag.kind() == kind)
+    }
+}
+
+/// Buy refused by a `MintSafetyPolicy`; `flags` are the blocking ones.
+#[derive(Clone, Debug)]
+pub struct MintSafetyRejection {
+    pub mint: Pubkey,
+    pub flags: Vec<MintFlag>,
+}
+
+impl std::fmt::Display for MintSafetyRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let flags: Vec<String> = self.flags.iter().map(ToString::to_string).collect();
+        write!(f, "Mint safety: {} blocked ({})", self.mint, flags.join(", "))
+    }
+}
+
+impl std::error::Error for MintSafetyRejection {}
+
+/// Action per flag. The default blocks everything that can freeze, take or lock tokens and warns
+/// about an active mint authority and transfer fees.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MintSafetyPolicy {
+    pub freeze_authority: MintCheckAction,
+    pub mint_authority: MintCheckAction,
+    pub transfer_hook: MintCheckAction,
+    pub permanent_delegate: MintCheckAction,
+    pub transfer_fee: MintCheckAction,
+    pub non_transferable: MintCheckAction,
+    pub default_frozen: MintCheckAction,
+}
+
+impl Default for MintSafetyPolicy {
+    fn default() -> Self {
+        Self {
+            freeze_authority: MintCheckAction::Block,
+            mint_authority: MintCheckAction::Warn,
+            transfer_hook: MintCheckAction::Block,
+            permanent_delegate: MintCheckAction::Block,
+            transfer_fee: MintCheckAction::Warn,
+            non_transferable: MintCheckAction::Block,
+            default_frozen: MintCheckAction::Block,
+        }
+    }
+}
+
+impl MintSafetyPolicy {
+    pub fn action(&self, kind: MintFlagKind) -> MintCheckAction {
+        match kind {
+            MintFlagKind::FreezeAuthority => self.freeze_authority,
+            MintFlagKind::MintAuthority => self.mint_authority,
+            MintFlagKind::TransferHook => self.transfer_hook,
+            MintFlagKind::PermanentDelegate => self.permanent_delegate,
+            MintFlagKind::TransferFee => self.transfer_fee,
+            MintFlagKind::NonTransferable => self.non_transferable,
+            MintFlagKind::DefaultFrozen => self.default_frozen,
+        }
+    }
+
+    /// Builder-style override of one flag's action.
+    pub fn with(mut self, kind: MintFlagKind, action: MintCheckAction) -> Self {
+        let slot = match kind {
+            MintFlagKind::FreezeAuthority => &mut self.freeze_authority,
+            MintFlagKind::MintAuthority => &mut self.mint_authority,
+            MintFlagKind::TransferHook => &mut self.transfer_hook,
+            MintFlagKind::PermanentDelegate => &mut self.permanent_delegate,
+            MintFlagKind::TransferFee => &mut self.transfer_fee,
+            MintFlagKind::NonTransferable => &mut self.non_transferable,
+            MintFlagKind::DefaultFrozen => &mut self.default_frozen,
+        };
+        *slot = action;
+        self
+    }
+
+    /// Flags of `report` that warrant a warning.
+    pub fn warnings<'a>(&self, report: &'a MintReport) -> Vec<&'a MintFlag> {
+        self.flags_with(report, MintCheckAction::Warn)
+    }
+
+    /// `Err` listing the blocking flags of `report`; warning flags are logged.
+    pub fn enforce(&self, report: &MintReport) -> Result<(), MintSafetyRejection> {
+        for flag in self.warnings(report) {
+            tracing::warn!(target: "sol_trade_sdk", "Mint safety: {} has {}", report.mint, flag);
+        }
+        let blocking: Vec<MintFlag> =
+            self.flags_with(report, MintCheckAction::Block).into_iter().copied().collect();
+        if blocking.is_empty() {
+            return Ok(());
+        }
+        Err(MintSafetyRejection { mint: report.mint, flags: blocking })
+    }
+
+    fn flags_with<'a>(&self, report: &'a MintReport, action: MintCheckAction) -> Vec<&'a MintFlag> {
+        report.flags.iter().filter(|flag| self.action(flag.kind()) == action).collect()
+    }
+}
+
+/// `COption<Pubkey>`: u32 tag + pubkey.
+#[inline]
+fn coption_pubkey(data: &[u8]) -> Option<Pubkey> {
+    let tag = u32::from_le_bytes(data[..4].try_into().unwrap());
+    (tag == 1).then(|| Pubkey::new_from_array(data[4..36].try_into().unwrap()))
+}
+
+/// `OptionalNonZeroPubkey`: all zero means none.
+#[inline]
+fn optional_pubkey(data: &[u8]) -> Option<Pubkey> {
+    let key = Pubkey::new_from_array(data.get(..32)?.try_into().unwrap());
+    (key != Pubkey::default()).then_some(key)
+}
+
+/// Flags of mint account `data` owned by `token_program`.
+pub fn inspect_mint(
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    data: &[u8],
+) -> Result<MintReport, anyhow::Error> {
+    if *token_program != TOKEN_PROGRAM && *token_program != TOKEN_PROGRAM_2022 {
+        return Err(anyhow!("{} is not a token mint (owner {})", mint, token_program));
+    }
+    if data.len() < MINT_LEN {
+        return Err(anyhow!("{} is not a token mint ({} bytes)", mint, data.len()));
+    }
+    let mut flags = Vec::new();
+    if let Some(authority) = coption_pubkey(&data[46..82]) {
+        flags.push(MintFlag::FreezeAuthority(authority));
+    }
+    if let Some(authority) = coption_pubkey(&data[0..36]) {
+        flags.push(MintFlag::MintAuthority(authority));
+    }
+    for (ext_type, value) in spl_token_2022::mint_extensions(data) {
+        let flag = match ext_type {
+            extension::TRANSFER_HOOK => value
+                .get(32..)
+                .and_then(optional_pubkey)
+                .map(|program| MintFlag::TransferHook { program }),
+            extension::PERMANENT_DELEGATE => {
+                optional_pubkey(value).map(MintFlag::PermanentDelegate)
+            }
+            extension::TRANSFER_FEE_CONFIG => TransferFeeConfig::parse(value)
+                .map(|config| {
+                    let older = config.older_transfer_fee;
+                    let newer = config.newer_transfer_fee;
+                    (
+                        older.transfer_fee_basis_points.max(newer.transfer_fee_basis_points),
+                        older.maximum_fee.max(newer.maximum_fee),
+                    )
+                })
+                .filter(|(basis_points, _)| *basis_points > 0)
+                .map(|(basis_points, maximum_fee)| MintFlag::TransferFee {
+                    basis_points,
+                    maximum_fee,
+                }),
+            extension::NON_TRANSFERABLE => Some(MintFlag::NonTransferable),
+            // AccountState::Frozen
+            extension::DEFAULT_ACCOUNT_STATE => {
+                (value.first() == Some(&2)).then_some(MintFlag::DefaultFrozen)
+            }
+            _ => None,
+        };
+        flags.extend(flag);
+    }
+    Ok(MintReport { mint: *mint, token_program: *token_program, decimals: data[44], flags })
+}
+
+/// Fetch `mint` and list its flags.
+pub async fn check_mint(rpc: &SolanaRpcClient, mint: &Pubkey) -> Result<MintReport, anyhow::Error> {
+    let account = rpc.get_account(mint).await?;
+    inspect_mint(mint, &account.owner, &account.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mint_data(mint_authority: Option<Pubkey>, freeze_authority: Option<Pubkey>) -> Vec<u8> {
+        let mut data = vec![0u8; MINT_LEN];
+        if let Some(authority) = mint_authority {
+            data[0] = 1;
+            data[4..36].copy_from_slice(authority.as_ref());
+        }
+        data[44] = 6;
+        data[45] = 1;
+        if let Some(authority) = freeze_authority {
+            data[46] = 1;
+            data[50..82].copy_from_slice(authority.as_ref());
+        }
+        data
+    }
+
+    fn push_extension(data: &mut Vec<u8>, ext_type: u16, value: &[u8]) {
+        if data.len() == MINT_LEN {
+            data.resize(165, 0);
+            data.push(1);
+        }
+        data.extend_from_slice(&ext_type.to_le_bytes());
+        data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        data.extend_from_slice(value);
+    }
+
+    #[test]
+    fn spl_mint_authorities() {
+        let mint = Pubkey::new_unique();
+        let report = inspect_mint(&mint, &TOKEN_PROGRAM, &mint_data(None, None)).unwrap();
+        assert!(report.flags.is_empty());
+        assert_eq!(report.decimals, 6);
+
+        let freeze = Pubkey::new_unique();
+        let report = inspect_mint(&mint, &TOKEN_PROGRAM, &mint_data(None, Some(freeze))).unwrap();
+        assert_eq!(report.flags, vec![MintFlag::FreezeAuthority(freeze)]);
+        let rejection = MintSafetyPolicy::default().enforce(&report).unwrap_err();
+        assert_eq!(rejection.flags, report.flags);
+        assert!(MintSafetyPolicy::default()
+            .with(MintFlagKind::FreezeAuthority, MintCheckAction::Allow)
+            .enforce(&report)
+            .is_ok());
+    }
+
+    #[test]
+    fn token_2022_extensions() {
+        let mint = Pubkey::new_unique();
+        let (hook, delegate) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = mint_data(Some(Pubkey::new_unique()), None);
+        let mut hook_value = vec![0u8; 32];
+        hook_value.extend_from_slice(hook.as_ref());
+        push_extension(&mut data, extension::TRANSFER_HOOK, &hook_value);
+        push_extension(&mut data, extension::PERMANENT_DELEGATE, delegate.as_ref());
+        let mut fee_value = vec![0u8; 108];
+        fee_value[98..106].copy_from_slice(&5_000u64.to_le_bytes());
+        fee_value[106..108].copy_from_slice(&250u16.to_le_bytes());
+        push_extension(&mut data, extension::TRANSFER_FEE_CONFIG, &fee_value);
+        push_extension(&mut data, extension::DEFAULT_ACCOUNT_STATE, &[1]);
+
+        let report = inspect_mint(&mint, &TOKEN_PROGRAM_2022, &data).unwrap();
+        let kinds: Vec<MintFlagKind> = report.flags.iter().map(MintFlag::kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MintFlagKind::MintAuthority,
+                MintFlagKind::TransferHook,
+                MintFlagKind::PermanentDelegate,
+                MintFlagKind::TransferFee
+            ]
+        );
+        assert_eq!(report.flags[1], MintFlag::TransferHook { program: hook });
+        assert_eq!(
+            report.flags[3],
+            MintFlag::TransferFee { basis_points: 250, maximum_fee: 5_000 }
+        );
+
+        let policy = MintSafetyPolicy::default();
+        assert_eq!(policy.warnings(&report).len(), 2);
+        assert_eq!(policy.enforce(&report).unwrap_err().flags.len(), 2);
+    }
+}
@@ -1,7 +1,18 @@
 pub mod common;
 pub mod core;
 pub mod factory;
+pub mod ledger;
+pub mod limit_order;
 pub mod middleware;
+pub mod migration;
+pub mod mint_safety;
+pub mod multi_hop;
+pub mod pool_finder;
+pub mod position;
+pub mod price_guard;
+pub mod risk;
+pub mod router;
+pub mod split;
 
 pub use core::params::SwapParams;
 pub use core::traits::InstructionBuilder;
@@ -0,0 +1,558 @@
+//! Two-hop routing through an intermediate quote token (WSOL / USDC / USD1).
+//!
+//! A trade with no direct pool between its input and output is chained through an intermediate,
+//! e.g. SOL -> USD1 on Raydium / Meteora, then USD1 -> token on Bonk. Both swaps are built into
+//! one transaction, so they land or fail together.
+//!
+//! Slippage is checked once, end to end:
+//! - the first hop is quoted with half the tolerance and forwards its minimum output as the exact
+//!   input of the second hop (any surplus stays in the intermediate token account);
+//! - the second hop must return at least `amount_out * (1 - slippage)`, where `amount_out` is the
+//!   output for the first hop's full expected amount.
+//!
+//! Legs that trade an exact output (PumpFun / PumpSwap buys) keep the output quoted for the
+//! forwarded amount, which is never below that minimum.
+
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use futures::future::join_all;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+
+use crate::constants::trade::trade::DEFAULT_SLIPPAGE;
+use crate::constants::{TOKEN_PROGRAM, USD1_TOKEN_ACCOUNT, USDC_TOKEN_ACCOUNT, WSOL_TOKEN_ACCOUNT};
+use crate::swqos::common::TradeError;
+use crate::swqos::TradeType;
+use crate::trading::common::transaction_builder::{
+    estimate_transaction_size, MAX_TRANSACTION_SIZE,
+};
+use crate::trading::core::executor::GenericTradeExecutor;
+use crate::trading::core::traits::{InstructionBuilder, TradeExecutor};
+use crate::trading::factory::DexType;
+use crate::trading::router::{RouteQuote, Router};
+use crate::trading::split::{create_token_account, is_sol, leg_compute_units, BASE_COMPUTE_UNITS};
+use crate::trading::{SwapParams, TradeFactory};
+
+/// Tokens a trade may be routed through, in quote priority: a hop between two of them treats
+/// the earlier one as the pool's quote token.
+pub const INTERMEDIATE_MINTS: [Pubkey; 3] =
+    [WSOL_TOKEN_ACCOUNT, USDC_TOKEN_ACCOUNT, USD1_TOKEN_ACCOUNT];
+
+/// One swap of a multi-hop route.
+#[derive(Clone)]
+pub struct Hop {
+    /// `Buy` when the hop spends the pool's quote token, `Sell` otherwise.
+    pub trade_type: TradeType,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    /// Best pool for this hop; `min_amount_out` is what the transaction enforces.
+    pub route: RouteQuote,
+}
+
+/// `input_mint` -> `intermediate_mint` -> `output_mint` in one transaction.
+#[derive(Clone)]
+pub struct TwoHopRoute {
+    pub input_mint: Pubkey,
+    pub intermediate_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub first: Hop,
+    /// Quoted (and executed) with `first.route.min_amount_out` as its input.
+    pub second: Hop,
+    /// Expected output when the first hop fills at its quote.
+    pub amount_out: u64,
+    /// End-to-end minimum output.
+    pub min_amount_out: u64,
+}
+
+impl fmt::Display for TwoHopRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {} -> {} -> {}: out {} (min {})",
+            self.amount_in,
+            self.input_mint,
+            self.intermediate_mint,
+            self.output_mint,
+            self.amount_out,
+            self.min_amount_out
+        )?;
+        for hop in [&self.first, &self.second] {
+            writeln!(
+                f,
+                "  {:<4} {:<14} {:<44} in {:>20} out {:>20} min {:>20} impact {:>6.2}%",
+                hop.trade_type,
+                format!("{:?}", hop.route.dex_type),
+                hop.route.pool.to_string(),
+                hop.route.amount_in,
+                hop.route.amount_out,
+                hop.route.min_amount_out,
+                hop.route.price_impact_bps as f64 / 100.0,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of `TradingClient::buy_two_hop` / `sell_two_hop`.
+pub struct TwoHopTrade {
+    pub route: TwoHopRoute,
+    pub success: bool,
+    pub signatures: Vec<Signature>,
+    pub error: Option<TradeError>,
+}
+
+/// Quote token of a pool pairing `a` with `b`: the one listed first in `INTERMEDIATE_MINTS`.
+fn hop_quote_mint(a: &Pubkey, b: &Pubkey) -> Option<Pubkey> {
+    INTERMEDIATE_MINTS.iter().find(|mint| *mint == a || *mint == b).copied()
+}
+
+/// Best single-pool route swapping `amount_in` of `input_mint` for `output_mint`.
+async fn quote_hop(
+    router: &Router,
+    input_mint: &Pubkey,
+    output_mint: &Pubkey,
+    amount_in: u64,
+    slippage_basis_points: u64,
+) -> Result<Hop, anyhow::Error> {
+    let quote_mint = hop_quote_mint(input_mint, output_mint).ok_or_else(|| {
+        anyhow!("{} -> {} has no WSOL / USDC / USD1 side", input_mint, output_mint)
+    })?;
+    let slippage = Some(slippage_basis_points);
+    let table = if quote_mint == *input_mint {
+        router.quote_buy(output_mint, input_mint, amount_in, slippage).await?
+    } else {
+        router.quote_sell(input_mint, output_mint, amount_in, slippage).await?
+    };
+    let trade_type = table.trade_type;
+    let route = table.routes.into_iter().next().ok_or_else(|| anyhow!("No route"))?;
+    Ok(Hop { trade_type, input_mint: *input_mint, output_mint: *output_mint, route })
+}
+
+/// End-to-end `(amount_out, min_amount_out)` of a second hop fed by a first hop that returns
+/// `first_amount_out` when filled at its quote. The minimum never exceeds the second hop's own
+/// quote for its (forwarded) input.
+fn end_to_end(
+    second: &RouteQuote,
+    first_amount_out: u64,
+    slippage_basis_points: u64,
+) -> Result<(u64, u64), anyhow::Error> {
+    let (amount_out, min_amount_out) =
+        second.curve.quote(first_amount_out, slippage_basis_points).map_err(|e| anyhow!(e))?;
+    Ok((amount_out, min_amount_out.min(second.amount_out)))
+}
+
+async fn quote_via(
+    router: &Router,
+    input_mint: &Pubkey,
+    intermediate_mint: &Pubkey,
+    output_mint: &Pubkey,
+    amount_in: u64,
+    slippage_basis_points: u64,
+) -> Result<TwoHopRoute, anyhow::Error> {
+    let first =
+        quote_hop(router, input_mint, intermediate_mint, amount_in, slippage_basis_points / 2)
+            .await?;
+    let forwarded = first.route.min_amount_out;
+    if forwarded == 0 {
+        return Err(anyhow!("First hop returns no {}", intermediate_mint));
+    }
+    let mut second =
+        quote_hop(router, intermediate_mint, output_mint, forwarded, slippage_basis_points).await?;
+    let (amount_out, min_amount_out) =
+        end_to_end(&second.route, first.route.amount_out, slippage_basis_points)?;
+    second.route.min_amount_out = min_amount_out;
+    Ok(TwoHopRoute {
+        input_mint: *input_mint,
+        intermediate_mint: *intermediate_mint,
+        output_mint: *output_mint,
+        amount_in,
+        first,
+        second,
+        amount_out,
+        min_amount_out,
+    })
+}
+
+/// Best two-hop route from `input_mint` to `output_mint` over every intermediate in
+/// `INTERMEDIATE_MINTS` other than the two ends (WSOL for SOL).
+pub async fn quote_two_hop(
+    router: &Router,
+    input_mint: &Pubkey,
+    output_mint: &Pubkey,
+    amount_in: u64,
+    slippage_basis_points: Option<u64>,
+) -> Result<TwoHopRoute, anyhow::Error> {
+    let slippage = slippage_basis_points.unwrap_or(DEFAULT_SLIPPAGE);
+    let intermediates: Vec<Pubkey> = INTERMEDIATE_MINTS
+        .iter()
+        .filter(|mint| *mint != input_mint && *mint != output_mint)
+        .copied()
+        .collect();
+    let results = join_all(intermediates.iter().map(|intermediate| {
+        quote_via(router, input_mint, intermediate, output_mint, amount_in, slippage)
+    }))
+    .await;
+
+    let mut best: Option<TwoHopRoute> = None;
+    let mut failures = Vec::new();
+    for (intermediate, result) in intermediates.iter().zip(results) {
+        match result {
+            Ok(route) => {
+                if best.as_ref().is_none_or(|b| route.amount_out > b.amount_out) {
+                    best = Some(route);
+                }
+            }
+            Err(e) => failures.push(format!("via {}: {}", intermediate, e)),
+        }
+    }
+    best.ok_or_else(|| {
+        anyhow!("No two-hop route from {} to {} ({})", input_mint, output_mint, failures.join("; "))
+    })
+}
+
+/// Estimated compute units of a two-hop transaction.
+fn compute_units(route: &TwoHopRoute) -> u32 {
+    BASE_COMPUTE_UNITS
+        + leg_compute_units(route.first.route.dex_type)
+        + leg_compute_units(route.second.route.dex_type)
+}
+
+/// Builds both hops of a `TwoHopRoute` into one instruction list.
+///
+/// `SwapParams` describe the whole trade: the first hop takes its input side (amount, input ATA
+/// flags), the second its output side. Token accounts (wrapped SOL input, intermediate, output)
+/// are created once before the hops, which are built with account creation off. The intermediate
+/// account is left open, except WSOL, which is unwrapped as soon as it is no longer needed.
+/// PumpFun trades native SOL, so WSOL next to a PumpFun hop is unwrapped before it / wrapped
+/// after it.
+pub struct TwoHopInstructionBuilder {
+    route: TwoHopRoute,
+}
+
+impl TwoHopInstructionBuilder {
+    pub fn new(route: TwoHopRoute) -> Self {
+        Self { route }
+    }
+
+    fn hop_params(&self, params: &SwapParams, first: bool) -> SwapParams {
+        let route = &self.route;
+        let via_wsol = route.intermediate_mint == WSOL_TOKEN_ACCOUNT;
+        let first_native = route.first.route.dex_type == DexType::PumpFun;
+        let second_native = route.second.route.dex_type == DexType::PumpFun;
+        let hop = if first { &route.first } else { &route.second };
+
+        let mut hop_params = params.clone();
+        hop_params.trade_type = hop.trade_type;
+        hop_params.protocol_params = hop.route.params.clone();
+        hop_params.input_amount = Some(hop.route.amount_in);
+        hop_params.fixed_output_amount =
+            (!hop.route.curve.exact_output()).then_some(hop.route.min_amount_out);
+        // Hops spend exactly their quoted input, which is what `setup` wraps.
+        hop_params.use_exact_sol_amount = Some(true);
+        hop_params.create_input_mint_ata = false;
+        hop_params.create_output_mint_ata = false;
+        if first {
+            hop_params.output_mint = route.intermediate_mint;
+            hop_params.output_token_program = None;
+            hop_params.slippage_basis_points =
+                Some(params.slippage_basis_points.unwrap_or(DEFAULT_SLIPPAGE) / 2);
+            hop_params.close_output_mint_ata = via_wsol && second_native && !first_native;
+        } else {
+            hop_params.input_mint = route.intermediate_mint;
+            hop_params.input_token_program = None;
+            hop_params.close_input_mint_ata = via_wsol && !second_native;
+        }
+        hop_params
+    }
+
+    /// Token accounts of the route, each created once: the wrapped SOL input, the intermediate
+    /// (WSOL only when a non-PumpFun hop uses it) and the output.
+    fn setup(&self, params: &SwapParams) -> anyhow::Result<Vec<Instruction>> {
+        let route = &self.route;
+        let first_native = route.first.route.dex_type == DexType::PumpFun;
+        let second_native = route.second.route.dex_type == DexType::PumpFun;
+        let mut instructions = Vec::new();
+        if params.create_input_mint_ata && is_sol(&route.input_mint) && !first_native {
+            instructions.extend(crate::trading::common::handle_wsol(
+                &params.payer.pubkey(),
+                route.first.route.amount_in,
+            ));
+        }
+        if route.intermediate_mint != WSOL_TOKEN_ACCOUNT || !(first_native && second_native) {
+            instructions.extend(create_token_account(
+                params,
+                &route.intermediate_mint,
+                &TOKEN_PROGRAM,
+            ));
+        }
+        if params.create_output_mint_ata {
+            if !is_sol(&route.output_mint) {
+                let token_program = route
+                    .second
+                    .route
+                    .params
+                    .token_program_of(&route.output_mint)
+                    .ok_or_else(|| anyhow!("No token program for {}", route.output_mint))?;
+                instructions.extend(create_token_account(
+                    params,
+                    &route.output_mint,
+                    &token_program,
+                ));
+            } else if !second_native {
+                instructions.extend(create_token_account(
+                    params,
+                    &route.output_mint,
+                    &TOKEN_PROGRAM,
+                ));
+            }
+        }
+        Ok(instructions)
+    }
+
+    async fn build(&self, params: &SwapParams) -> anyhow::Result<Vec<Instruction>> {
+        let route = &self.route;
+        let mut instructions = self.setup(params)?;
+        for (first, hop) in [(true, &route.first), (false, &route.second)] {
+            if !first
+                && route.intermediate_mint == WSOL_TOKEN_ACCOUNT
+                && route.first.route.dex_type == DexType::PumpFun
+                && route.second.route.dex_type != DexType::PumpFun
+            {
+                // The PumpFun hop paid out native SOL; wrap what the second hop spends.
+                instructions.extend(crate::trading::common::wrap_sol_only(
+                    &params.payer.pubkey(),
+                    route.second.route.amount_in,
+                ));
+            }
+            let hop_params = self.hop_params(params, first);
+            let builder = TradeFactory::create_instruction_builder(hop.route.dex_type);
+            let hop_instructions = if hop.trade_type == TradeType::Buy {
+                builder.build_buy_instructions(&hop_params).await?
+            } else {
+                builder.build_sell_instructions(&hop_params).await?
+            };
+            instructions.extend(hop_instructions);
+        }
+        Ok(instructions)
+    }
+}
+
+#[async_trait::async_trait]
+impl InstructionBuilder for TwoHopInstructionBuilder {
+    async fn build_buy_instructions(
+        &self,
+        params: &SwapParams,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        self.build(params).await
+    }
+
+    async fn build_sell_instructions(
+        &self,
+        params: &SwapParams,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        self.build(params).await
+    }
+}
+
+/// Send `route` as one transaction through the regular executor (SWQOS fan-out, confirmation).
+///
+/// Fails before sending when the two hops do not fit one transaction (pass an address lookup
+/// table in `params` to shrink it). The gas fee strategy's compute unit limit is raised to the
+/// route's estimate when lower.
+pub async fn execute_two_hop(
+    route: &TwoHopRoute,
+    mut params: SwapParams,
+) -> Result<(bool, Vec<Signature>, Option<anyhow::Error>), anyhow::Error> {
+    let builder = Arc::new(TwoHopInstructionBuilder::new(route.clone()));
+    let instructions = builder.build(&params).await?;
+    let size = estimate_transaction_size(
+        &params.payer.pubkey(),
+        &instructions,
+        params.address_lookup_table_account.as_ref(),
+        params.trade_type == TradeType::Buy || params.with_tip,
+        params.durable_nonce.as_ref(),
+    );
+    if size > MAX_TRANSACTION_SIZE {
+        return Err(anyhow!(
+            "Two-hop transaction is {} bytes (max {}), use an address lookup table",
+            size,
+            MAX_TRANSACTION_SIZE
+        ));
+    }
+    params.protocol_params = route.first.route.params.clone();
+    params.gas_fee_strategy =
+        params.gas_fee_strategy.with_min_cu_limit(params.trade_type, compute_units(route));
+    let executor = GenericTradeExecutor::new(builder, "TwoHop");
+    executor.swap(params).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{ASSOCIATED_TOKEN_PROGRAM_ID, SOL_TOKEN_ACCOUNT, SYSTEM_PROGRAM};
+    use crate::trading::core::params::{BonkParams, DexParamEnum, RaydiumAmmV4Params};
+    use crate::trading::router::Curve;
+
+    /// AMM v4 hop over a `coin` / `pc` pool with equal reserves.
+    fn amm_v4_hop(trade_type: TradeType, input_mint: Pubkey, output_mint: Pubkey) -> Hop {
+        let (coin, pc) = match trade_type {
+            TradeType::Buy => (output_mint, input_mint),
+            _ => (input_mint, output_mint),
+        };
+        let reserve = 1_000_000_000_000;
+        let params = RaydiumAmmV4Params::new(
+            Pubkey::new_unique(),
+            coin,
+            pc,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            reserve,
+            reserve,
+        );
+        let mut route = RouteQuote {
+            dex_type: DexType::RaydiumAmmV4,
+            pool: params.amm,
+            quote_mint: pc,
+            amount_in: 0,
+            amount_out: 0,
+            min_amount_out: 0,
+            price_impact_bps: 0,
+            params: DexParamEnum::RaydiumAmmV4(params),
+            curve: Curve::RaydiumAmmV4 {
+                coin_reserve: reserve,
+                pc_reserve: reserve,
+                is_base_in: trade_type != TradeType::Buy,
+            },
+        };
+        route.requote(1_000_000, 100).unwrap();
+        Hop { trade_type, input_mint, output_mint, route }
+    }
+
+    fn two_hop(first: Hop, second: Hop) -> TwoHopRoute {
+        TwoHopRoute {
+            input_mint: first.input_mint,
+            intermediate_mint: first.output_mint,
+            output_mint: second.output_mint,
+            amount_in: first.route.amount_in,
+            amount_out: second.route.amount_out,
+            min_amount_out: second.route.min_amount_out,
+            first,
+            second,
+        }
+    }
+
+    /// System program instructions (`createAccountWithSeed`) that create `mint`'s seed account.
+    fn seed_creates(instructions: &[Instruction], params: &SwapParams, mint: &Pubkey) -> usize {
+        let account =
+            crate::common::fast_fn::get_associated_token_address_with_program_id_fast_use_seed(
+                &params.payer.pubkey(),
+                mint,
+                &TOKEN_PROGRAM,
+                true,
+            );
+        instructions
+            .iter()
+            .filter(|ix| {
+                ix.program_id == SYSTEM_PROGRAM && ix.accounts.iter().any(|m| m.pubkey == account)
+            })
+            .count()
+    }
+
+    #[test]
+    fn hop_quote_mint_follows_priority() {
+        let token = Pubkey::new_unique();
+        assert_eq!(
+            hop_quote_mint(&USD1_TOKEN_ACCOUNT, &WSOL_TOKEN_ACCOUNT),
+            Some(WSOL_TOKEN_ACCOUNT)
+        );
+        assert_eq!(
+            hop_quote_mint(&USD1_TOKEN_ACCOUNT, &USDC_TOKEN_ACCOUNT),
+            Some(USDC_TOKEN_ACCOUNT)
+        );
+        assert_eq!(hop_quote_mint(&token, &USD1_TOKEN_ACCOUNT), Some(USD1_TOKEN_ACCOUNT));
+        assert_eq!(hop_quote_mint(&token, &Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn end_to_end_min_applies_full_slippage_once() {
+        let mut second = RouteQuote {
+            dex_type: DexType::RaydiumCpmm,
+            pool: Pubkey::new_unique(),
+            quote_mint: USD1_TOKEN_ACCOUNT,
+            amount_in: 0,
+            amount_out: 0,
+            min_amount_out: 0,
+            price_impact_bps: 0,
+            params: DexParamEnum::Bonk(BonkParams::default()),
+            curve: Curve::RaydiumCpmm {
+                base_reserve: 1_000_000_000_000,
+                quote_reserve: 1_000_000_000_000,
+                is_base_in: true,
+                transfer_fees: Default::default(),
+            },
+        };
+        let slippage = 200;
+        // First hop expects 1_000_000 and forwards its 1% minimum.
+        let (first_out, forwarded) = (1_000_000, 990_000);
+        second.requote(forwarded, slippage).unwrap();
+        let (amount_out, min_amount_out) = end_to_end(&second, first_out, slippage).unwrap();
+        assert!(amount_out > second.amount_out);
+        assert!(min_amount_out <= second.amount_out);
+        let expected_min = amount_out as f64 * 0.98;
+        assert!((min_amount_out as f64 - expected_min).abs() <= 1.0);
+    }
+
+    #[tokio::test]
+    async fn seed_optimized_buy_creates_intermediate_and_output_once() {
+        crate::common::seed::set_default_rents();
+        let token = Pubkey::new_unique();
+        let route = two_hop(
+            amm_v4_hop(TradeType::Buy, WSOL_TOKEN_ACCOUNT, USDC_TOKEN_ACCOUNT),
+            amm_v4_hop(TradeType::Buy, USDC_TOKEN_ACCOUNT, token),
+        );
+        let params = SwapParams::for_test(
+            TradeType::Buy,
+            SOL_TOKEN_ACCOUNT,
+            token,
+            route.first.route.params.clone(),
+        );
+        assert!(params.open_seed_optimize);
+
+        let instructions = TwoHopInstructionBuilder::new(route).build(&params).await.unwrap();
+        assert_eq!(seed_creates(&instructions, &params, &USDC_TOKEN_ACCOUNT), 1);
+        assert_eq!(seed_creates(&instructions, &params, &token), 1);
+        // Only the WSOL input goes through the associated token program.
+        let ata_creates =
+            instructions.iter().filter(|ix| ix.program_id == ASSOCIATED_TOKEN_PROGRAM_ID).count();
+        assert_eq!(ata_creates, 1);
+    }
+
+    #[tokio::test]
+    async fn seed_optimized_sell_creates_intermediate_and_output_once() {
+        crate::common::seed::set_default_rents();
+        let token = Pubkey::new_unique();
+        let route = two_hop(
+            amm_v4_hop(TradeType::Sell, token, USDC_TOKEN_ACCOUNT),
+            amm_v4_hop(TradeType::Sell, USDC_TOKEN_ACCOUNT, WSOL_TOKEN_ACCOUNT),
+        );
+        let mut params = SwapParams::for_test(
+            TradeType::Sell,
+            token,
+            SOL_TOKEN_ACCOUNT,
+            route.first.route.params.clone(),
+        );
+        params.create_input_mint_ata = false;
+
+        let instructions = TwoHopInstructionBuilder::new(route).build(&params).await.unwrap();
+        assert_eq!(seed_creates(&instructions, &params, &USDC_TOKEN_ACCOUNT), 1);
+        assert_eq!(seed_creates(&instructions, &params, &token), 0);
+        let ata_creates =
+            instructions.iter().filter(|ix| ix.program_id == ASSOCIATED_TOKEN_PROGRAM_ID).count();
+        assert_eq!(ata_creates, 1);
+    }
+}
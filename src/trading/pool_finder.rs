@@ -0,0 +1,482 @@
+//! Pool discovery by mint for every supported DEX.
+//!
+//! - **PDA**: pools the program derives deterministically are fetched directly (PumpFun curve,
+//!   PumpSwap v2 / canonical pools, Bonk WSOL / USD1 pools, Raydium CPMM fee tiers).
+//! - **Scan**: getProgramAccounts with memcmp filters on the mint offsets finds the rest; when the
+//!   RPC rejects the scan, PDA hits are still returned.
+//! - **Ranking**: vault balances are read in one batched call. Raw reserves of different quote
+//!   mints are not comparable, so candidates are grouped by quote mint (WSOL, USDC, USD1, then
+//!   any other) and sorted by quote reserve within each group.
+
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use solana_account_decoder::UiAccountEncoding;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::SolanaRpcClient;
+use crate::constants::{USD1_TOKEN_ACCOUNT, USDC_TOKEN_ACCOUNT, WSOL_TOKEN_ACCOUNT};
+use crate::instruction::utils::{
+    bonk, bonk_types, meteora_damm_v2, meteora_damm_v2_types, pumpfun, pumpswap, pumpswap_types,
+    raydium_amm_v4, raydium_amm_v4_types, raydium_cpmm, raydium_cpmm_types,
+};
+use crate::trading::factory::DexType;
+
+/// Quote mints probed when pools are located by PDA.
+const PDA_QUOTE_MINTS: [Pubkey; 3] = [WSOL_TOKEN_ACCOUNT, USDC_TOKEN_ACCOUNT, USD1_TOKEN_ACCOUNT];
+/// Raydium CPMM amm config indices (fee tiers) probed by PDA.
+const CPMM_CONFIG_INDICES: std::ops::Range<u16> = 0..8;
+/// `get_multiple_accounts` limit per request.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// One pool trading `mint`, as returned by `find_pool_by_mint`.
+#[derive(Clone, Debug)]
+pub struct PoolCandidate {
+    pub dex_type: DexType,
+    /// Pool address (bonding curve for `PumpFun`, pool state for Bonk / CPMM, amm for AMM v4).
+    pub pool: Pubkey,
+    /// Token paired with `mint` in this pool.
+    pub quote_mint: Pubkey,
+    /// `mint` held by the pool.
+    pub base_reserve: u64,
+    /// `quote_mint` held by the pool, in raw units; candidates sharing a `quote_mint` are ranked
+    /// by this.
+    pub quote_reserve: u64,
+}
+
+/// Pool with its vaults, oriented so `base_vault` holds `mint`.
+struct PoolVaults {
+    pool: Pubkey,
+    quote_mint: Pubkey,
+    base_vault: Pubkey,
+    quote_vault: Pubkey,
+}
+
+impl PoolVaults {
+    /// Orient a pool of (`mint_a`, `mint_b`) around `mint`; `None` if `mint` is neither side.
+    fn oriented(
+        mint: &Pubkey,
+        pool: Pubkey,
+        (mint_a, vault_a): (Pubkey, Pubkey),
+        (mint_b, vault_b): (Pubkey, Pubkey),
+    ) -> Option<Self> {
+        if mint_a == *mint {
+            Some(Self { pool, quote_mint: mint_b, base_vault: vault_a, quote_vault: vault_b })
+        } else if mint_b == *mint {
+            Some(Self { pool, quote_mint: mint_a, base_vault: vault_b, quote_vault: vault_a })
+        } else {
+            None
+        }
+    }
+}
+
+/// All pools of `dex_type` trading `mint`, grouped by quote mint and deepest first within a group.
+pub async fn find_pools_by_mint(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+    dex_type: DexType,
+) -> Result<Vec<PoolCandidate>, anyhow::Error> {
+    let pools = match dex_type {
+        DexType::PumpFun => return pumpfun_candidates(rpc, mint).await,
+        DexType::PumpSwap => pumpswap_pools(rpc, mint).await?,
+        DexType::Bonk => bonk_pools(rpc, mint).await?,
+        DexType::RaydiumCpmm => raydium_cpmm_pools(rpc, mint).await?,
+        DexType::RaydiumAmmV4 => raydium_amm_v4_pools(rpc, mint).await?,
+        DexType::MeteoraDammV2 => meteora_damm_v2_pools(rpc, mint).await?,
+    };
+    let mut candidates = with_reserves(rpc, dex_type, pools).await?;
+    if candidates.is_empty() {
+        return Err(anyhow!("No {:?} pool found for mint {}", dex_type, mint));
+    }
+    rank_candidates(&mut candidates);
+    Ok(candidates)
+}
+
+/// Order by quote mint (`PDA_QUOTE_MINTS` order, then any other mint), then by quote reserve,
+/// deepest first.
+fn rank_candidates(candidates: &mut [PoolCandidate]) {
+    candidates.sort_by(|a, b| {
+        let group = |c: &PoolCandidate| {
+            PDA_QUOTE_MINTS.iter().position(|q| *q == c.quote_mint).unwrap_or(PDA_QUOTE_MINTS.len())
+        };
+        group(a)
+            .cmp(&group(b))
+            .then_with(|| a.quote_mint.cmp(&b.quote_mint))
+            .then_with(|| b.quote_reserve.cmp(&a.quote_reserve))
+    });
+}
+
+/// getProgramAccounts on `program_id` for accounts of `data_size` bytes matching every
+/// `(offset, pubkey)` memcmp. Returns `(address, data)` pairs.
+pub(crate) async fn scan_program_accounts(
+    rpc: &SolanaRpcClient,
+    program_id: &Pubkey,
+    data_size: u64,
+    memcmps: &[(usize, &Pubkey)],
+) -> Result<Vec<(Pubkey, Vec<u8>)>, anyhow::Error> {
+    let mut filters = vec![solana_rpc_client_api::filter::RpcFilterType::DataSize(data_size)];
+    for (offset, pubkey) in memcmps {
+        filters.push(solana_rpc_client_api::filter::RpcFilterType::Memcmp(
+            solana_client::rpc_filter::Memcmp::new_base58_encoded(*offset, pubkey.as_ref()),
+        ));
+    }
+    let config = solana_rpc_client_api::config::RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: solana_rpc_client_api::config::RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: None,
+            commitment: None,
+            min_context_slot: None,
+        },
+        with_context: None,
+        sort_results: None,
+    };
+    #[allow(deprecated)]
+    let accounts = rpc.get_program_accounts_with_config(program_id, config).await?;
+    Ok(accounts.into_iter().map(|(address, account)| (address, account.data)).collect())
+}
+
+/// Scan with `mint` at either offset; with `pda_hits`, a failed scan is tolerated.
+async fn scan_both_sides(
+    rpc: &SolanaRpcClient,
+    program_id: &Pubkey,
+    data_size: u64,
+    (offset_a, offset_b): (usize, usize),
+    mint: &Pubkey,
+    pda_hits: bool,
+) -> Result<Vec<(Pubkey, Vec<u8>)>, anyhow::Error> {
+    let mut found = Vec::new();
+    for offset in [offset_a, offset_b] {
+        match scan_program_accounts(rpc, program_id, data_size, &[(offset, mint)]).await {
+            Ok(accounts) => found.extend(accounts),
+            Err(_) if pda_hits => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(found)
+}
+
+/// Fetch `addresses` owned by `program_id` in batches, returning `(address, data)` of those that exist.
+async fn fetch_owned_accounts(
+    rpc: &SolanaRpcClient,
+    program_id: &Pubkey,
+    addresses: &[Pubkey],
+) -> Result<Vec<(Pubkey, Vec<u8>)>, anyhow::Error> {
+    let mut found = Vec::new();
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc.get_multiple_accounts(chunk).await?;
+        for (address, account) in chunk.iter().zip(accounts) {
+            if let Some(account) = account.filter(|a| a.owner == *program_id) {
+                found.push((*address, account.data));
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Read both vault balances of every pool and build the candidates.
+async fn with_reserves(
+    rpc: &SolanaRpcClient,
+    dex_type: DexType,
+    pools: Vec<PoolVaults>,
+) -> Result<Vec<PoolCandidate>, anyhow::Error> {
+    let vaults: Vec<Pubkey> = pools.iter().flat_map(|p| [p.base_vault, p.quote_vault]).collect();
+    let mut balances = Vec::with_capacity(vaults.len());
+    for chunk in vaults.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        for account in rpc.get_multiple_accounts(chunk).await? {
+            // SPL Token / Token-2022 account: mint(32) + owner(32) + amount(u64)
+            balances.push(account.and_then(|a| {
+                a.data.get(64..72).map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+            }));
+        }
+    }
+    Ok(candidates_from_balances(dex_type, pools, &balances))
+}
+
+/// Pair each pool with its (base, quote) vault balances; pools with a missing vault are dropped.
+fn candidates_from_balances(
+    dex_type: DexType,
+    pools: Vec<PoolVaults>,
+    balances: &[Option<u64>],
+) -> Vec<PoolCandidate> {
+    pools
+        .into_iter()
+        .zip(balances.chunks(2))
+        .filter_map(|(pool, balance)| {
+            Some(PoolCandidate {
+                dex_type,
+                pool: pool.pool,
+                quote_mint: pool.quote_mint,
+                base_reserve: balance[0]?,
+                quote_reserve: balance[1]?,
+            })
+        })
+        .collect()
+}
+
+async fn pumpfun_candidates(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+) -> Result<Vec<PoolCandidate>, anyhow::Error> {
+    let (curve, address) = pumpfun::fetch_bonding_curve_account(rpc, mint).await?;
+    if curve.complete {
+        return Err(anyhow!("PumpFun bonding curve of {} is complete", mint));
+    }
+    Ok(vec![PoolCandidate {
+        dex_type: DexType::PumpFun,
+        pool: address,
+        quote_mint: WSOL_TOKEN_ACCOUNT,
+        base_reserve: curve.real_token_reserves,
+        quote_reserve: curve.real_sol_reserves,
+    }])
+}
+
+async fn pumpswap_pools(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+) -> Result<Vec<PoolVaults>, anyhow::Error> {
+    let program_id = pumpswap::accounts::AMM_PROGRAM;
+    let mut addresses = vec![pumpswap::get_canonical_pool_pda(mint)];
+    addresses.extend(pumpswap::get_pool_v2_pda(mint));
+    let mut accounts = fetch_owned_accounts(rpc, &program_id, &addresses).await?;
+    let scanned = scan_both_sides(
+        rpc,
+        &program_id,
+        pumpswap::POOL_ACCOUNT_DATA_LEN,
+        (pumpswap::BASE_MINT_OFFSET, pumpswap::QUOTE_MINT_OFFSET),
+        mint,
+        !accounts.is_empty(),
+    )
+    .await?;
+    accounts.extend(scanned);
+    Ok(dedup(accounts)
+        .filter_map(|(address, data)| {
+            let pool = pumpswap_types::pool_decode(data.get(8..)?)?;
+            PoolVaults::oriented(
+                mint,
+                address,
+                (pool.base_mint, pool.pool_base_token_account),
+                (pool.quote_mint, pool.pool_quote_token_account),
+            )
+        })
+        .collect())
+}
+
+async fn bonk_pools(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+) -> Result<Vec<PoolVaults>, anyhow::Error> {
+    let addresses: Vec<Pubkey> = [WSOL_TOKEN_ACCOUNT, USD1_TOKEN_ACCOUNT]
+        .iter()
+        .filter_map(|quote_mint| bonk::get_pool_pda(mint, quote_mint))
+        .collect();
+    let accounts = fetch_owned_accounts(rpc, &bonk::accounts::BONK, &addresses).await?;
+    Ok(accounts
+        .into_iter()
+        .filter_map(|(address, data)| {
+            let pool = bonk_types::pool_state_decode(data.get(8..)?)?;
+            // Migrated curves no longer trade on LaunchLab
+            if pool.status != bonk::pool_status::FUND {
+                return None;
+            }
+            PoolVaults::oriented(
+                mint,
+                address,
+                (pool.base_mint, pool.base_vault),
+                (pool.quote_mint, pool.quote_vault),
+            )
+        })
+        .collect())
+}
+
+async fn raydium_cpmm_pools(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+) -> Result<Vec<PoolVaults>, anyhow::Error> {
+    let program_id = raydium_cpmm::accounts::RAYDIUM_CPMM;
+    let mut addresses = Vec::new();
+    for index in CPMM_CONFIG_INDICES {
+        let Some(amm_config) = raydium_cpmm::get_amm_config_pda(index) else { continue };
+        for quote_mint in PDA_QUOTE_MINTS.iter().filter(|q| *q != mint) {
+            let (token0, token1) =
+                if mint < quote_mint { (mint, quote_mint) } else { (quote_mint, mint) };
+            addresses.extend(raydium_cpmm::get_pool_pda(&amm_config, token0, token1));
+        }
+    }
+    let mut accounts = fetch_owned_accounts(rpc, &program_id, &addresses).await?;
+    let scanned = scan_both_sides(
+        rpc,
+        &program_id,
+        raydium_cpmm::POOL_STATE_ACCOUNT_DATA_LEN,
+        (raydium_cpmm::TOKEN0_MINT_OFFSET, raydium_cpmm::TOKEN1_MINT_OFFSET),
+        mint,
+        !accounts.is_empty(),
+    )
+    .await?;
+    accounts.extend(scanned);
+    Ok(dedup(accounts)
+        .filter_map(|(address, data)| {
+            let pool = raydium_cpmm_types::pool_state_decode(data.get(8..)?)?;
+            PoolVaults::oriented(
+                mint,
+                address,
+                (pool.token0_mint, pool.token0_vault),
+                (pool.token1_mint, pool.token1_vault),
+            )
+        })
+        .collect())
+}
+
+async fn raydium_amm_v4_pools(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+) -> Result<Vec<PoolVaults>, anyhow::Error> {
+    let accounts = scan_both_sides(
+        rpc,
+        &raydium_amm_v4::accounts::RAYDIUM_AMM_V4,
+        raydium_amm_v4_types::AMM_INFO_SIZE as u64,
+        (raydium_amm_v4::COIN_MINT_OFFSET, raydium_amm_v4::PC_MINT_OFFSET),
+        mint,
+        false,
+    )
+    .await?;
+    Ok(dedup(accounts)
+        .filter_map(|(address, data)| {
+            let amm = raydium_amm_v4_types::amm_info_decode(&data)?;
+            PoolVaults::oriented(
+                mint,
+                address,
+                (amm.coin_mint, amm.token_coin),
+                (amm.pc_mint, amm.token_pc),
+            )
+        })
+        .collect())
+}
+
+async fn meteora_damm_v2_pools(
+    rpc: &SolanaRpcClient,
+    mint: &Pubkey,
+) -> Result<Vec<PoolVaults>, anyhow::Error> {
+    let accounts = scan_both_sides(
+        rpc,
+        &meteora_damm_v2::accounts::METEORA_DAMM_V2,
+        meteora_damm_v2::POOL_ACCOUNT_DATA_LEN,
+        (meteora_damm_v2::TOKEN_A_MINT_OFFSET, meteora_damm_v2::TOKEN_B_MINT_OFFSET),
+        mint,
+        false,
+    )
+    .await?;
+    Ok(dedup(accounts)
+        .filter_map(|(address, data)| {
+            let pool = meteora_damm_v2_types::pool_decode(data.get(8..)?)?;
+            PoolVaults::oriented(
+                mint,
+                address,
+                (pool.token_a_mint, pool.token_a_vault),
+                (pool.token_b_mint, pool.token_b_vault),
+            )
+        })
+        .collect())
+}
+
+/// Drop repeated addresses (a pool found both by PDA and by scan), keeping the first.
+fn dedup(accounts: Vec<(Pubkey, Vec<u8>)>) -> impl Iterator<Item = (Pubkey, Vec<u8>)> {
+    let mut seen = HashSet::new();
+    accounts.into_iter().filter(move |(address, _)| seen.insert(*address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(quote_mint: Pubkey, quote_reserve: u64) -> PoolCandidate {
+        PoolCandidate {
+            dex_type: DexType::RaydiumCpmm,
+            pool: Pubkey::new_unique(),
+            quote_mint,
+            base_reserve: 1_000,
+            quote_reserve,
+        }
+    }
+
+    #[test]
+    fn ranking_never_compares_reserves_across_quote_mints() {
+        let other = Pubkey::new_unique();
+        let mut candidates = vec![
+            candidate(USDC_TOKEN_ACCOUNT, 5_000_000_000_000),
+            candidate(other, 9_000_000_000_000),
+            candidate(WSOL_TOKEN_ACCOUNT, 1_000),
+            candidate(USDC_TOKEN_ACCOUNT, 7_000_000_000_000),
+            candidate(WSOL_TOKEN_ACCOUNT, 2_000),
+        ];
+        rank_candidates(&mut candidates);
+
+        let ranked: Vec<(Pubkey, u64)> =
+            candidates.iter().map(|c| (c.quote_mint, c.quote_reserve)).collect();
+        assert_eq!(
+            ranked,
+            vec![
+                (WSOL_TOKEN_ACCOUNT, 2_000),
+                (WSOL_TOKEN_ACCOUNT, 1_000),
+                (USDC_TOKEN_ACCOUNT, 7_000_000_000_000),
+                (USDC_TOKEN_ACCOUNT, 5_000_000_000_000),
+                (other, 9_000_000_000_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn pools_are_oriented_around_the_mint() {
+        let mint = Pubkey::new_unique();
+        let (mint_vault, quote_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let pool = Pubkey::new_unique();
+
+        let flipped = PoolVaults::oriented(
+            &mint,
+            pool,
+            (WSOL_TOKEN_ACCOUNT, quote_vault),
+            (mint, mint_vault),
+        )
+        .unwrap();
+        assert_eq!(flipped.quote_mint, WSOL_TOKEN_ACCOUNT);
+        assert_eq!(flipped.base_vault, mint_vault);
+        assert_eq!(flipped.quote_vault, quote_vault);
+
+        let unrelated = PoolVaults::oriented(
+            &mint,
+            pool,
+            (WSOL_TOKEN_ACCOUNT, quote_vault),
+            (USDC_TOKEN_ACCOUNT, mint_vault),
+        );
+        assert!(unrelated.is_none());
+    }
+
+    #[test]
+    fn pools_with_a_missing_vault_are_dropped() {
+        let mint = Pubkey::new_unique();
+        let pool = |quote_mint| {
+            PoolVaults::oriented(
+                &mint,
+                Pubkey::new_unique(),
+                (mint, Pubkey::new_unique()),
+                (quote_mint, Pubkey::new_unique()),
+            )
+            .unwrap()
+        };
+        let pools =
+            vec![pool(WSOL_TOKEN_ACCOUNT), pool(USDC_TOKEN_ACCOUNT), pool(USD1_TOKEN_ACCOUNT)];
+        let balances = [Some(10), Some(20), Some(30), None, None, Some(60)];
+
+        let candidates = candidates_from_balances(DexType::PumpSwap, pools, &balances);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].quote_mint, WSOL_TOKEN_ACCOUNT);
+        assert_eq!((candidates[0].base_reserve, candidates[0].quote_reserve), (10, 20));
+    }
+
+    #[test]
+    fn dedup_keeps_the_first_hit_of_an_address() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let kept: Vec<_> = dedup(vec![(a, vec![1]), (b, vec![2]), (a, vec![3])]).collect();
+        assert_eq!(kept, vec![(a, vec![1]), (b, vec![2])]);
+    }
+}
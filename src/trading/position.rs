@@ -0,0 +1,675 @@
+//! Take-profit / stop-loss position manager.
+//!
+//! - **Entry**: `PositionManager::buy_and_track` buys and records the fill from the token balance
+//!   change; `open` records a fill the caller measured itself. Buying a mint that is already
+//!   open adds to the position at the weighted average price.
+//! - **Price**: `update_reserves` / `update_params` feed prices from the caller's event stream
+//!   (`update_params` also refreshes the params exits are sold with); `poll` reads every open
+//!   pool over RPC instead.
+//! - **Exits**: each price is checked against the position's `ExitRules` (take-profit ladder,
+//!   stop loss, trailing stop, maximum hold time); a triggered rule sells a percentage of the
+//!   remaining tokens through `TradingClient::sell_by_percent`. A sell that provably did not
+//!   execute (nothing was submitted, or it failed on-chain) leaves the rule armed for the next
+//!   price; when the outcome is unknown (e.g. the confirmation timed out) the exit stays pending
+//!   and the position does not exit again until `resolve`d.
+//! - **Persistence**: with `with_store`, open positions are written to a JSON file after every
+//!   change and restored on start. Sell params are not persisted: re-attach them with
+//!   `set_sell_params` (exits of a position without them fail and are retried).
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::anyhow;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+
+use crate::common::fast_fn::get_associated_token_address_with_program_id_fast_use_seed;
+use crate::swqos::common::TradeError;
+use crate::trading::core::async_executor::nothing_submitted;
+use crate::trading::core::params::DexParamEnum;
+use crate::trading::factory::DexType;
+use crate::trading::ledger::unix_now;
+use crate::trading::price_guard;
+use crate::{TradeBuyParams, TradeSellParams, TradingClient};
+
+/// Sell `percent` of the remaining tokens once the price is `gain_bps` above the entry price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TakeProfitLevel {
+    pub gain_bps: u64,
+    /// 1-100, of the tokens still held when the level triggers.
+    pub percent: u64,
+}
+
+/// When to exit a position. Unset rules never trigger.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitRules {
+    /// Partial (or full) exits as the price rises; each level triggers once.
+    pub take_profit: Vec<TakeProfitLevel>,
+    /// Sell everything once the price is this far below the entry price (basis points).
+    pub stop_loss_bps: Option<u64>,
+    /// Sell everything once the price falls this far below the highest price seen since entry
+    /// (basis points). Only armed once the price has been above the entry price.
+    pub trailing_stop_bps: Option<u64>,
+    /// Sell everything once the position has been open this long (seconds).
+    pub max_hold_secs: Option<u64>,
+}
+
+impl ExitRules {
+    /// Single full take-profit at `take_profit_bps` and stop loss at `stop_loss_bps`.
+    pub fn take_profit_stop_loss(take_profit_bps: u64, stop_loss_bps: u64) -> Self {
+        Self {
+            take_profit: vec![TakeProfitLevel { gain_bps: take_profit_bps, percent: 100 }],
+            stop_loss_bps: Some(stop_loss_bps),
+            ..Default::default()
+        }
+    }
+}
+
+/// Rule that triggered an exit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExitReason {
+    /// Index into `ExitRules::take_profit`.
+    TakeProfit(usize),
+    StopLoss,
+    TrailingStop,
+    MaxHoldTime,
+}
+
+impl fmt::Display for ExitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExitReason::TakeProfit(level) => write!(f, "take-profit #{}", level + 1),
+            ExitReason::StopLoss => write!(f, "stop-loss"),
+            ExitReason::TrailingStop => write!(f, "trailing stop"),
+            ExitReason::MaxHoldTime => write!(f, "max hold time"),
+        }
+    }
+}
+
+/// Exit sell that was submitted but did not confirm; it may still have landed. Settle it with
+/// `PositionManager::resolve`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingExit {
+    pub reason: ExitReason,
+    /// Tokens the sell was for.
+    pub tokens: u64,
+}
+
+/// An open position. Prices are in quote per token, raw units (as implied by pool reserves).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Position {
+    pub mint: Pubkey,
+    pub dex_type: DexType,
+    /// Quote spent on entry (lamports for SOL).
+    pub entry_amount_in: u64,
+    /// Tokens received on entry.
+    pub entry_tokens: u64,
+    /// Tokens still held.
+    pub remaining_tokens: u64,
+    pub entry_price: f64,
+    pub peak_price: f64,
+    pub last_price: f64,
+    /// Unix seconds.
+    pub opened_at: u64,
+    pub rules: ExitRules,
+    /// Take-profit levels already sold (same order as `rules.take_profit`).
+    pub take_profit_hit: Vec<bool>,
+    /// Exit whose outcome is unknown; no other exit triggers until it is resolved.
+    #[serde(default)]
+    pub pending_exit: Option<PendingExit>,
+    /// An exit sell is in flight.
+    #[serde(skip)]
+    exiting: bool,
+}
+
+impl Position {
+    pub fn new(
+        mint: Pubkey,
+        dex_type: DexType,
+        entry_amount_in: u64,
+        entry_tokens: u64,
+        rules: ExitRules,
+    ) -> Self {
+        let entry_price = entry_amount_in as f64 / entry_tokens.max(1) as f64;
+        Self {
+            mint,
+            dex_type,
+            entry_amount_in,
+            entry_tokens,
+            remaining_tokens: entry_tokens,
+            entry_price,
+            peak_price: entry_price,
+            last_price: entry_price,
+            opened_at: unix_now(),
+            take_profit_hit: vec![false; rules.take_profit.len()],
+            rules,
+            pending_exit: None,
+            exiting: false,
+        }
+    }
+
+    /// Change of `last_price` against `entry_price` in basis points.
+    pub fn gain_bps(&self) -> i64 {
+        move_bps(self.entry_price, self.last_price)
+    }
+
+    /// Record `price` and return the exit it triggers at `now` (unix seconds), as a percentage of
+    /// the remaining tokens. Stop rules take precedence over take-profit levels; of several
+    /// take-profit levels crossed at once the highest not yet sold wins.
+    pub fn evaluate(&mut self, price: f64, now: u64) -> Option<(ExitReason, u64)> {
+        self.last_price = price;
+        self.peak_price = self.peak_price.max(price);
+        if self.remaining_tokens == 0 {
+            return None;
+        }
+        let gain = move_bps(self.entry_price, price);
+        if let Some(stop_loss) = self.rules.stop_loss_bps {
+            if gain <= -(stop_loss as i64) {
+                return Some((ExitReason::StopLoss, 100));
+            }
+        }
+        if let Some(trailing) = self.rules.trailing_stop_bps {
+            if self.peak_price > self.entry_price
+                && move_bps(self.peak_price, price) <= -(trailing as i64)
+            {
+                return Some((ExitReason::TrailingStop, 100));
+            }
+        }
+        if let Some(max_hold) = self.rules.max_hold_secs {
+            if now.saturating_sub(self.opened_at) >= max_hold {
+                return Some((ExitReason::MaxHoldTime, 100));
+            }
+        }
+        self.rules
+            .take_profit
+            .iter()
+            .enumerate()
+            .filter(|(i, level)| {
+                !self.take_profit_hit.get(*i).copied().unwrap_or(false)
+                    && gain >= level.gain_bps as i64
+            })
+            .max_by_key(|(_, level)| level.gain_bps)
+            .map(|(i, level)| (ExitReason::TakeProfit(i), level.percent.clamp(1, 100)))
+    }
+
+    /// Apply a filled exit of `tokens_sold`; a take-profit also marks every lower level sold.
+    fn record_exit(&mut self, reason: ExitReason, tokens_sold: u64) {
+        self.remaining_tokens = self.remaining_tokens.saturating_sub(tokens_sold);
+        if let ExitReason::TakeProfit(i) = reason {
+            let gain_bps = self.rules.take_profit[i].gain_bps;
+            for (level, hit) in self.rules.take_profit.iter().zip(self.take_profit_hit.iter_mut()) {
+                *hit |= level.gain_bps <= gain_bps;
+            }
+        }
+    }
+
+    /// Add a fill to the position at the weighted average price.
+    fn add_fill(&mut self, amount_in: u64, tokens: u64) {
+        self.entry_amount_in += amount_in;
+        self.entry_tokens += tokens;
+        self.remaining_tokens += tokens;
+        self.entry_price = self.entry_amount_in as f64 / self.entry_tokens.max(1) as f64;
+    }
+}
+
+/// Outcome of one triggered exit.
+pub struct ExitOutcome {
+    pub mint: Pubkey,
+    pub reason: ExitReason,
+    /// Percentage of the remaining tokens sold.
+    pub percent: u64,
+    pub price: f64,
+    pub success: bool,
+    /// The sell was submitted but its outcome is unknown; the exit is pending.
+    pub pending: bool,
+    pub signatures: Vec<Signature>,
+    pub error: Option<TradeError>,
+}
+
+#[inline]
+fn move_bps(from: f64, to: f64) -> i64 {
+    if from <= 0.0 {
+        return 0;
+    }
+    ((to - from) / from * 10_000.0) as i64
+}
+
+/// Whether an exit sell that did not succeed provably did not execute: it was never submitted,
+/// or failed on-chain. `From<anyhow::Error>` gives code 500 to every `TradeError` that did not
+/// come from a landed transaction.
+fn exit_not_executed(signatures: &[Signature], error: Option<&TradeError>) -> bool {
+    nothing_submitted(signatures) || error.is_some_and(|e| e.code != 500)
+}
+
+/// Tracks open positions and sells them according to their `ExitRules`.
+pub struct PositionManager {
+    client: TradingClient,
+    positions: Mutex<HashMap<Pubkey, Position>>,
+    sell_params: Mutex<HashMap<Pubkey, TradeSellParams>>,
+    store_path: Option<PathBuf>,
+}
+
+impl PositionManager {
+    /// In-memory manager selling through `client`.
+    pub fn new(client: TradingClient) -> Self {
+        Self {
+            client,
+            positions: Mutex::new(HashMap::new()),
+            sell_params: Mutex::new(HashMap::new()),
+            store_path: None,
+        }
+    }
+
+    /// Manager persisting open positions to `path` (JSON), restoring any already stored there.
+    pub fn with_store(
+        client: TradingClient,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, anyhow::Error> {
+        let path = path.as_ref().to_path_buf();
+        let positions: Vec<Position> = match std::fs::read(&path) {
+            Ok(data) => serde_json::from_slice(&data)
+                .map_err(|e| anyhow!("Failed to read positions from {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let mut manager = Self::new(client);
+        manager.positions = Mutex::new(positions.into_iter().map(|p| (p.mint, p)).collect());
+        manager.store_path = Some(path);
+        Ok(manager)
+    }
+
+    /// Snapshot of the open positions.
+    pub fn positions(&self) -> Vec<Position> {
+        self.positions.lock().values().cloned().collect()
+    }
+
+    pub fn position(&self, mint: &Pubkey) -> Option<Position> {
+        self.positions.lock().get(mint).cloned()
+    }
+
+    /// Params exits of `mint` are sold with (`input_token_amount` is replaced per exit; a
+    /// `recent_blockhash` is fetched per exit unless a durable nonce is set).
+    pub fn set_sell_params(&self, mint: Pubkey, params: TradeSellParams) {
+        self.sell_params.lock().insert(mint, params);
+    }
+
+    /// Record an entry fill: `tokens` received for `amount_in` of quote.
+    pub fn open(
+        &self,
+        mint: Pubkey,
+        dex_type: DexType,
+        amount_in: u64,
+        tokens: u64,
+        rules: ExitRules,
+    ) -> Result<(), anyhow::Error> {
+        if tokens == 0 {
+            return Err(anyhow!("Cannot open a position of 0 tokens"));
+        }
+        {
+            let mut positions = self.positions.lock();
+            match positions.get_mut(&mint) {
+                Some(position) => position.add_fill(amount_in, tokens),
+                None => {
+                    positions.insert(mint, Position::new(mint, dex_type, amount_in, tokens, rules));
+                }
+            }
+        }
+        self.persist()
+    }
+
+    /// Stop tracking `mint` (nothing is sold).
+    pub fn close(&self, mint: &Pubkey) -> Result<Option<Position>, anyhow::Error> {
+        let position = self.positions.lock().remove(mint);
+        self.sell_params.lock().remove(mint);
+        self.persist()?;
+        Ok(position)
+    }
+
+    /// Buy with `params` and track the tokens received under `rules`, selling exits with
+    /// `sell_params`. The fill is read from the token account balance, so the buy must wait for
+    /// confirmation.
+    pub async fn buy_and_track(
+        &self,
+        params: TradeBuyParams,
+        sell_params: TradeSellParams,
+        rules: ExitRules,
+    ) -> Result<(bool, Vec<Signature>, Option<TradeError>), anyhow::Error> {
+        if !params.wait_transaction_confirmed || params.simulate {
+            return Err(anyhow!(
+                "buy_and_track needs a confirmed (non-simulated) buy to read the filled amount"
+            ));
+        }
+        let (mint, dex_type, amount_in) = (params.mint, params.dex_type, params.input_token_amount);
+        let rpc = &self.client.infrastructure.rpc;
+        let token_program = rpc.get_account(&mint).await?.owner;
+        let token_account = get_associated_token_address_with_program_id_fast_use_seed(
+            &self.client.payer.pubkey(),
+            &mint,
+            &token_program,
+            self.client.use_seed_optimize,
+        );
+        let before = token_balance(&self.client, &token_account).await;
+        let result = self.client.buy(params).await?;
+        if result.0 {
+            let tokens = token_balance(&self.client, &token_account).await.saturating_sub(before);
+            self.set_sell_params(mint, sell_params);
+            self.open(mint, dex_type, amount_in, tokens, rules)?;
+        }
+        Ok(result)
+    }
+
+    /// Feed a price from pool reserves (`token_reserve` of the mint, `quote_reserve` of its quote
+    /// token; virtual reserves for bonding curves) and run any exit it triggers.
+    pub async fn update_reserves(
+        &self,
+        mint: &Pubkey,
+        token_reserve: u64,
+        quote_reserve: u64,
+    ) -> Option<ExitOutcome> {
+        if token_reserve == 0 {
+            return None;
+        }
+        self.update_price(mint, quote_reserve as f64 / token_reserve as f64).await
+    }
+
+    /// Feed fresh protocol params (e.g. built from a trade event): they replace the
+    /// `extension_params` exits are sold with, and their reserves give the price.
+    pub async fn update_params(
+        &self,
+        mint: &Pubkey,
+        params: DexParamEnum,
+    ) -> Result<Option<ExitOutcome>, anyhow::Error> {
+        let price = price_guard::snapshot_price(&params, mint)?;
+        if let Some(sell_params) = self.sell_params.lock().get_mut(mint) {
+            sell_params.extension_params = params;
+        }
+        Ok(self.update_price(mint, price).await)
+    }
+
+    /// Read the price of every open position over RPC (from its sell params) and run the exits
+    /// triggered; positions without sell params or whose pool fails to load are skipped.
+    pub async fn poll(&self) -> Vec<ExitOutcome> {
+        let tracked: Vec<(Pubkey, DexParamEnum)> = {
+            let positions = self.positions.lock();
+            let sell_params = self.sell_params.lock();
+            positions
+                .keys()
+                .filter_map(|mint| {
+                    sell_params.get(mint).map(|p| (*mint, p.extension_params.clone()))
+                })
+                .collect()
+        };
+        let rpc = &self.client.infrastructure.rpc;
+        let mut outcomes = Vec::new();
+        for (mint, params) in tracked {
+            if let Ok(price) = price_guard::fresh_price(rpc, &params, &mint).await {
+                outcomes.extend(self.update_price(&mint, price).await);
+            }
+        }
+        outcomes
+    }
+
+    /// `poll` every `interval` until no position is open.
+    pub async fn poll_until_closed(&self, interval: Duration) -> Vec<ExitOutcome> {
+        let mut outcomes = Vec::new();
+        while !self.positions.lock().is_empty() {
+            outcomes.extend(self.poll().await);
+            tokio::time::sleep(interval).await;
+        }
+        outcomes
+    }
+
+    /// Record `price` for `mint` and sell if an exit rule triggers.
+    pub async fn update_price(&self, mint: &Pubkey, price: f64) -> Option<ExitOutcome> {
+        let (reason, percent, remaining) = {
+            let mut positions = self.positions.lock();
+            let position = positions.get_mut(mint)?;
+            if position.exiting || position.pending_exit.is_some() {
+                position.last_price = price;
+                return None;
+            }
+            let (reason, percent) = position.evaluate(price, unix_now())?;
+            position.exiting = true;
+            (reason, percent, position.remaining_tokens)
+        };
+
+        let result = self.sell_exit(mint, remaining, percent).await;
+        let (success, signatures, error) = match result {
+            Ok(result) => result,
+            Err(e) => (false, Vec::new(), Some(TradeError::from(e))),
+        };
+        let exit = PendingExit { reason, tokens: remaining * percent / 100 };
+        let pending = !success && !exit_not_executed(&signatures, error.as_ref());
+        self.settle_exit(mint, exit, success, pending);
+        Some(ExitOutcome {
+            mint: *mint,
+            reason,
+            percent,
+            price,
+            success,
+            pending,
+            signatures,
+            error,
+        })
+    }
+
+    /// Settle the pending exit of `mint` once its sell's outcome is known: a fill is applied to
+    /// the position, otherwise the rule is armed again. Returns the position (removed when the
+    /// fill sold everything), or `None` when `mint` has no pending exit.
+    pub fn resolve(&self, mint: &Pubkey, filled: bool) -> Option<Position> {
+        let position = {
+            let mut positions = self.positions.lock();
+            let position = positions.get_mut(mint)?;
+            let exit = position.pending_exit.take()?;
+            if filled {
+                position.record_exit(exit.reason, exit.tokens);
+            }
+            let position = position.clone();
+            if position.remaining_tokens == 0 {
+                positions.remove(mint);
+                self.sell_params.lock().remove(mint);
+            }
+            position
+        };
+        if let Err(e) = self.persist() {
+            tracing::warn!(target: "sol_trade_sdk", "Failed to persist positions: {}", e);
+        }
+        Some(position)
+    }
+
+    /// Apply the outcome of `exit`: `filled` records it, `pending` holds the position until
+    /// `resolve`, anything else arms the rule again.
+    fn settle_exit(&self, mint: &Pubkey, exit: PendingExit, filled: bool, pending: bool) {
+        {
+            let mut positions = self.positions.lock();
+            let Some(position) = positions.get_mut(mint) else { return };
+            position.exiting = false;
+            if filled {
+                position.record_exit(exit.reason, exit.tokens);
+                if position.remaining_tokens == 0 {
+                    positions.remove(mint);
+                    self.sell_params.lock().remove(mint);
+                }
+            } else if pending {
+                position.pending_exit = Some(exit);
+            }
+        }
+        if filled || pending {
+            if let Err(e) = self.persist() {
+                tracing::warn!(target: "sol_trade_sdk", "Failed to persist positions: {}", e);
+            }
+        }
+    }
+
+    async fn sell_exit(
+        &self,
+        mint: &Pubkey,
+        remaining: u64,
+        percent: u64,
+    ) -> Result<(bool, Vec<Signature>, Option<TradeError>), anyhow::Error> {
+        let mut params = self
+            .sell_params
+            .lock()
+            .get(mint)
+            .cloned()
+            .ok_or_else(|| anyhow!("No sell params for {}, call set_sell_params", mint))?;
+        if params.durable_nonce.is_none() {
+            params.recent_blockhash =
+                Some(self.client.infrastructure.rpc.get_latest_blockhash().await?);
+        }
+        self.client.sell_by_percent(params, remaining, percent).await
+    }
+
+    /// Write the open positions to the store (write to a temporary file, then rename).
+    fn persist(&self) -> Result<(), anyhow::Error> {
+        let Some(path) = &self.store_path else { return Ok(()) };
+        let data = serde_json::to_vec_pretty(&self.positions())?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, data)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Token balance of `token_account`, 0 when it does not exist yet.
+async fn token_balance(client: &TradingClient, token_account: &Pubkey) -> u64 {
+    client
+        .infrastructure
+        .rpc
+        .get_token_account_balance(token_account)
+        .await
+        .ok()
+        .and_then(|balance| balance.amount.parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{InfrastructureConfig, SolanaRpcClient};
+    use crate::swqos::{health::SwqosHealthMonitor, scoreboard::SwqosScoreboard};
+    use crate::TradingInfrastructure;
+    use arc_swap::ArcSwap;
+    use solana_commitment_config::CommitmentConfig;
+    use solana_sdk::signature::Keypair;
+    use std::sync::Arc;
+
+    /// In-memory manager over a client whose RPC node is not running.
+    fn manager() -> PositionManager {
+        let config = InfrastructureConfig::new(
+            "http://127.0.0.1:8899".to_string(),
+            vec![],
+            CommitmentConfig::processed(),
+        );
+        let infrastructure = TradingInfrastructure {
+            rpc: Arc::new(SolanaRpcClient::new(config.rpc_url.clone())),
+            swqos_clients: Arc::new(ArcSwap::from_pointee(Vec::new())),
+            swqos_scoreboard: Arc::new(SwqosScoreboard::default()),
+            swqos_health: Arc::new(SwqosHealthMonitor::default()),
+            config,
+        };
+        PositionManager::new(TradingClient::from_infrastructure(
+            Arc::new(Keypair::new()),
+            Arc::new(infrastructure),
+            false,
+        ))
+    }
+
+    fn position(rules: ExitRules) -> Position {
+        // 1_000 quote for 1_000 tokens: entry price 1.0
+        let mut position =
+            Position::new(Pubkey::new_unique(), DexType::PumpFun, 1_000, 1_000, rules);
+        position.opened_at = 0;
+        position
+    }
+
+    #[test]
+    fn take_profit_ladder_sells_in_parts() {
+        let rules = ExitRules {
+            take_profit: vec![
+                TakeProfitLevel { gain_bps: 5_000, percent: 50 },
+                TakeProfitLevel { gain_bps: 10_000, percent: 100 },
+            ],
+            stop_loss_bps: Some(2_000),
+            ..Default::default()
+        };
+        let mut p = position(rules);
+        assert_eq!(p.evaluate(1.2, 0), None);
+        assert_eq!(p.evaluate(1.6, 0), Some((ExitReason::TakeProfit(0), 50)));
+        p.record_exit(ExitReason::TakeProfit(0), 500);
+        assert_eq!(p.remaining_tokens, 500);
+        assert_eq!(p.evaluate(1.7, 0), None);
+        assert_eq!(p.evaluate(2.1, 0), Some((ExitReason::TakeProfit(1), 100)));
+        assert_eq!(p.evaluate(0.79, 0), Some((ExitReason::StopLoss, 100)));
+    }
+
+    #[test]
+    fn trailing_stop_and_max_hold() {
+        let rules = ExitRules {
+            trailing_stop_bps: Some(1_000),
+            max_hold_secs: Some(60),
+            ..Default::default()
+        };
+        let mut p = position(rules);
+        // Not armed below entry.
+        assert_eq!(p.evaluate(0.85, 10), None);
+        assert_eq!(p.evaluate(2.0, 10), None);
+        assert_eq!(p.evaluate(1.85, 10), None);
+        assert_eq!(p.evaluate(1.79, 10), Some((ExitReason::TrailingStop, 100)));
+        assert_eq!(p.evaluate(2.0, 60), Some((ExitReason::MaxHoldTime, 100)));
+
+        p.add_fill(3_000, 1_000);
+        assert_eq!(p.entry_price, 2.0);
+        assert_eq!(p.remaining_tokens, 2_000);
+    }
+
+    #[tokio::test]
+    async fn exits_with_an_unknown_outcome_stay_pending_until_resolved() {
+        let submitted = [Signature::new_unique()];
+        let on_chain =
+            TradeError { code: 6004, message: "slippage".to_string(), instruction: None };
+        let timeout = TradeError::from(anyhow!("Transaction confirmation timed out after 15s"));
+        assert!(exit_not_executed(&[], Some(&timeout)));
+        assert!(exit_not_executed(&[Signature::default()], None));
+        assert!(exit_not_executed(&submitted, Some(&on_chain)));
+        assert!(!exit_not_executed(&submitted, Some(&timeout)));
+        assert!(!exit_not_executed(&submitted, None));
+
+        let manager = manager();
+        let mint = Pubkey::new_unique();
+        let rules = ExitRules {
+            take_profit: vec![TakeProfitLevel { gain_bps: 5_000, percent: 50 }],
+            stop_loss_bps: Some(2_000),
+            ..Default::default()
+        };
+        manager.open(mint, DexType::PumpFun, 1_000, 1_000, rules).unwrap();
+
+        // No sell params, so nothing is sent and the rule stays armed.
+        let outcome = manager.update_price(&mint, 2.0).await.unwrap();
+        assert!(!outcome.success && !outcome.pending);
+        assert_eq!(manager.position(&mint).unwrap().pending_exit, None);
+
+        // The take-profit sell timed out: the position does not exit again until resolved.
+        let exit = PendingExit { reason: ExitReason::TakeProfit(0), tokens: 500 };
+        manager.settle_exit(&mint, exit, false, true);
+        assert!(manager.update_price(&mint, 2.0).await.is_none());
+        assert!(manager.update_price(&mint, 0.5).await.is_none());
+        let position = manager.resolve(&mint, true).unwrap();
+        assert_eq!((position.remaining_tokens, position.pending_exit), (500, None));
+        assert!(manager.resolve(&mint, true).is_none());
+        assert!(manager.update_price(&mint, 2.0).await.is_none());
+
+        // An unfilled stop loss is armed again; a filled one closes the position.
+        let exit = PendingExit { reason: ExitReason::StopLoss, tokens: 500 };
+        manager.settle_exit(&mint, exit, false, true);
+        assert_eq!(manager.resolve(&mint, false).unwrap().remaining_tokens, 500);
+        manager.settle_exit(&mint, exit, false, true);
+        assert_eq!(manager.resolve(&mint, true).unwrap().remaining_tokens, 0);
+        assert!(manager.position(&mint).is_none());
+    }
+}
@@ -0,0 +1,331 @@
+//! Price guard for trades built from event-sourced reserves.
+//!
+//! Reserves in `DexParamEnum` (e.g. `PumpSwapParams::pool_base_token_reserves`, the PumpFun
+//! `BondingCurveAccount`) usually come from gRPC events and may be stale by the time the trade
+//! lands; a copy-trade buy is front-run exactly when the price moved up in between. The guard
+//! compares the price implied by those reserves with the pool's current state and refuses the
+//! trade when it moved against it by more than `max_move_bps`.
+//!
+//! - `PriceGuardMode::Rpc` re-reads the pool at `processed` commitment before sending (one extra
+//!   round trip).
+//! - `PriceGuardMode::OnChain` adds no latency: the slippage is capped at `max_move_bps`, so the
+//!   swap's own minimum output (maximum input for exact-output buys), computed from the same
+//!   snapshot, reverts it on chain. Trades whose bound does not come from the slippage (a
+//!   `fixed_output_token_amount`, or `MeteoraDammV2` params) are refused in this mode.
+
+use anyhow::anyhow;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::bonding_curve::BondingCurveAccount;
+use crate::common::SolanaRpcClient;
+use crate::instruction::utils::bonk_types::pool_state_decode;
+use crate::instruction::utils::pumpfun::get_bonding_curve_pda;
+use crate::swqos::TradeType;
+use crate::trading::core::params::DexParamEnum;
+
+/// How the guard is enforced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceGuardMode {
+    /// Compare against a fresh `processed` read before sending.
+    Rpc,
+    /// Cap the slippage at `max_move_bps` and let the swap revert on chain.
+    OnChain,
+}
+
+/// Abort a trade when the pool price moved against it by more than `max_move_bps` since the
+/// reserves in its protocol params were captured.
+#[derive(Clone, Copy, Debug)]
+pub struct PriceGuard {
+    pub max_move_bps: u64,
+    pub mode: PriceGuardMode,
+}
+
+impl PriceGuard {
+    pub fn rpc(max_move_bps: u64) -> Self {
+        Self { max_move_bps, mode: PriceGuardMode::Rpc }
+    }
+
+    pub fn on_chain(max_move_bps: u64) -> Self {
+        Self { max_move_bps, mode: PriceGuardMode::OnChain }
+    }
+
+    /// Slippage to trade with in `OnChain` mode: the tighter of `slippage_basis_points` and
+    /// `max_move_bps`.
+    pub fn cap_slippage(&self, slippage_basis_points: Option<u64>) -> Option<u64> {
+        Some(slippage_basis_points.map_or(self.max_move_bps, |s| s.min(self.max_move_bps)))
+    }
+
+    /// Slippage to trade `params` with in `OnChain` mode (see `cap_slippage`). `Err` when the
+    /// swap's bound ignores the slippage: a `fixed_output_token_amount` is set, or the params
+    /// are `MeteoraDammV2`, whose builder only uses the fixed output.
+    pub fn on_chain_slippage(
+        &self,
+        params: &DexParamEnum,
+        fixed_output_token_amount: Option<u64>,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<Option<u64>, anyhow::Error> {
+        if fixed_output_token_amount.is_some() {
+            return Err(anyhow!(
+                "Price guard: OnChain mode cannot bound a trade with fixed_output_token_amount, use Rpc mode"
+            ));
+        }
+        if matches!(params, DexParamEnum::MeteoraDammV2(_)) {
+            return Err(anyhow!(
+                "Price guard: OnChain mode cannot bound MeteoraDammV2 trades, whose builder ignores slippage"
+            ));
+        }
+        Ok(self.cap_slippage(slippage_basis_points))
+    }
+
+    /// `Err` when `check` moved against the trade by more than `max_move_bps`.
+    pub fn ensure(&self, check: &PriceCheck) -> Result<(), anyhow::Error> {
+        if check.adverse_move_bps > self.max_move_bps as i64 {
+            return Err(anyhow!(
+                "Price guard: implied price moved {} bps against the trade (max {}), snapshot {:.12} now {:.12}",
+                check.adverse_move_bps,
+                self.max_move_bps,
+                check.snapshot_price,
+                check.fresh_price
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Implied price (quote per token, raw units) in the params snapshot and on chain.
+#[derive(Clone, Copy, Debug)]
+pub struct PriceCheck {
+    pub snapshot_price: f64,
+    pub fresh_price: f64,
+    /// Move against the trade in basis points: price up for a buy, down for a sell. Negative
+    /// when the price moved in the trade's favour.
+    pub adverse_move_bps: i64,
+}
+
+/// `(token_reserve, quote_reserve)` of a pool.
+type Reserves = (u128, u128);
+
+#[inline]
+fn implied_price((token_reserve, quote_reserve): Reserves) -> f64 {
+    if token_reserve == 0 {
+        return 0.0;
+    }
+    quote_reserve as f64 / token_reserve as f64
+}
+
+fn adverse_move_bps(trade_type: TradeType, snapshot_price: f64, fresh_price: f64) -> i64 {
+    if snapshot_price <= 0.0 {
+        return 0;
+    }
+    let moved = (fresh_price - snapshot_price) / snapshot_price * 10_000.0;
+    match trade_type {
+        TradeType::Sell => -moved as i64,
+        _ => moved as i64,
+    }
+}
+
+/// Orient `(base, quote)` reserves so `mint` is the token side.
+#[inline]
+fn oriented(base_mint: &Pubkey, mint: &Pubkey, base: u64, quote: u64) -> Reserves {
+    if base_mint == mint {
+        (base as u128, quote as u128)
+    } else {
+        (quote as u128, base as u128)
+    }
+}
+
+/// Reserves captured in `params`.
+fn snapshot_reserves(params: &DexParamEnum, mint: &Pubkey) -> Result<Reserves, anyhow::Error> {
+    Ok(match params {
+        DexParamEnum::PumpFun(p) => (
+            p.bonding_curve.virtual_token_reserves as u128,
+            p.bonding_curve.virtual_sol_reserves as u128,
+        ),
+        DexParamEnum::PumpSwap(p) => {
+            oriented(&p.base_mint, mint, p.pool_base_token_reserves, p.pool_quote_token_reserves)
+        }
+        DexParamEnum::Bonk(p) => {
+            (p.virtual_base.saturating_sub(p.real_base), p.virtual_quote + p.real_quote)
+        }
+        DexParamEnum::RaydiumCpmm(p) => {
+            oriented(&p.base_mint, mint, p.base_reserve, p.quote_reserve)
+        }
+        DexParamEnum::RaydiumAmmV4(p) => oriented(&p.coin_mint, mint, p.coin_reserve, p.pc_reserve),
+        DexParamEnum::MeteoraDammV2(_) => {
+            return Err(anyhow!("Price guard: MeteoraDammV2Params carry no reserves to compare"))
+        }
+    })
+}
+
+/// Accounts holding the reserves of `params`: the curve / pool state, or the two vaults.
+fn reserve_accounts(params: &DexParamEnum, mint: &Pubkey) -> Result<Vec<Pubkey>, anyhow::Error> {
+    Ok(match params {
+        DexParamEnum::PumpFun(p) => {
+            let curve = if p.bonding_curve.account == Pubkey::default() {
+                get_bonding_curve_pda(mint)
+                    .ok_or_else(|| anyhow!("bonding_curve PDA derivation failed for {}", mint))?
+            } else {
+                p.bonding_curve.account
+            };
+            vec![curve]
+        }
+        DexParamEnum::PumpSwap(p) => vec![p.pool_base_token_account, p.pool_quote_token_account],
+        DexParamEnum::Bonk(p) => vec![p.pool_state],
+        DexParamEnum::RaydiumCpmm(p) => vec![p.base_vault, p.quote_vault],
+        DexParamEnum::RaydiumAmmV4(p) => vec![p.token_coin, p.token_pc],
+        DexParamEnum::MeteoraDammV2(_) => {
+            return Err(anyhow!("Price guard: MeteoraDammV2Params carry no reserves to compare"))
+        }
+    })
+}
+
+/// SPL Token / Token-2022 account amount: mint(32) + owner(32) + amount(u64).
+#[inline]
+fn token_amount(data: &[u8]) -> Option<u64> {
+    data.get(64..72).map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+}
+
+/// Current reserves of the pool in `params`, read at `processed` commitment.
+async fn fresh_reserves(
+    rpc: &SolanaRpcClient,
+    params: &DexParamEnum,
+    mint: &Pubkey,
+) -> Result<Reserves, anyhow::Error> {
+    let keys = reserve_accounts(params, mint)?;
+    let accounts = rpc
+        .get_multiple_accounts_with_commitment(&keys, CommitmentConfig::processed())
+        .await?
+        .value;
+    let data = |i: usize| -> Result<&[u8], anyhow::Error> {
+        accounts
+            .get(i)
+            .and_then(|a| a.as_ref())
+            .map(|a| a.data.as_slice())
+            .ok_or_else(|| anyhow!("Price guard: account {} not found", keys[i]))
+    };
+    let amount = |i: usize| -> Result<u64, anyhow::Error> {
+        token_amount(data(i)?)
+            .ok_or_else(|| anyhow!("Price guard: {} is not a token account", keys[i]))
+    };
+    Ok(match params {
+        DexParamEnum::PumpFun(_) => {
+            let curve = data(0)?
+                .get(8..)
+                .and_then(|d| {
+                    solana_sdk::borsh1::try_from_slice_unchecked::<BondingCurveAccount>(d).ok()
+                })
+                .ok_or_else(|| anyhow!("Price guard: failed to decode bonding curve"))?;
+            (curve.virtual_token_reserves as u128, curve.virtual_sol_reserves as u128)
+        }
+        DexParamEnum::PumpSwap(p) => oriented(&p.base_mint, mint, amount(0)?, amount(1)?),
+        DexParamEnum::Bonk(_) => {
+            let pool = data(0)?
+                .get(8..)
+                .and_then(pool_state_decode)
+                .ok_or_else(|| anyhow!("Price guard: failed to decode Bonk pool state"))?;
+            (
+                (pool.virtual_base as u128).saturating_sub(pool.real_base as u128),
+                pool.virtual_quote as u128 + pool.real_quote as u128,
+            )
+        }
+        DexParamEnum::RaydiumCpmm(p) => oriented(&p.base_mint, mint, amount(0)?, amount(1)?),
+        DexParamEnum::RaydiumAmmV4(p) => oriented(&p.coin_mint, mint, amount(0)?, amount(1)?),
+        DexParamEnum::MeteoraDammV2(_) => {
+            return Err(anyhow!("Price guard: MeteoraDammV2Params carry no reserves to compare"))
+        }
+    })
+}
+
+/// Price implied by the reserves captured in `params`.
+pub(crate) fn snapshot_price(params: &DexParamEnum, mint: &Pubkey) -> Result<f64, anyhow::Error> {
+    Ok(implied_price(snapshot_reserves(params, mint)?))
+}
+
+/// Current price of the pool in `params`, read at `processed` commitment.
+pub(crate) async fn fresh_price(
+    rpc: &SolanaRpcClient,
+    params: &DexParamEnum,
+    mint: &Pubkey,
+) -> Result<f64, anyhow::Error> {
+    Ok(implied_price(fresh_reserves(rpc, params, mint).await?))
+}
+
+/// Compare the price implied by the reserves in `params` with the pool's `processed` state.
+pub async fn check_price(
+    rpc: &SolanaRpcClient,
+    params: &DexParamEnum,
+    mint: &Pubkey,
+    trade_type: TradeType,
+) -> Result<PriceCheck, anyhow::Error> {
+    let snapshot_price = snapshot_price(params, mint)?;
+    let fresh_price = fresh_price(rpc, params, mint).await?;
+    Ok(PriceCheck {
+        snapshot_price,
+        fresh_price,
+        adverse_move_bps: adverse_move_bps(trade_type, snapshot_price, fresh_price),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trading::core::params::{BonkParams, MeteoraDammV2Params};
+
+    #[test]
+    fn adverse_move_depends_on_side() {
+        assert_eq!(adverse_move_bps(TradeType::Buy, 1.0, 1.02), 200);
+        assert_eq!(adverse_move_bps(TradeType::Sell, 1.0, 1.02), -200);
+        assert_eq!(adverse_move_bps(TradeType::Sell, 1.0, 0.97), 300);
+
+        let guard = PriceGuard::rpc(100);
+        let check = |adverse_move_bps| PriceCheck {
+            snapshot_price: 1.0,
+            fresh_price: 1.0,
+            adverse_move_bps,
+        };
+        assert!(guard.ensure(&check(100)).is_ok());
+        assert!(guard.ensure(&check(-500)).is_ok());
+        assert!(guard.ensure(&check(101)).is_err());
+        assert_eq!(PriceGuard::on_chain(50).cap_slippage(Some(300)), Some(50));
+        assert_eq!(PriceGuard::on_chain(50).cap_slippage(Some(20)), Some(20));
+    }
+
+    #[test]
+    fn on_chain_mode_refuses_trades_slippage_does_not_bound() {
+        let guard = PriceGuard::on_chain(50);
+        let bonk = DexParamEnum::Bonk(BonkParams::default());
+        assert_eq!(guard.on_chain_slippage(&bonk, None, Some(300)).unwrap(), Some(50));
+        assert!(guard.on_chain_slippage(&bonk, Some(1_000), Some(300)).is_err());
+
+        let meteora = DexParamEnum::MeteoraDammV2(MeteoraDammV2Params {
+            pool: Pubkey::new_unique(),
+            token_a_vault: Pubkey::new_unique(),
+            token_b_vault: Pubkey::new_unique(),
+            token_a_mint: Pubkey::new_unique(),
+            token_b_mint: Pubkey::new_unique(),
+            token_a_program: crate::constants::TOKEN_PROGRAM,
+            token_b_program: crate::constants::TOKEN_PROGRAM,
+            token_a_transfer_fee: None,
+            token_b_transfer_fee: None,
+        });
+        assert!(guard.on_chain_slippage(&meteora, None, Some(300)).is_err());
+    }
+
+    #[test]
+    fn reserves_are_oriented_on_the_traded_mint() {
+        let mint = Pubkey::new_unique();
+        let wsol = crate::constants::WSOL_TOKEN_ACCOUNT;
+        assert_eq!(oriented(&wsol, &mint, 10, 1_000), (1_000, 10));
+        assert_eq!(oriented(&mint, &mint, 1_000, 10), (1_000, 10));
+
+        let bonk = BonkParams {
+            virtual_base: 1_000,
+            real_base: 400,
+            virtual_quote: 30,
+            real_quote: 20,
+            ..Default::default()
+        };
+        assert_eq!(snapshot_reserves(&DexParamEnum::Bonk(bonk), &mint).unwrap(), (600, 50));
+    }
+}
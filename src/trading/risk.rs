@@ -0,0 +1,470 @@
+//! Risk limits checked before any instruction is built, by every `TradingClient` trade entry
+//! point (`buy` / `sell`, split and two-hop trades and the helpers built on them).
+//!
+//! A `RiskEngine` holds the limits (replaceable at runtime) and the state they are checked
+//! against: SOL committed per mint, trades in the last minute and the day's realized PnL. A trade
+//! that breaks a limit fails with a `RiskRejection` inside the `anyhow::Error`
+//! (`err.downcast_ref::<RiskRejection>()`).
+//!
+//! - Buys are checked against every limit; the SOL amount of a passing buy is committed to its
+//!   mint right away and given back only when the trade provably did not execute (nothing was
+//!   submitted, or it failed on-chain). A confirmation timeout keeps the SOL committed.
+//! - Sells are only checked against the trade rate and the tip cap, so a position can always be
+//!   exited. A sell that closes the token account (`close_mint_token_ata`), or sells at least the
+//!   tokens the ledger (`with_ledger`) holds for the mint, releases the mint.
+//! - SOL limits apply to SOL / WSOL-quoted buys; buys with other quote tokens only count as
+//!   trades.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::common::GasFeeStrategy;
+use crate::constants::WSOL_TOKEN_ACCOUNT;
+use crate::swqos::TradeType;
+use crate::trading::core::async_executor::trade_not_executed;
+use crate::trading::ledger::{unix_now, Ledger};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Limits a trade is checked against; `None` (or an empty deny list) disables a limit. SOL
+/// amounts are in lamports.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RiskLimits {
+    /// Largest SOL input of a single buy.
+    pub max_sol_per_trade: Option<u64>,
+    /// Largest SOL committed to one mint across buys.
+    pub max_sol_per_mint: Option<u64>,
+    /// Most mints with SOL committed at once.
+    pub max_open_positions: Option<usize>,
+    /// Most buys + sells within any 60 seconds.
+    pub max_trades_per_minute: Option<u32>,
+    /// Buys stop once the day's (UTC) realized loss reaches this.
+    pub max_daily_loss: Option<u64>,
+    /// When set, only these mints may be bought.
+    pub allowed_mints: Option<HashSet<Pubkey>>,
+    /// Mints that may not be bought.
+    pub denied_mints: HashSet<Pubkey>,
+    /// Largest tip (SOL) of any SWQOS strategy of the trade.
+    pub max_tip_per_trade: Option<f64>,
+}
+
+/// Why a trade was refused.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RiskRejection {
+    MintDenied(Pubkey),
+    MintNotAllowed(Pubkey),
+    TradeSize { amount: u64, max: u64 },
+    MintExposure { mint: Pubkey, committed: u64, amount: u64, max: u64 },
+    OpenPositions { open: usize, max: usize },
+    TradeRate { trades: u32, max: u32 },
+    DailyLoss { loss: u64, max: u64 },
+    Tip { tip: f64, max: f64 },
+}
+
+impl std::fmt::Display for RiskRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MintDenied(mint) => write!(f, "Risk: mint {} is on the deny list", mint),
+            Self::MintNotAllowed(mint) => write!(f, "Risk: mint {} is not on the allow list", mint),
+            Self::TradeSize { amount, max } => {
+                write!(f, "Risk: trade of {} lamports exceeds the per-trade max {}", amount, max)
+            }
+            Self::MintExposure { mint, committed, amount, max } => write!(
+                f,
+                "Risk: {} lamports on {} (already {}) exceeds the per-mint max {}",
+                amount, mint, committed, max
+            ),
+            Self::OpenPositions { open, max } => {
+                write!(f, "Risk: {} open positions, max {}", open, max)
+            }
+            Self::TradeRate { trades, max } => {
+                write!(f, "Risk: {} trades in the last minute, max {}", trades, max)
+            }
+            Self::DailyLoss { loss, max } => {
+                write!(f, "Risk: daily loss of {} lamports reached the max {}", loss, max)
+            }
+            Self::Tip { tip, max } => write!(f, "Risk: tip {} SOL exceeds the max {}", tip, max),
+        }
+    }
+}
+
+impl std::error::Error for RiskRejection {}
+
+#[derive(Default)]
+struct RiskState {
+    /// SOL committed per mint.
+    committed: HashMap<Pubkey, u64>,
+    /// Accepted trades of the last minute.
+    recent: VecDeque<Instant>,
+    /// UTC day `daily_pnl` belongs to.
+    day: u64,
+    /// Realized PnL reported with `record_pnl` today.
+    daily_pnl: i128,
+}
+
+impl RiskState {
+    fn roll_day(&mut self) {
+        let day = unix_now() / SECONDS_PER_DAY;
+        if day != self.day {
+            self.day = day;
+            self.daily_pnl = 0;
+        }
+    }
+
+    /// Trades within the last minute, after dropping older ones.
+    fn trades_last_minute(&mut self, now: Instant) -> u32 {
+        while self.recent.front().is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60))
+        {
+            self.recent.pop_front();
+        }
+        self.recent.len() as u32
+    }
+}
+
+/// `RiskLimits` plus the state they are checked against; share it as `Arc<RiskEngine>`.
+pub struct RiskEngine {
+    limits: RwLock<RiskLimits>,
+    state: Mutex<RiskState>,
+    ledger: Option<Arc<Ledger>>,
+}
+
+impl RiskEngine {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self { limits: RwLock::new(limits), state: Mutex::new(RiskState::default()), ledger: None }
+    }
+
+    /// Also count the ledger's realized SOL PnL of the day towards `max_daily_loss`, and release
+    /// a mint once a sell covers the tokens the ledger holds of it.
+    pub fn with_ledger(mut self, ledger: Arc<Ledger>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    pub fn limits(&self) -> RiskLimits {
+        self.limits.read().clone()
+    }
+
+    /// Replace the limits; applies to the next check.
+    pub fn set_limits(&self, limits: RiskLimits) {
+        *self.limits.write() = limits;
+    }
+
+    /// Change the limits in place, e.g. `engine.update_limits(|l| l.denied_mints.insert(mint))`.
+    pub fn update_limits<R>(&self, f: impl FnOnce(&mut RiskLimits) -> R) -> R {
+        f(&mut self.limits.write())
+    }
+
+    /// SOL committed to `mint`.
+    pub fn committed(&self, mint: &Pubkey) -> u64 {
+        self.state.lock().committed.get(mint).copied().unwrap_or(0)
+    }
+
+    /// Mints with SOL committed.
+    pub fn open_positions(&self) -> usize {
+        self.state.lock().committed.len()
+    }
+
+    /// Forget the SOL committed to `mint`, e.g. after selling it outside the client.
+    pub fn release(&self, mint: &Pubkey) {
+        self.state.lock().committed.remove(mint);
+    }
+
+    /// Report realized PnL (lamports) not seen by the ledger.
+    pub fn record_pnl(&self, lamports: i64) {
+        let mut state = self.state.lock();
+        state.roll_day();
+        state.daily_pnl += lamports as i128;
+    }
+
+    /// Realized PnL of the current UTC day: `record_pnl` reports plus the ledger's SOL trades.
+    pub fn daily_pnl(&self) -> i128 {
+        let from_ledger = self.ledger.as_ref().map_or(0, |ledger| {
+            let day_start = unix_now() / SECONDS_PER_DAY * SECONDS_PER_DAY;
+            ledger.realized_pnl_since(&WSOL_TOKEN_ACCOUNT, day_start)
+        });
+        let mut state = self.state.lock();
+        state.roll_day();
+        state.daily_pnl + from_ledger
+    }
+
+    /// Check a buy of `mint` for `sol_amount` lamports (`None` when not quoted in SOL) with tips
+    /// of up to `tip` SOL. A passing buy is counted and its SOL committed.
+    pub fn check_buy(
+        &self,
+        mint: &Pubkey,
+        sol_amount: Option<u64>,
+        tip: f64,
+    ) -> Result<(), RiskRejection> {
+        let limits = self.limits.read().clone();
+        if limits.denied_mints.contains(mint) {
+            return Err(RiskRejection::MintDenied(*mint));
+        }
+        if limits.allowed_mints.as_ref().is_some_and(|allowed| !allowed.contains(mint)) {
+            return Err(RiskRejection::MintNotAllowed(*mint));
+        }
+        check_tip(&limits, tip)?;
+        if let Some(max) = limits.max_daily_loss {
+            let pnl = self.daily_pnl();
+            if pnl <= -(max as i128) {
+                return Err(RiskRejection::DailyLoss { loss: pnl.unsigned_abs() as u64, max });
+            }
+        }
+
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        check_rate(&limits, &mut state, now)?;
+        if let Some(amount) = sol_amount {
+            if let Some(max) = limits.max_sol_per_trade {
+                if amount > max {
+                    return Err(RiskRejection::TradeSize { amount, max });
+                }
+            }
+            let committed = state.committed.get(mint).copied().unwrap_or(0);
+            if let Some(max) = limits.max_sol_per_mint {
+                if committed + amount > max {
+                    return Err(RiskRejection::MintExposure {
+                        mint: *mint,
+                        committed,
+                        amount,
+                        max,
+                    });
+                }
+            }
+            if let Some(max) = limits.max_open_positions {
+                let open = state.committed.len();
+                if committed == 0 && open >= max {
+                    return Err(RiskRejection::OpenPositions { open, max });
+                }
+            }
+            *state.committed.entry(*mint).or_default() += amount;
+        }
+        state.recent.push_back(now);
+        Ok(())
+    }
+
+    /// Give back SOL committed by a buy that did not land.
+    pub fn refund_buy(&self, mint: &Pubkey, sol_amount: u64) {
+        let mut state = self.state.lock();
+        if let Some(committed) = state.committed.get_mut(mint) {
+            *committed = committed.saturating_sub(sol_amount);
+            if *committed == 0 {
+                state.committed.remove(mint);
+            }
+        }
+    }
+
+    /// Tokens of `mint` held according to the ledger; `None` without a ledger.
+    pub fn tokens_held(&self, mint: &Pubkey) -> Option<u64> {
+        let ledger = self.ledger.as_ref()?;
+        Some(ledger.pnl(mint).iter().map(|pnl| pnl.tokens_held).sum())
+    }
+
+    /// Check a sell of `amount` tokens of `mint` with tips of up to `tip` SOL. A passing sell is
+    /// counted; one that `closes` the token account or sells every token the ledger holds
+    /// releases the mint.
+    pub fn check_sell(
+        &self,
+        mint: &Pubkey,
+        tip: f64,
+        amount: u64,
+        closes: bool,
+    ) -> Result<(), RiskRejection> {
+        let limits = self.limits.read().clone();
+        check_tip(&limits, tip)?;
+        let exits = closes || self.tokens_held(mint).is_some_and(|held| held > 0 && amount >= held);
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        check_rate(&limits, &mut state, now)?;
+        state.recent.push_back(now);
+        if exits {
+            state.committed.remove(mint);
+        }
+        Ok(())
+    }
+}
+
+/// SOL committed to a mint by a passing buy, held until the buy's outcome is known. Dropping it
+/// without `settle` (e.g. an error before sending) gives the SOL back.
+#[must_use = "dropping the commitment refunds the buy"]
+pub(crate) struct BuyCommitment {
+    engine: Arc<RiskEngine>,
+    mint: Pubkey,
+    amount: u64,
+    refund: bool,
+}
+
+impl BuyCommitment {
+    pub(crate) fn new(engine: Arc<RiskEngine>, mint: Pubkey, amount: u64) -> Self {
+        Self { engine, mint, amount, refund: true }
+    }
+
+    /// Keep the SOL committed unless `outcome` proves the buy did not execute.
+    pub(crate) fn settle(
+        mut self,
+        outcome: &anyhow::Result<(bool, Vec<Signature>, Option<anyhow::Error>)>,
+    ) {
+        self.refund = trade_not_executed(outcome);
+    }
+}
+
+impl Drop for BuyCommitment {
+    fn drop(&mut self) {
+        if self.refund {
+            self.engine.refund_buy(&self.mint, self.amount);
+        }
+    }
+}
+
+fn check_tip(limits: &RiskLimits, tip: f64) -> Result<(), RiskRejection> {
+    match limits.max_tip_per_trade {
+        Some(max) if tip > max => Err(RiskRejection::Tip { tip, max }),
+        _ => Ok(()),
+    }
+}
+
+fn check_rate(
+    limits: &RiskLimits,
+    state: &mut RiskState,
+    now: Instant,
+) -> Result<(), RiskRejection> {
+    if let Some(max) = limits.max_trades_per_minute {
+        let trades = state.trades_last_minute(now);
+        if trades >= max {
+            return Err(RiskRejection::TradeRate { trades, max });
+        }
+    }
+    Ok(())
+}
+
+/// Largest tip (SOL) among the `trade_type` strategies of `strategy`.
+pub(crate) fn max_tip(strategy: &GasFeeStrategy, trade_type: TradeType) -> f64 {
+    strategy.get_strategies(trade_type).iter().map(|(_, _, value)| value.tip).fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buys_are_refused_past_each_limit() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let engine = RiskEngine::new(RiskLimits {
+            max_sol_per_trade: Some(100),
+            max_sol_per_mint: Some(150),
+            max_open_positions: Some(2),
+            max_tip_per_trade: Some(0.01),
+            ..Default::default()
+        });
+        assert_eq!(
+            engine.check_buy(&a, Some(101), 0.0),
+            Err(RiskRejection::TradeSize { amount: 101, max: 100 })
+        );
+        assert!(engine.check_buy(&a, Some(100), 0.0).is_ok());
+        assert!(matches!(
+            engine.check_buy(&a, Some(60), 0.0),
+            Err(RiskRejection::MintExposure { committed: 100, .. })
+        ));
+        assert!(engine.check_buy(&b, Some(10), 0.0).is_ok());
+        assert_eq!(
+            engine.check_buy(&c, Some(10), 0.0),
+            Err(RiskRejection::OpenPositions { open: 2, max: 2 })
+        );
+        assert!(matches!(engine.check_buy(&c, Some(10), 0.02), Err(RiskRejection::Tip { .. })));
+
+        engine.refund_buy(&b, 10);
+        assert!(engine.check_sell(&a, 0.0, 0, true).is_ok());
+        assert_eq!(engine.open_positions(), 0);
+
+        engine.update_limits(|l| l.denied_mints.insert(c));
+        assert_eq!(engine.check_buy(&c, Some(10), 0.0), Err(RiskRejection::MintDenied(c)));
+    }
+
+    #[test]
+    fn trade_rate_and_daily_loss() {
+        let mint = Pubkey::new_unique();
+        let engine = RiskEngine::new(RiskLimits {
+            max_trades_per_minute: Some(2),
+            max_daily_loss: Some(1_000),
+            ..Default::default()
+        });
+        assert!(engine.check_buy(&mint, None, 0.0).is_ok());
+        assert!(engine.check_sell(&mint, 0.0, 0, false).is_ok());
+        assert_eq!(
+            engine.check_sell(&mint, 0.0, 0, false),
+            Err(RiskRejection::TradeRate { trades: 2, max: 2 })
+        );
+
+        engine.set_limits(RiskLimits { max_daily_loss: Some(1_000), ..Default::default() });
+        engine.record_pnl(-1_000);
+        assert_eq!(
+            engine.check_buy(&mint, None, 0.0),
+            Err(RiskRejection::DailyLoss { loss: 1_000, max: 1_000 })
+        );
+        assert!(engine.check_sell(&mint, 0.0, 0, true).is_ok());
+    }
+
+    #[test]
+    fn selling_the_ledger_position_releases_the_mint() {
+        let mint = Pubkey::new_unique();
+        let ledger = Arc::new(Ledger::in_memory());
+        ledger
+            .record(crate::trading::ledger::LedgerEntry {
+                timestamp: unix_now(),
+                signature: Signature::new_unique(),
+                side: TradeType::Buy,
+                mint,
+                quote_mint: WSOL_TOKEN_ACCOUNT,
+                dex: "PumpFun".to_string(),
+                token_amount: 1_000,
+                quote_amount: 100,
+                network_fee: 0,
+                tip: 0,
+                swqos: None,
+            })
+            .unwrap();
+        let engine = RiskEngine::new(RiskLimits::default()).with_ledger(ledger);
+        assert!(engine.check_buy(&mint, Some(100), 0.0).is_ok());
+
+        assert!(engine.check_sell(&mint, 0.0, 400, false).is_ok());
+        assert_eq!(engine.committed(&mint), 100);
+        assert!(engine.check_sell(&mint, 0.0, 1_000, false).is_ok());
+        assert_eq!(engine.open_positions(), 0);
+    }
+
+    #[test]
+    fn commitments_refund_only_trades_that_did_not_execute() {
+        use crate::swqos::common::TradeError;
+
+        let mint = Pubkey::new_unique();
+        let engine = Arc::new(RiskEngine::new(RiskLimits::default()));
+        let commit = |amount| {
+            engine.check_buy(&mint, Some(amount), 0.0).unwrap();
+            BuyCommitment::new(engine.clone(), mint, amount)
+        };
+        let submitted = vec![Signature::new_unique()];
+
+        // Dropped before sending, e.g. a quote error.
+        drop(commit(1));
+        assert_eq!(engine.committed(&mint), 0);
+        commit(2).settle(&Err(anyhow::anyhow!("build failed")));
+        assert_eq!(engine.committed(&mint), 0);
+        commit(4).settle(&Ok((false, vec![Signature::default()], None)));
+        assert_eq!(engine.committed(&mint), 0);
+        let on_chain =
+            TradeError { code: 6004, message: "slippage".to_string(), instruction: None };
+        commit(8).settle(&Ok((false, submitted.clone(), Some(on_chain.into()))));
+        assert_eq!(engine.committed(&mint), 0);
+
+        let timeout = anyhow::anyhow!("Transaction confirmation timed out after 15s");
+        commit(16).settle(&Ok((false, submitted.clone(), Some(timeout))));
+        assert_eq!(engine.committed(&mint), 16);
+        commit(32).settle(&Ok((true, submitted, None)));
+        assert_eq!(engine.committed(&mint), 48);
+    }
+}
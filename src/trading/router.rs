@@ -0,0 +1,858 @@
+//! Best-price routing of a mint across DEXes.
+//!
+//! - **Candidates**: every pool of the configured DEX types that pairs `mint` with the requested
+//!   quote mint (see `pool_finder`), limited to pairs the instruction builders can trade.
+//! - **Quote**: each pool is quoted with the matching `utils::calc` module from fresh chain state;
+//!   pools that fail to load or quote are listed in `RouteTable::skipped`.
+//! - **Execute**: `RouteQuote::apply_to_buy` / `apply_to_sell` point trade params at a route;
+//!   `TradingClient::buy_best_route` / `sell_best_route` quote and trade in one call.
+
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use futures::future::join_all;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::common::spl_token_2022::TransferFee;
+use crate::common::SolanaRpcClient;
+use crate::constants::trade::trade::DEFAULT_SLIPPAGE;
+use crate::constants::{
+    TOKEN_PROGRAM, TOKEN_PROGRAM_2022, USD1_TOKEN_ACCOUNT, USDC_TOKEN_ACCOUNT, WSOL_TOKEN_ACCOUNT,
+};
+use crate::instruction::utils::meteora_damm_v2_types::Pool;
+use crate::instruction::utils::{meteora_damm_v2, pumpfun, pumpswap};
+use crate::swqos::common::TradeError;
+use crate::swqos::TradeType;
+use crate::trading::core::params::{
+    transfer_fees_by_rpc, BonkParams, DexParamEnum, MeteoraDammV2Params, PumpFunParams,
+    PumpSwapParams, RaydiumAmmV4Params, RaydiumCpmmParams,
+};
+use crate::trading::factory::DexType;
+use crate::trading::pool_finder::{find_pools_by_mint, PoolCandidate};
+use crate::utils::calc;
+use crate::utils::calc::common::{calculate_with_slippage_sell, transfer_fee_excluded_amount};
+use crate::{TradeBuyParams, TradeSellParams};
+
+/// DEX types a `Router` quotes by default.
+pub const ALL_DEX_TYPES: [DexType; 6] = [
+    DexType::PumpFun,
+    DexType::PumpSwap,
+    DexType::Bonk,
+    DexType::RaydiumCpmm,
+    DexType::RaydiumAmmV4,
+    DexType::MeteoraDammV2,
+];
+
+/// One quoted route: a single pool trading `amount_in` for `amount_out`.
+#[derive(Clone)]
+pub struct RouteQuote {
+    pub dex_type: DexType,
+    pub pool: Pubkey,
+    /// Token paired with the mint (input of a buy, output of a sell).
+    pub quote_mint: Pubkey,
+    pub amount_in: u64,
+    /// Expected output after fees.
+    pub amount_out: u64,
+    /// `amount_out` reduced by the slippage tolerance.
+    pub min_amount_out: u64,
+    /// Shortfall of `amount_out` against the pool's spot price, fees included (basis points).
+    pub price_impact_bps: u64,
+    /// Protocol params loaded while quoting, ready for `TradeBuyParams::extension_params`.
+    pub params: DexParamEnum,
+    pub(crate) curve: Curve,
+}
+
+impl RouteQuote {
+    /// Re-quote this route for `amount_in` against the pool state captured when it was quoted.
+    pub fn requote(&mut self, amount_in: u64, slippage_basis_points: u64) -> Result<(), String> {
+        let (amount_out, min_amount_out) = self.curve.quote(amount_in, slippage_basis_points)?;
+        let spot_out = self.curve.spot_output(amount_in);
+        self.amount_in = amount_in;
+        self.amount_out = amount_out;
+        self.min_amount_out = min_amount_out;
+        self.price_impact_bps = if spot_out > amount_out as u128 {
+            ((spot_out - amount_out as u128) * 10_000 / spot_out) as u64
+        } else {
+            0
+        };
+        Ok(())
+    }
+
+    /// Point a buy at this route (DEX type, protocol params and, for Meteora DAMM v2 which needs
+    /// an explicit minimum, `fixed_output_token_amount` when unset).
+    pub fn apply_to_buy(&self, params: &mut TradeBuyParams) {
+        params.dex_type = self.dex_type;
+        params.extension_params = self.params.clone();
+        if self.dex_type == DexType::MeteoraDammV2 && params.fixed_output_token_amount.is_none() {
+            params.fixed_output_token_amount = Some(self.min_amount_out);
+        }
+    }
+
+    /// Point a sell at this route (see `apply_to_buy`).
+    pub fn apply_to_sell(&self, params: &mut TradeSellParams) {
+        params.dex_type = self.dex_type;
+        params.extension_params = self.params.clone();
+        if self.dex_type == DexType::MeteoraDammV2 && params.fixed_output_token_amount.is_none() {
+            params.fixed_output_token_amount = Some(self.min_amount_out);
+        }
+    }
+}
+
+/// Pool that was found but could not be quoted.
+#[derive(Clone, Debug)]
+pub struct SkippedRoute {
+    pub dex_type: DexType,
+    /// `None` when pool discovery itself failed for this DEX type.
+    pub pool: Option<Pubkey>,
+    pub reason: String,
+}
+
+/// All quoted routes for one trade, best (highest `amount_out`) first.
+#[derive(Clone)]
+pub struct RouteTable {
+    /// `Buy` or `Sell`.
+    pub trade_type: TradeType,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount_in: u64,
+    pub routes: Vec<RouteQuote>,
+    pub skipped: Vec<SkippedRoute>,
+}
+
+impl RouteTable {
+    #[inline]
+    pub fn best(&self) -> Option<&RouteQuote> {
+        self.routes.first()
+    }
+
+    /// How much less than the best route `route` returns, in basis points.
+    pub fn shortfall_bps(&self, route: &RouteQuote) -> u64 {
+        match self.best() {
+            Some(best) if best.amount_out > 0 => {
+                (best.amount_out.saturating_sub(route.amount_out) as u128 * 10_000
+                    / best.amount_out as u128) as u64
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for RouteTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {} with {} of {} ({} routes)",
+            self.trade_type,
+            self.mint,
+            self.amount_in,
+            self.quote_mint,
+            self.routes.len()
+        )?;
+        writeln!(
+            f,
+            "{:<3} {:<14} {:<44} {:>20} {:>20} {:>9} {:>9}",
+            "#", "dex", "pool", "amount_out", "min_amount_out", "impact%", "vs best%"
+        )?;
+        for (i, route) in self.routes.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<3} {:<14} {:<44} {:>20} {:>20} {:>9.2} {:>9.2}",
+                i + 1,
+                format!("{:?}", route.dex_type),
+                route.pool.to_string(),
+                route.amount_out,
+                route.min_amount_out,
+                route.price_impact_bps as f64 / 100.0,
+                self.shortfall_bps(route) as f64 / 100.0,
+            )?;
+        }
+        for skipped in &self.skipped {
+            let pool = skipped.pool.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string());
+            writeln!(f, "skipped {:?} {}: {}", skipped.dex_type, pool, skipped.reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of `TradingClient::buy_best_route` / `sell_best_route`.
+pub struct RoutedTrade {
+    /// Routes compared before trading; the trade used `table.best()`.
+    pub table: RouteTable,
+    pub success: bool,
+    pub signatures: Vec<Signature>,
+    pub error: Option<TradeError>,
+}
+
+/// Quotes a mint on every configured DEX and picks the best route.
+#[derive(Clone)]
+pub struct Router {
+    rpc: Arc<SolanaRpcClient>,
+    dex_types: Vec<DexType>,
+}
+
+impl Router {
+    /// Router over all supported DEX types.
+    pub fn new(rpc: Arc<SolanaRpcClient>) -> Self {
+        Self { rpc, dex_types: ALL_DEX_TYPES.to_vec() }
+    }
+
+    /// Only quote `dex_types`.
+    pub fn with_dex_types(mut self, dex_types: &[DexType]) -> Self {
+        self.dex_types = dex_types.to_vec();
+        self
+    }
+
+    /// Quote buying `mint` with `amount_in` of `quote_mint` (WSOL for SOL).
+    pub async fn quote_buy(
+        &self,
+        mint: &Pubkey,
+        quote_mint: &Pubkey,
+        amount_in: u64,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<RouteTable, anyhow::Error> {
+        self.quote(TradeType::Buy, mint, quote_mint, amount_in, slippage_basis_points).await
+    }
+
+    /// Quote selling `amount_in` of `mint` for `quote_mint` (WSOL for SOL).
+    pub async fn quote_sell(
+        &self,
+        mint: &Pubkey,
+        quote_mint: &Pubkey,
+        amount_in: u64,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<RouteTable, anyhow::Error> {
+        self.quote(TradeType::Sell, mint, quote_mint, amount_in, slippage_basis_points).await
+    }
+
+    async fn quote(
+        &self,
+        trade_type: TradeType,
+        mint: &Pubkey,
+        quote_mint: &Pubkey,
+        amount_in: u64,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<RouteTable, anyhow::Error> {
+        if amount_in == 0 {
+            return Err(anyhow!("Amount cannot be zero"));
+        }
+        let rpc = self.rpc.as_ref();
+        let slippage = slippage_basis_points.unwrap_or(DEFAULT_SLIPPAGE);
+        let mut skipped = Vec::new();
+
+        let found = join_all(
+            self.dex_types.iter().map(|dex_type| find_pools_by_mint(rpc, mint, *dex_type)),
+        )
+        .await;
+        let mut candidates = Vec::new();
+        for (dex_type, result) in self.dex_types.iter().zip(found) {
+            match result {
+                Ok(pools) => candidates.extend(pools.into_iter().filter(|c| {
+                    c.quote_mint == *quote_mint && is_tradable_pair(c.dex_type, quote_mint)
+                })),
+                Err(e) => skipped.push(SkippedRoute {
+                    dex_type: *dex_type,
+                    pool: None,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+
+        let side = Side { trade_type, mint, quote_mint, amount_in, slippage };
+        let quotes = join_all(candidates.iter().map(|c| quote_pool(rpc, c, &side))).await;
+        let mut routes = Vec::with_capacity(quotes.len());
+        for (candidate, result) in candidates.iter().zip(quotes) {
+            match result {
+                Ok(route) => routes.push(route),
+                Err(e) => skipped.push(SkippedRoute {
+                    dex_type: candidate.dex_type,
+                    pool: Some(candidate.pool),
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        if routes.is_empty() {
+            return Err(anyhow!("No route for {} {} against {}", trade_type, mint, quote_mint));
+        }
+        rank_routes(&mut routes);
+        Ok(RouteTable {
+            trade_type,
+            mint: *mint,
+            quote_mint: *quote_mint,
+            amount_in,
+            routes,
+            skipped,
+        })
+    }
+}
+
+/// Best route (highest `amount_out`) first.
+fn rank_routes(routes: &mut [RouteQuote]) {
+    routes.sort_by_key(|r| std::cmp::Reverse(r.amount_out));
+}
+
+/// Quote pairs the instruction builders accept for each DEX.
+fn is_tradable_pair(dex_type: DexType, quote_mint: &Pubkey) -> bool {
+    match dex_type {
+        DexType::PumpFun => *quote_mint == WSOL_TOKEN_ACCOUNT,
+        DexType::Bonk => *quote_mint == WSOL_TOKEN_ACCOUNT || *quote_mint == USD1_TOKEN_ACCOUNT,
+        _ => *quote_mint == WSOL_TOKEN_ACCOUNT || *quote_mint == USDC_TOKEN_ACCOUNT,
+    }
+}
+
+struct Side<'a> {
+    trade_type: TradeType,
+    mint: &'a Pubkey,
+    quote_mint: &'a Pubkey,
+    amount_in: u64,
+    slippage: u64,
+}
+
+impl Side<'_> {
+    #[inline]
+    fn is_buy(&self) -> bool {
+        self.trade_type == TradeType::Buy
+    }
+
+    #[inline]
+    fn input_mint(&self) -> &Pubkey {
+        if self.is_buy() {
+            self.quote_mint
+        } else {
+            self.mint
+        }
+    }
+}
+
+/// `amount_in * output_reserve / input_reserve` (output at the spot price, before fees).
+#[inline]
+fn spot_output(amount_in: u64, input_reserve: u128, output_reserve: u128) -> u128 {
+    if input_reserve == 0 {
+        return 0;
+    }
+    (amount_in as u128).saturating_mul(output_reserve) / input_reserve
+}
+
+/// Token-2022 transfer fees of the input and output mints of a route.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct TransferFees {
+    pub input: Option<TransferFee>,
+    pub output: Option<TransferFee>,
+}
+
+impl TransferFees {
+    /// Fees for a swap from the `(a, b)` side of a pool when `a_in`, else from `b`.
+    fn oriented(a: Option<TransferFee>, b: Option<TransferFee>, a_in: bool) -> Self {
+        if a_in {
+            Self { input: a, output: b }
+        } else {
+            Self { input: b, output: a }
+        }
+    }
+}
+
+/// Pool state captured while quoting, oriented in the trade direction so a route can be
+/// re-quoted at any input amount without another RPC round trip.
+#[derive(Clone)]
+pub(crate) enum Curve {
+    PumpFun {
+        virtual_token: u128,
+        virtual_sol: u128,
+        real_token: u128,
+        creator: Pubkey,
+        is_buy: bool,
+    },
+    PumpSwap {
+        base_reserve: u64,
+        quote_reserve: u64,
+        creator: Pubkey,
+        /// Input is the pool's quote token.
+        quote_in: bool,
+        transfer_fees: TransferFees,
+    },
+    Bonk {
+        virtual_base: u128,
+        virtual_quote: u128,
+        real_base: u128,
+        real_quote: u128,
+        is_buy: bool,
+    },
+    RaydiumCpmm {
+        base_reserve: u64,
+        quote_reserve: u64,
+        is_base_in: bool,
+        transfer_fees: TransferFees,
+    },
+    RaydiumAmmV4 {
+        coin_reserve: u64,
+        pc_reserve: u64,
+        is_base_in: bool,
+    },
+    MeteoraDammV2 {
+        pool: Box<Pool>,
+        a_to_b: bool,
+        transfer_fees: TransferFees,
+    },
+}
+
+impl Curve {
+    /// `(amount_out, min_amount_out)` for `amount_in`.
+    pub(crate) fn quote(&self, amount_in: u64, slippage: u64) -> Result<(u64, u64), String> {
+        match self {
+            Curve::PumpFun { virtual_token, virtual_sol, real_token, creator, is_buy } => {
+                let out = if *is_buy {
+                    calc::pumpfun::get_buy_token_amount_from_sol_amount(
+                        *virtual_token,
+                        *virtual_sol,
+                        *real_token,
+                        *creator,
+                        amount_in,
+                    )
+                } else {
+                    calc::pumpfun::get_sell_sol_amount_from_token_amount(
+                        *virtual_token,
+                        *virtual_sol,
+                        *creator,
+                        amount_in,
+                    )
+                };
+                Ok((out, calculate_with_slippage_sell(out, slippage)))
+            }
+            Curve::PumpSwap { base_reserve, quote_reserve, creator, quote_in, transfer_fees } => {
+                let amount_in =
+                    transfer_fee_excluded_amount(amount_in, transfer_fees.input.as_ref());
+                let received =
+                    |amount| transfer_fee_excluded_amount(amount, transfer_fees.output.as_ref());
+                if *quote_in {
+                    let result = calc::pumpswap::buy_quote_input_internal(
+                        amount_in,
+                        slippage,
+                        *base_reserve,
+                        *quote_reserve,
+                        creator,
+                    )?;
+                    let out = received(result.base);
+                    Ok((out, calculate_with_slippage_sell(out, slippage)))
+                } else {
+                    let result = calc::pumpswap::sell_base_input_internal(
+                        amount_in,
+                        slippage,
+                        *base_reserve,
+                        *quote_reserve,
+                        creator,
+                    )?;
+                    Ok((received(result.ui_quote), received(result.min_quote)))
+                }
+            }
+            Curve::Bonk { virtual_base, virtual_quote, real_base, real_quote, is_buy } => {
+                let quote_with = |slippage: u64| {
+                    let f = if *is_buy {
+                        calc::bonk::get_buy_token_amount_from_sol_amount
+                    } else {
+                        calc::bonk::get_sell_sol_amount_from_token_amount
+                    };
+                    f(
+                        amount_in,
+                        *virtual_base,
+                        *virtual_quote,
+                        *real_base,
+                        *real_quote,
+                        slippage as u128,
+                    )
+                };
+                Ok((quote_with(0), quote_with(slippage)))
+            }
+            Curve::RaydiumCpmm { base_reserve, quote_reserve, is_base_in, transfer_fees } => {
+                let result = calc::raydium_cpmm::compute_swap_amount_with_transfer_fees(
+                    *base_reserve,
+                    *quote_reserve,
+                    *is_base_in,
+                    amount_in,
+                    slippage,
+                    transfer_fees.input.as_ref(),
+                    transfer_fees.output.as_ref(),
+                );
+                Ok((result.amount_out, result.min_amount_out))
+            }
+            Curve::RaydiumAmmV4 { coin_reserve, pc_reserve, is_base_in } => {
+                let result = calc::raydium_amm_v4::compute_swap_amount(
+                    *coin_reserve,
+                    *pc_reserve,
+                    *is_base_in,
+                    amount_in,
+                    slippage,
+                );
+                Ok((result.amount_out, result.min_amount_out))
+            }
+            Curve::MeteoraDammV2 { pool, a_to_b, transfer_fees } => {
+                let result = calc::meteora_damm_v2::compute_swap_amount_with_transfer_fees(
+                    pool,
+                    *a_to_b,
+                    amount_in,
+                    slippage,
+                    transfer_fees.input.as_ref(),
+                    transfer_fees.output.as_ref(),
+                )?;
+                Ok((result.amount_out, result.min_amount_out))
+            }
+        }
+    }
+
+    /// Output of `amount_in` at the spot price, before fees.
+    pub(crate) fn spot_output(&self, amount_in: u64) -> u128 {
+        let (input_reserve, output_reserve) = match self {
+            Curve::PumpFun { virtual_token, virtual_sol, is_buy, .. } => {
+                if *is_buy {
+                    (*virtual_sol, *virtual_token)
+                } else {
+                    (*virtual_token, *virtual_sol)
+                }
+            }
+            Curve::PumpSwap { base_reserve, quote_reserve, quote_in, .. } => {
+                let (base, quote) = (*base_reserve as u128, *quote_reserve as u128);
+                if *quote_in {
+                    (quote, base)
+                } else {
+                    (base, quote)
+                }
+            }
+            Curve::Bonk { virtual_base, virtual_quote, real_base, real_quote, is_buy } => {
+                let base = virtual_base.saturating_sub(*real_base);
+                let quote = virtual_quote.saturating_add(*real_quote);
+                if *is_buy {
+                    (quote, base)
+                } else {
+                    (base, quote)
+                }
+            }
+            Curve::RaydiumCpmm { base_reserve, quote_reserve, is_base_in, .. }
+            | Curve::RaydiumAmmV4 {
+                coin_reserve: base_reserve,
+                pc_reserve: quote_reserve,
+                is_base_in,
+            } => {
+                let (base, quote) = (*base_reserve as u128, *quote_reserve as u128);
+                if *is_base_in {
+                    (base, quote)
+                } else {
+                    (quote, base)
+                }
+            }
+            Curve::MeteoraDammV2 { pool, a_to_b, .. } => {
+                let (reserve_a, reserve_b) = calc::meteora_damm_v2::virtual_reserves(pool);
+                if *a_to_b {
+                    (reserve_a, reserve_b)
+                } else {
+                    (reserve_b, reserve_a)
+                }
+            }
+        };
+        spot_output(amount_in, input_reserve, output_reserve)
+    }
+
+    /// PumpFun / PumpSwap buys trade an exact output with a maximum input; their builders take
+    /// `fixed_output_amount` as that output rather than as a minimum.
+    #[inline]
+    pub(crate) fn exact_output(&self) -> bool {
+        matches!(self, Curve::PumpFun { is_buy: true, .. } | Curve::PumpSwap { quote_in: true, .. })
+    }
+}
+
+async fn quote_pool(
+    rpc: &SolanaRpcClient,
+    candidate: &PoolCandidate,
+    side: &Side<'_>,
+) -> Result<RouteQuote, anyhow::Error> {
+    let (params, curve) = load_curve(rpc, candidate, side).await?;
+    let mut route = RouteQuote {
+        dex_type: candidate.dex_type,
+        pool: candidate.pool,
+        quote_mint: *side.quote_mint,
+        amount_in: 0,
+        amount_out: 0,
+        min_amount_out: 0,
+        price_impact_bps: 0,
+        params,
+        curve,
+    };
+    route.requote(side.amount_in, side.slippage).map_err(|e| anyhow!(e))?;
+    Ok(route)
+}
+
+/// Fetch the protocol params of `candidate` and the curve to quote it with.
+async fn load_curve(
+    rpc: &SolanaRpcClient,
+    candidate: &PoolCandidate,
+    side: &Side<'_>,
+) -> Result<(DexParamEnum, Curve), anyhow::Error> {
+    let input_mint = side.input_mint();
+    match candidate.dex_type {
+        DexType::PumpFun => {
+            let params = PumpFunParams::from_mint_by_rpc(rpc, side.mint).await?;
+            let curve = Curve::PumpFun {
+                virtual_token: params.bonding_curve.virtual_token_reserves as u128,
+                virtual_sol: params.bonding_curve.virtual_sol_reserves as u128,
+                real_token: params.bonding_curve.real_token_reserves as u128,
+                creator: pumpfun::get_creator(&params.creator_vault),
+                is_buy: side.is_buy(),
+            };
+            Ok((DexParamEnum::PumpFun(params), curve))
+        }
+        DexType::PumpSwap => {
+            let params = PumpSwapParams::from_pool_address_by_rpc(rpc, &candidate.pool).await?;
+            let creator = if params.coin_creator_vault_authority
+                != pumpswap::accounts::DEFAULT_COIN_CREATOR_VAULT_AUTHORITY
+            {
+                params.coin_creator_vault_authority
+            } else {
+                Pubkey::default()
+            };
+            let quote_in = params.quote_mint == *input_mint;
+            let curve = Curve::PumpSwap {
+                base_reserve: params.pool_base_token_reserves,
+                quote_reserve: params.pool_quote_token_reserves,
+                creator,
+                quote_in,
+                transfer_fees: TransferFees::oriented(
+                    params.base_transfer_fee,
+                    params.quote_transfer_fee,
+                    !quote_in,
+                ),
+            };
+            Ok((DexParamEnum::PumpSwap(params), curve))
+        }
+        DexType::Bonk => {
+            let params = BonkParams::from_mint_by_rpc(
+                rpc,
+                side.mint,
+                *side.quote_mint == USD1_TOKEN_ACCOUNT,
+            )
+            .await?;
+            let curve = Curve::Bonk {
+                virtual_base: params.virtual_base,
+                virtual_quote: params.virtual_quote,
+                real_base: params.real_base,
+                real_quote: params.real_quote,
+                is_buy: side.is_buy(),
+            };
+            Ok((DexParamEnum::Bonk(params), curve))
+        }
+        DexType::RaydiumCpmm => {
+            let params = RaydiumCpmmParams::from_pool_address_by_rpc(rpc, &candidate.pool).await?;
+            let is_base_in = params.base_mint == *input_mint;
+            let curve = Curve::RaydiumCpmm {
+                base_reserve: params.base_reserve,
+                quote_reserve: params.quote_reserve,
+                is_base_in,
+                transfer_fees: TransferFees::oriented(
+                    params.base_transfer_fee,
+                    params.quote_transfer_fee,
+                    is_base_in,
+                ),
+            };
+            Ok((DexParamEnum::RaydiumCpmm(params), curve))
+        }
+        DexType::RaydiumAmmV4 => {
+            let params = RaydiumAmmV4Params::from_amm_address_by_rpc(rpc, candidate.pool).await?;
+            let curve = Curve::RaydiumAmmV4 {
+                coin_reserve: params.coin_reserve,
+                pc_reserve: params.pc_reserve,
+                is_base_in: params.coin_mint == *input_mint,
+            };
+            Ok((DexParamEnum::RaydiumAmmV4(params), curve))
+        }
+        DexType::MeteoraDammV2 => {
+            let pool = meteora_damm_v2::fetch_pool(rpc, &candidate.pool).await?;
+            // token_*_flag: 0 = SPL Token, 1 = Token-2022
+            let token_program =
+                |flag: u8| if flag == 1 { TOKEN_PROGRAM_2022 } else { TOKEN_PROGRAM };
+            let (token_a_program, token_b_program) =
+                (token_program(pool.token_a_flag), token_program(pool.token_b_flag));
+            let [token_a_transfer_fee, token_b_transfer_fee] = transfer_fees_by_rpc(
+                rpc,
+                [(pool.token_a_mint, token_a_program), (pool.token_b_mint, token_b_program)],
+            )
+            .await?;
+            let params = MeteoraDammV2Params::new(
+                candidate.pool,
+                pool.token_a_vault,
+                pool.token_b_vault,
+                pool.token_a_mint,
+                pool.token_b_mint,
+                token_a_program,
+                token_b_program,
+            )
+            .with_transfer_fees(token_a_transfer_fee, token_b_transfer_fee);
+            let a_to_b = pool.token_a_mint == *input_mint;
+            let transfer_fees = TransferFees::oriented(
+                params.token_a_transfer_fee,
+                params.token_b_transfer_fee,
+                a_to_b,
+            );
+            let curve = Curve::MeteoraDammV2 { pool: Box::new(pool), a_to_b, transfer_fees };
+            Ok((DexParamEnum::MeteoraDammV2(params), curve))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::GasFeeStrategy;
+    use crate::TradeTokenType;
+
+    fn route(dex_type: DexType, curve: Curve, amount_in: u64, slippage: u64) -> RouteQuote {
+        let mut route = RouteQuote {
+            dex_type,
+            pool: Pubkey::new_unique(),
+            quote_mint: WSOL_TOKEN_ACCOUNT,
+            amount_in: 0,
+            amount_out: 0,
+            min_amount_out: 0,
+            price_impact_bps: 0,
+            params: DexParamEnum::Bonk(BonkParams::default()),
+            curve,
+        };
+        route.requote(amount_in, slippage).unwrap();
+        route
+    }
+
+    fn amm_v4(reserve: u64) -> Curve {
+        Curve::RaydiumAmmV4 { coin_reserve: reserve, pc_reserve: reserve, is_base_in: false }
+    }
+
+    fn meteora() -> Curve {
+        // sqrt_price = 1.0 (Q64.64), 1e9 virtual reserves on each side.
+        Curve::MeteoraDammV2 {
+            pool: Box::new(Pool {
+                liquidity: 1_000_000_000u128 << 64,
+                sqrt_price: 1u128 << 64,
+                sqrt_min_price: 1u128 << 32,
+                sqrt_max_price: 1u128 << 96,
+                ..Default::default()
+            }),
+            a_to_b: true,
+            transfer_fees: TransferFees::default(),
+        }
+    }
+
+    fn buy_params() -> TradeBuyParams {
+        TradeBuyParams {
+            dex_type: DexType::PumpFun,
+            input_token_type: TradeTokenType::SOL,
+            mint: Pubkey::new_unique(),
+            input_token_amount: 1_000_000,
+            slippage_basis_points: Some(100),
+            recent_blockhash: None,
+            extension_params: DexParamEnum::Bonk(BonkParams::default()),
+            address_lookup_table_account: None,
+            wait_transaction_confirmed: true,
+            create_input_token_ata: true,
+            close_input_token_ata: true,
+            create_mint_ata: true,
+            durable_nonce: None,
+            fixed_output_token_amount: None,
+            gas_fee_strategy: GasFeeStrategy::new(),
+            simulate: false,
+            use_exact_sol_amount: None,
+            grpc_recv_us: None,
+        }
+    }
+
+    fn sell_params() -> TradeSellParams {
+        TradeSellParams {
+            dex_type: DexType::PumpFun,
+            output_token_type: TradeTokenType::SOL,
+            mint: Pubkey::new_unique(),
+            input_token_amount: 1_000_000,
+            slippage_basis_points: Some(100),
+            recent_blockhash: None,
+            with_tip: true,
+            extension_params: DexParamEnum::Bonk(BonkParams::default()),
+            address_lookup_table_account: None,
+            wait_transaction_confirmed: true,
+            create_output_token_ata: true,
+            close_output_token_ata: true,
+            close_mint_token_ata: false,
+            durable_nonce: None,
+            fixed_output_token_amount: None,
+            gas_fee_strategy: GasFeeStrategy::new(),
+            simulate: false,
+            grpc_recv_us: None,
+        }
+    }
+
+    #[test]
+    fn deepest_pool_is_the_best_route() {
+        let amount_in = 10_000_000;
+        let mut routes = vec![
+            route(DexType::RaydiumAmmV4, amm_v4(1_000_000_000), amount_in, 100),
+            route(DexType::RaydiumAmmV4, amm_v4(10_000_000_000), amount_in, 100),
+            route(DexType::RaydiumAmmV4, amm_v4(100_000_000), amount_in, 100),
+        ];
+        let deepest = routes[1].pool;
+        rank_routes(&mut routes);
+        let table = RouteTable {
+            trade_type: TradeType::Buy,
+            mint: Pubkey::new_unique(),
+            quote_mint: WSOL_TOKEN_ACCOUNT,
+            amount_in,
+            routes,
+            skipped: vec![],
+        };
+
+        let best = table.best().unwrap();
+        assert_eq!(best.pool, deepest);
+        assert!(table.routes.windows(2).all(|w| w[0].amount_out >= w[1].amount_out));
+        assert_eq!(table.shortfall_bps(best), 0);
+        assert!(table.shortfall_bps(&table.routes[2]) > 0);
+        assert!(best.price_impact_bps < table.routes[2].price_impact_bps);
+    }
+
+    #[test]
+    fn requote_applies_slippage_to_min_amount_out() {
+        let mut route = route(DexType::RaydiumAmmV4, amm_v4(1_000_000_000), 1_000_000, 0);
+        assert_eq!(route.min_amount_out, route.amount_out);
+
+        route.requote(2_000_000, 500).unwrap();
+        assert_eq!(route.amount_in, 2_000_000);
+        let expected = (route.amount_out as f64 * 0.95) as u64;
+        assert!(route.min_amount_out.abs_diff(expected) <= 1);
+    }
+
+    #[test]
+    fn meteora_routes_fix_the_minimum_output_when_unset() {
+        let route = route(DexType::MeteoraDammV2, meteora(), 1_000_000, 100);
+        assert!(route.min_amount_out > 0 && route.min_amount_out < route.amount_out);
+
+        let mut buy = buy_params();
+        route.apply_to_buy(&mut buy);
+        assert_eq!(buy.dex_type, DexType::MeteoraDammV2);
+        assert_eq!(buy.fixed_output_token_amount, Some(route.min_amount_out));
+
+        let mut sell = sell_params();
+        route.apply_to_sell(&mut sell);
+        assert_eq!(sell.dex_type, DexType::MeteoraDammV2);
+        assert_eq!(sell.fixed_output_token_amount, Some(route.min_amount_out));
+
+        let mut pinned = buy_params();
+        pinned.fixed_output_token_amount = Some(42);
+        route.apply_to_buy(&mut pinned);
+        assert_eq!(pinned.fixed_output_token_amount, Some(42));
+    }
+
+    #[test]
+    fn other_routes_leave_the_minimum_output_to_the_builder() {
+        let route = route(DexType::RaydiumAmmV4, amm_v4(1_000_000_000), 1_000_000, 100);
+        let mut buy = buy_params();
+        route.apply_to_buy(&mut buy);
+        assert_eq!(buy.dex_type, DexType::RaydiumAmmV4);
+        assert!(buy.fixed_output_token_amount.is_none());
+
+        let mut sell = sell_params();
+        route.apply_to_sell(&mut sell);
+        assert_eq!(sell.dex_type, DexType::RaydiumAmmV4);
+        assert!(sell.fixed_output_token_amount.is_none());
+    }
+}
@@ -0,0 +1,504 @@
+//! Split-order execution: one order spread over several pools of the same mint.
+//!
+//! - **Optimize**: the input is handed out in `steps` chunks, each to the route whose next chunk
+//!   returns the most, which equalizes marginal prices across the pools.
+//! - **Compose**: token accounts are created (and SOL wrapped) once before the legs, then every
+//!   leg is built by its DEX's instruction builder with account creation off; only the last leg
+//!   closes token accounts.
+//! - **Limits**: legs are dropped (and the split re-optimized) until the transaction fits one
+//!   packet and the estimated compute units fit `SplitConfig::max_compute_units`.
+//! - **Aggregate min-out**: every leg keeps its own slippage minimum, so the transaction fails
+//!   unless the total reaches the plan's `min_amount_out`; that total is checked against
+//!   `SplitConfig::min_amount_out` before sending.
+
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+
+use crate::constants::{SOL_TOKEN_ACCOUNT, TOKEN_PROGRAM, WSOL_TOKEN_ACCOUNT};
+use crate::swqos::common::TradeError;
+use crate::swqos::TradeType;
+use crate::trading::common::transaction_builder::{
+    estimate_transaction_size, MAX_TRANSACTION_SIZE,
+};
+use crate::trading::core::executor::GenericTradeExecutor;
+use crate::trading::core::traits::{InstructionBuilder, TradeExecutor};
+use crate::trading::factory::DexType;
+use crate::trading::router::{RouteQuote, RouteTable};
+use crate::trading::{SwapParams, TradeFactory};
+
+/// Chunks the input is divided into when optimizing.
+pub const DEFAULT_SPLIT_STEPS: u32 = 50;
+/// Compute unit cap of a transaction.
+pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+/// Compute units for ATA creation and WSOL wrap / unwrap around the swaps.
+pub(crate) const BASE_COMPUTE_UNITS: u32 = 40_000;
+
+/// Rough compute units of one swap leg (kept on the high side).
+pub(crate) fn leg_compute_units(dex_type: DexType) -> u32 {
+    match dex_type {
+        DexType::PumpFun => 100_000,
+        DexType::PumpSwap => 160_000,
+        DexType::Bonk => 130_000,
+        DexType::RaydiumCpmm => 100_000,
+        DexType::RaydiumAmmV4 => 80_000,
+        DexType::MeteoraDammV2 => 120_000,
+    }
+}
+
+/// `true` for native SOL and WSOL, which trade through the payer's WSOL ATA.
+#[inline]
+pub(crate) fn is_sol(mint: &Pubkey) -> bool {
+    *mint == SOL_TOKEN_ACCOUNT || *mint == WSOL_TOKEN_ACCOUNT
+}
+
+/// Create the payer's `mint` token account (seed-derived with `open_seed_optimize`, except WSOL).
+///
+/// Seed-derived accounts are created with a plain `createAccountWithSeed`, which fails when the
+/// account exists, so a composed transaction must create each account exactly once.
+pub(crate) fn create_token_account(
+    params: &SwapParams,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Vec<Instruction> {
+    let mint = if *mint == SOL_TOKEN_ACCOUNT { &WSOL_TOKEN_ACCOUNT } else { mint };
+    crate::common::fast_fn::create_associated_token_account_idempotent_fast_use_seed(
+        &params.payer.pubkey(),
+        &params.payer.pubkey(),
+        mint,
+        token_program,
+        params.open_seed_optimize,
+    )
+}
+
+/// Split-order settings.
+#[derive(Clone, Debug)]
+pub struct SplitConfig {
+    /// Maximum pools in one transaction.
+    pub max_legs: usize,
+    /// Chunks the input is divided into; more steps give a finer split.
+    pub steps: u32,
+    /// Estimated compute units the legs may use in total.
+    pub max_compute_units: u32,
+    /// Aggregate minimum output; the trade is rejected before sending if the plan's summed leg
+    /// minimums fall below it.
+    pub min_amount_out: Option<u64>,
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self {
+            max_legs: 3,
+            steps: DEFAULT_SPLIT_STEPS,
+            max_compute_units: MAX_COMPUTE_UNITS,
+            min_amount_out: None,
+        }
+    }
+}
+
+/// Input allocated across pools; each leg is a `RouteQuote` re-quoted at its share.
+#[derive(Clone)]
+pub struct SplitPlan {
+    /// `Buy` or `Sell`.
+    pub trade_type: TradeType,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount_in: u64,
+    pub legs: Vec<RouteQuote>,
+    /// Sum of the legs' expected outputs.
+    pub amount_out: u64,
+    /// Sum of the legs' minimum outputs.
+    pub min_amount_out: u64,
+    /// Estimated compute units of the composed transaction.
+    pub compute_units: u32,
+}
+
+impl fmt::Display for SplitPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {} with {} of {}: {} legs, out {} (min {}), ~{} CU",
+            self.trade_type,
+            self.mint,
+            self.amount_in,
+            self.quote_mint,
+            self.legs.len(),
+            self.amount_out,
+            self.min_amount_out,
+            self.compute_units
+        )?;
+        for leg in &self.legs {
+            writeln!(
+                f,
+                "  {:<14} {:<44} in {:>20} ({:>6.2}%) out {:>20} impact {:>6.2}%",
+                format!("{:?}", leg.dex_type),
+                leg.pool.to_string(),
+                leg.amount_in,
+                leg.amount_in as f64 * 100.0 / self.amount_in.max(1) as f64,
+                leg.amount_out,
+                leg.price_impact_bps as f64 / 100.0,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of `TradingClient::buy_split` / `sell_split`.
+pub struct SplitTrade {
+    pub plan: SplitPlan,
+    /// Routes the plan was optimized from.
+    pub table: RouteTable,
+    pub success: bool,
+    pub signatures: Vec<Signature>,
+    pub error: Option<TradeError>,
+}
+
+/// Split `table.amount_in` over at most `max_legs` of the best routes in `table`.
+pub fn optimize_split(
+    table: &RouteTable,
+    max_legs: usize,
+    steps: u32,
+    slippage_basis_points: u64,
+) -> Result<SplitPlan, anyhow::Error> {
+    let mut legs: Vec<RouteQuote> = table.routes.iter().take(max_legs.max(1)).cloned().collect();
+    if legs.is_empty() {
+        return Err(anyhow!("No route to split {} over", table.mint));
+    }
+    let chunk = (table.amount_in / steps.max(1) as u64).max(1);
+    let mut allocated = vec![0u64; legs.len()];
+    let mut outputs = vec![0u64; legs.len()];
+    let mut remaining = table.amount_in;
+    while remaining > 0 {
+        let size = chunk.min(remaining);
+        let mut best: Option<(usize, u64)> = None;
+        for (i, leg) in legs.iter().enumerate() {
+            let Ok((out, _)) = leg.curve.quote(allocated[i] + size, 0) else { continue };
+            let gain = out.saturating_sub(outputs[i]);
+            if best.is_none_or(|(_, best_gain)| gain > best_gain) {
+                best = Some((i, gain));
+            }
+        }
+        let (i, _) = best.ok_or_else(|| {
+            anyhow!("Pools cannot absorb {} of {}", table.amount_in, table.quote_mint)
+        })?;
+        allocated[i] += size;
+        outputs[i] = legs[i].curve.quote(allocated[i], 0).map(|(out, _)| out).unwrap_or(0);
+        remaining -= size;
+    }
+
+    let mut planned = Vec::with_capacity(legs.len());
+    for (mut leg, amount) in legs.drain(..).zip(allocated) {
+        if amount == 0 {
+            continue;
+        }
+        leg.requote(amount, slippage_basis_points).map_err(|e| anyhow!(e))?;
+        planned.push(leg);
+    }
+    // PumpFun trades native SOL and never closes the WSOL account, so it must not be the last
+    // leg (the one that closes accounts); the rest go largest first.
+    planned.sort_by_key(|leg| (leg.dex_type != DexType::PumpFun, std::cmp::Reverse(leg.amount_in)));
+
+    Ok(SplitPlan {
+        trade_type: table.trade_type,
+        mint: table.mint,
+        quote_mint: table.quote_mint,
+        amount_in: table.amount_in,
+        amount_out: planned.iter().map(|leg| leg.amount_out).sum(),
+        min_amount_out: planned.iter().map(|leg| leg.min_amount_out).sum(),
+        compute_units: BASE_COMPUTE_UNITS
+            + planned.iter().map(|leg| leg_compute_units(leg.dex_type)).sum::<u32>(),
+        legs: planned,
+    })
+}
+
+/// Builds every leg of a split with its DEX's builder into one instruction list.
+pub struct SplitInstructionBuilder {
+    legs: Vec<RouteQuote>,
+}
+
+impl SplitInstructionBuilder {
+    pub fn new(legs: Vec<RouteQuote>) -> Self {
+        Self { legs }
+    }
+
+    /// Token accounts every leg shares: the SOL input of a buy is wrapped for all non-PumpFun
+    /// legs at once, and the output account is created once.
+    fn setup(&self, params: &SwapParams, is_buy: bool) -> anyhow::Result<Vec<Instruction>> {
+        let wrapped: u64 = self
+            .legs
+            .iter()
+            .filter(|leg| leg.dex_type != DexType::PumpFun)
+            .map(|leg| leg.amount_in)
+            .sum();
+        let mut instructions = Vec::new();
+        if is_buy {
+            if params.create_input_mint_ata && is_sol(&params.input_mint) && wrapped > 0 {
+                instructions
+                    .extend(crate::trading::common::handle_wsol(&params.payer.pubkey(), wrapped));
+            }
+            if params.create_output_mint_ata {
+                let token_program = self
+                    .legs
+                    .iter()
+                    .find_map(|leg| leg.params.token_program_of(&params.output_mint))
+                    .ok_or_else(|| anyhow!("No token program for {}", params.output_mint))?;
+                instructions.extend(create_token_account(
+                    params,
+                    &params.output_mint,
+                    &token_program,
+                ));
+            }
+        } else if params.create_output_mint_ata && (wrapped > 0 || !is_sol(&params.output_mint)) {
+            instructions.extend(create_token_account(params, &params.output_mint, &TOKEN_PROGRAM));
+        }
+        Ok(instructions)
+    }
+
+    async fn build(&self, params: &SwapParams, is_buy: bool) -> anyhow::Result<Vec<Instruction>> {
+        let mut instructions = self.setup(params, is_buy)?;
+        for (i, leg) in self.legs.iter().enumerate() {
+            let mut leg_params = params.clone();
+            leg_params.protocol_params = leg.params.clone();
+            leg_params.input_amount = Some(leg.amount_in);
+            leg_params.fixed_output_amount =
+                (leg.dex_type == DexType::MeteoraDammV2).then_some(leg.min_amount_out);
+            // Legs spend exactly their share, which is what `setup` wrapped.
+            leg_params.use_exact_sol_amount = Some(true);
+            leg_params.create_input_mint_ata = false;
+            leg_params.create_output_mint_ata = false;
+            if i + 1 < self.legs.len() {
+                leg_params.close_input_mint_ata = false;
+                leg_params.close_output_mint_ata = false;
+            }
+            let builder = TradeFactory::create_instruction_builder(leg.dex_type);
+            let leg_instructions = if is_buy {
+                builder.build_buy_instructions(&leg_params).await?
+            } else {
+                builder.build_sell_instructions(&leg_params).await?
+            };
+            instructions.extend(leg_instructions);
+        }
+        Ok(instructions)
+    }
+}
+
+#[async_trait::async_trait]
+impl InstructionBuilder for SplitInstructionBuilder {
+    async fn build_buy_instructions(
+        &self,
+        params: &SwapParams,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        self.build(params, true).await
+    }
+
+    async fn build_sell_instructions(
+        &self,
+        params: &SwapParams,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        self.build(params, false).await
+    }
+}
+
+/// Largest split of `table` (at most `config.max_legs` legs) whose transaction fits one packet
+/// and `config.max_compute_units`.
+pub async fn plan_within_limits(
+    table: &RouteTable,
+    config: &SplitConfig,
+    params: &SwapParams,
+) -> Result<SplitPlan, anyhow::Error> {
+    let is_buy = table.trade_type == TradeType::Buy;
+    let slippage =
+        params.slippage_basis_points.unwrap_or(crate::constants::trade::trade::DEFAULT_SLIPPAGE);
+    let mut rejected = Vec::new();
+    for max_legs in (1..=config.max_legs.max(1).min(table.routes.len())).rev() {
+        let plan = optimize_split(table, max_legs, config.steps, slippage)?;
+        if plan.compute_units > config.max_compute_units && plan.legs.len() > 1 {
+            rejected.push(format!("{} legs: ~{} CU", plan.legs.len(), plan.compute_units));
+            continue;
+        }
+        let instructions =
+            SplitInstructionBuilder::new(plan.legs.clone()).build(params, is_buy).await?;
+        let size = estimate_transaction_size(
+            &params.payer.pubkey(),
+            &instructions,
+            params.address_lookup_table_account.as_ref(),
+            is_buy || params.with_tip,
+            params.durable_nonce.as_ref(),
+        );
+        if size <= MAX_TRANSACTION_SIZE {
+            return Ok(plan);
+        }
+        rejected.push(format!("{} legs: {} bytes", plan.legs.len(), size));
+    }
+    Err(anyhow!("No split of {} fits one transaction ({})", table.mint, rejected.join(", ")))
+}
+
+/// Send `plan` as one transaction through the regular executor (SWQOS fan-out, confirmation).
+/// The gas fee strategy's compute unit limit is raised to the plan's estimate when lower.
+pub async fn execute_plan(
+    plan: &SplitPlan,
+    mut params: SwapParams,
+) -> Result<(bool, Vec<Signature>, Option<anyhow::Error>), anyhow::Error> {
+    if let Some(best) = plan.legs.first() {
+        params.protocol_params = best.params.clone();
+    }
+    params.gas_fee_strategy =
+        params.gas_fee_strategy.with_min_cu_limit(plan.trade_type, plan.compute_units);
+    let executor = GenericTradeExecutor::new(
+        Arc::new(SplitInstructionBuilder::new(plan.legs.clone())),
+        "Split",
+    );
+    executor.swap(params).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trading::core::params::{BonkParams, DexParamEnum, RaydiumAmmV4Params};
+    use crate::trading::router::Curve;
+
+    fn cpmm_route(reserve: u64) -> RouteQuote {
+        RouteQuote {
+            dex_type: DexType::RaydiumCpmm,
+            pool: Pubkey::new_unique(),
+            quote_mint: crate::constants::WSOL_TOKEN_ACCOUNT,
+            amount_in: 0,
+            amount_out: 0,
+            min_amount_out: 0,
+            price_impact_bps: 0,
+            params: DexParamEnum::Bonk(BonkParams::default()),
+            curve: Curve::RaydiumCpmm {
+                base_reserve: reserve,
+                quote_reserve: reserve,
+                is_base_in: true,
+                transfer_fees: Default::default(),
+            },
+        }
+    }
+
+    fn table(routes: Vec<RouteQuote>, amount_in: u64) -> RouteTable {
+        RouteTable {
+            trade_type: TradeType::Buy,
+            mint: Pubkey::new_unique(),
+            quote_mint: crate::constants::WSOL_TOKEN_ACCOUNT,
+            amount_in,
+            routes,
+            skipped: vec![],
+        }
+    }
+
+    #[test]
+    fn split_follows_pool_depth() {
+        let amount_in = 100_000_000;
+        let table = table(vec![cpmm_route(3_000_000_000), cpmm_route(1_000_000_000)], amount_in);
+        let plan = optimize_split(&table, 2, 100, 100).unwrap();
+        assert_eq!(plan.legs.len(), 2);
+        assert_eq!(plan.legs.iter().map(|l| l.amount_in).sum::<u64>(), amount_in);
+        // Equal marginal price on x*y=k pools: shares proportional to depth (3:1).
+        let deep_share = plan.legs[0].amount_in as f64 / amount_in as f64;
+        assert!((deep_share - 0.75).abs() < 0.02, "deep pool share {}", deep_share);
+
+        let mut single = table.routes[0].clone();
+        single.requote(amount_in, 100).unwrap();
+        assert!(plan.amount_out > single.amount_out);
+        assert_eq!(plan.min_amount_out, plan.legs.iter().map(|l| l.min_amount_out).sum::<u64>());
+    }
+
+    #[test]
+    fn small_order_stays_in_one_pool() {
+        let table = table(vec![cpmm_route(1_000_000_000_000), cpmm_route(1_000_000)], 1_000);
+        let plan = optimize_split(&table, 2, 10, 0).unwrap();
+        assert_eq!(plan.legs.len(), 1);
+        assert_eq!(plan.legs[0].amount_in, 1_000);
+    }
+
+    fn amm_v4_leg(mint: Pubkey, amount_in: u64) -> RouteQuote {
+        let params = RaydiumAmmV4Params::new(
+            Pubkey::new_unique(),
+            mint,
+            WSOL_TOKEN_ACCOUNT,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1_000_000_000_000,
+            1_000_000_000_000,
+        );
+        let mut leg = cpmm_route(1_000_000_000_000);
+        leg.dex_type = DexType::RaydiumAmmV4;
+        leg.params = DexParamEnum::RaydiumAmmV4(params);
+        leg.curve = Curve::RaydiumAmmV4 {
+            coin_reserve: 1_000_000_000_000,
+            pc_reserve: 1_000_000_000_000,
+            is_base_in: false,
+        };
+        leg.requote(amount_in, 100).unwrap();
+        leg
+    }
+
+    /// System program instructions (`createAccountWithSeed`) that create `account`.
+    fn seed_creates(instructions: &[Instruction], account: &Pubkey) -> usize {
+        instructions
+            .iter()
+            .filter(|ix| {
+                ix.program_id == crate::constants::SYSTEM_PROGRAM
+                    && ix.accounts.iter().any(|meta| meta.pubkey == *account)
+            })
+            .count()
+    }
+
+    #[tokio::test]
+    async fn seed_optimized_split_buy_creates_the_output_account_once() {
+        crate::common::seed::set_default_rents();
+        let mint = Pubkey::new_unique();
+        let legs = vec![amm_v4_leg(mint, 600_000), amm_v4_leg(mint, 400_000)];
+        let params =
+            SwapParams::for_test(TradeType::Buy, SOL_TOKEN_ACCOUNT, mint, legs[0].params.clone());
+        assert!(params.open_seed_optimize);
+
+        let instructions = SplitInstructionBuilder::new(legs).build(&params, true).await.unwrap();
+        let output_account =
+            crate::common::fast_fn::get_associated_token_address_with_program_id_fast_use_seed(
+                &params.payer.pubkey(),
+                &mint,
+                &TOKEN_PROGRAM,
+                true,
+            );
+        assert_eq!(seed_creates(&instructions, &output_account), 1);
+
+        // SOL is wrapped once for both legs and unwrapped by the last one.
+        let wraps: Vec<u64> = instructions
+            .iter()
+            .filter(|ix| ix.program_id == crate::constants::SYSTEM_PROGRAM && ix.data.len() == 12)
+            .map(|ix| u64::from_le_bytes(ix.data[4..12].try_into().unwrap()))
+            .collect();
+        assert_eq!(wraps, vec![1_000_000]);
+        let swaps = instructions
+            .iter()
+            .filter(|ix| {
+                ix.program_id == crate::instruction::utils::raydium_amm_v4::accounts::RAYDIUM_AMM_V4
+            })
+            .count();
+        assert_eq!(swaps, 2);
+    }
+
+    #[tokio::test]
+    async fn seed_optimized_split_sell_creates_the_output_account_once() {
+        crate::common::seed::set_default_rents();
+        let mint = Pubkey::new_unique();
+        let legs = vec![amm_v4_leg(mint, 600_000), amm_v4_leg(mint, 400_000)];
+        let mut params =
+            SwapParams::for_test(TradeType::Sell, mint, SOL_TOKEN_ACCOUNT, legs[0].params.clone());
+        params.create_input_mint_ata = false;
+
+        let instructions = SplitInstructionBuilder::new(legs).build(&params, false).await.unwrap();
+        let wsol_creates = instructions
+            .iter()
+            .filter(|ix| ix.program_id == crate::constants::ASSOCIATED_TOKEN_PROGRAM_ID)
+            .count();
+        assert_eq!(wsol_creates, 1);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::common::spl_token_2022::TransferFee;
+
 /// Calculate transaction fee based on amount and fee basis points
 ///
 /// # Parameters
@@ -65,3 +67,112 @@ pub const fn calculate_with_slippage_sell(amount: u64, basis_points: u64) -> u64
         amount - (amount * basis_points / 10000)
     }
 }
+
+/// Amount arriving when `amount` is transferred, net of the Token-2022 transfer fee
+///
+/// `fee` is the mint's fee in force (`None` for mints without the TransferFeeConfig extension).
+#[inline]
+pub fn transfer_fee_excluded_amount(amount: u64, fee: Option<&TransferFee>) -> u64 {
+    fee.map_or(amount, |fee| fee.calculate_post_fee_amount(amount))
+}
+
+/// Result for depositing liquidity into a constant-product pool
+///
+/// `base`/`quote` follow the pool's own token order (PumpSwap base/quote, CPMM token0/token1,
+/// AMM v4 coin/pc).
+#[derive(Clone, Debug)]
+pub struct DepositLiquidityResult {
+    /// LP tokens minted to the depositor
+    pub lp_token: u64,
+    /// Base tokens the deposit takes at current reserves
+    pub base: u64,
+    /// Quote tokens the deposit takes at current reserves
+    pub quote: u64,
+    /// Maximum base amount with slippage protection
+    pub max_base: u64,
+    /// Maximum quote amount with slippage protection
+    pub max_quote: u64,
+}
+
+/// Result for withdrawing liquidity from a constant-product pool
+#[derive(Clone, Debug)]
+pub struct WithdrawLiquidityResult {
+    /// LP tokens burned
+    pub lp_token: u64,
+    /// Base tokens returned at current reserves
+    pub base: u64,
+    /// Quote tokens returned at current reserves
+    pub quote: u64,
+    /// Minimum base amount with slippage protection
+    pub min_base: u64,
+    /// Minimum quote amount with slippage protection
+    pub min_quote: u64,
+}
+
+/// Token amounts needed to mint `lp_token` LP tokens, proportional to the reserves and rounded up
+///
+/// # Parameters
+/// * `lp_token` - LP tokens to mint
+/// * `slippage_basis_points` - Slippage basis points, applied upwards to both sides
+/// * `base_reserve` / `quote_reserve` - Pool reserves backing the LP supply
+/// * `lp_supply` - Current LP supply
+pub fn lp_deposit_amounts(
+    lp_token: u64,
+    slippage_basis_points: u64,
+    base_reserve: u64,
+    quote_reserve: u64,
+    lp_supply: u64,
+) -> Result<DepositLiquidityResult, String> {
+    if lp_supply == 0 {
+        return Err("Invalid input: 'lpSupply' cannot be zero.".to_string());
+    }
+    let base = ceil_div((lp_token as u128) * (base_reserve as u128), lp_supply as u128) as u64;
+    let quote = ceil_div((lp_token as u128) * (quote_reserve as u128), lp_supply as u128) as u64;
+    Ok(DepositLiquidityResult {
+        lp_token,
+        base,
+        quote,
+        max_base: calculate_with_slippage_buy(base, slippage_basis_points),
+        max_quote: calculate_with_slippage_buy(quote, slippage_basis_points),
+    })
+}
+
+/// Token amounts returned for burning `lp_token` LP tokens, proportional to the reserves and
+/// rounded down
+pub fn lp_withdraw_amounts(
+    lp_token: u64,
+    slippage_basis_points: u64,
+    base_reserve: u64,
+    quote_reserve: u64,
+    lp_supply: u64,
+) -> Result<WithdrawLiquidityResult, String> {
+    if lp_supply == 0 {
+        return Err("Invalid input: 'lpSupply' cannot be zero.".to_string());
+    }
+    if lp_token > lp_supply {
+        return Err("Cannot withdraw more LP tokens than the pool supply.".to_string());
+    }
+    let base = ((lp_token as u128) * (base_reserve as u128) / (lp_supply as u128)) as u64;
+    let quote = ((lp_token as u128) * (quote_reserve as u128) / (lp_supply as u128)) as u64;
+    Ok(WithdrawLiquidityResult {
+        lp_token,
+        base,
+        quote,
+        min_base: calculate_with_slippage_sell(base, slippage_basis_points),
+        min_quote: calculate_with_slippage_sell(quote, slippage_basis_points),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_fee_excluded_amount_nets_the_fee_in_force() {
+        assert_eq!(transfer_fee_excluded_amount(1_000_000, None), 1_000_000);
+        let fee = TransferFee { epoch: 0, maximum_fee: 5_000, transfer_fee_basis_points: 100 };
+        assert_eq!(transfer_fee_excluded_amount(10_001, Some(&fee)), 9_900);
+        assert_eq!(transfer_fee_excluded_amount(1_000_000, Some(&fee)), 995_000);
+        assert_eq!(transfer_fee_excluded_amount(0, Some(&fee)), 0);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::common::spl_token_2022::TransferFee;
+use crate::instruction::utils::meteora_damm_v2_types::Pool;
+use crate::utils::calc::common::transfer_fee_excluded_amount;
+
+/// Fee numerators are expressed over this denominator.
+pub const FEE_DENOMINATOR: u64 = 1_000_000_000;
+/// Upper bound the program applies to base + dynamic fee.
+pub const MAX_FEE_NUMERATOR: u64 = 500_000_000;
+/// `collect_fee_mode` value that charges fees only in token B.
+pub const COLLECT_FEE_MODE_ONLY_B: u8 = 1;
+
+/// Result of a swap quote on a DAMM v2 pool.
+#[derive(Debug, Clone)]
+pub struct ComputeSwapParams {
+    /// The input amount for the swap
+    pub amount_in: u64,
+    /// The expected output amount after fees
+    pub amount_out: u64,
+    /// The minimum acceptable output amount (considering slippage_basis_points)
+    pub min_amount_out: u64,
+    /// The trading fee amount (in the input token when charged on input, otherwise in the output token)
+    pub fee: u64,
+}
+
+/// `a * b / d` with a 256-bit intermediate product; `None` if the quotient overflows `u128`.
+fn mul_div(a: u128, b: u128, d: u128) -> Option<u128> {
+    if d == 0 {
+        return None;
+    }
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+    let (lo_lo, hi_lo, lo_hi) = (a_lo * b_lo, a_hi * b_lo, a_lo * b_hi);
+    let mid = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let lo = (lo_lo & MASK) | (mid << 64);
+    let hi = a_hi * b_hi + (hi_lo >> 64) + (lo_hi >> 64) + (mid >> 64);
+    if hi == 0 {
+        return Some(lo / d);
+    }
+    if hi >= d {
+        return None;
+    }
+    // Long division of (hi, lo) by d; the quotient fits in 128 bits since hi < d.
+    let (mut rem, mut quotient) = (hi, 0u128);
+    for i in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> i) & 1);
+        if carry == 1 || rem >= d {
+            rem = rem.wrapping_sub(d);
+            quotient |= 1 << i;
+        }
+    }
+    Some(quotient)
+}
+
+/// Total fee numerator: base (cliff) fee plus the dynamic fee when enabled, capped at
+/// `MAX_FEE_NUMERATOR`.
+///
+/// Fee schedulers only lower the fee from the cliff value over time, so this is an upper bound.
+pub fn fee_numerator(pool: &Pool) -> u64 {
+    let dynamic_fee = &pool.pool_fees.dynamic_fee;
+    let variable_fee = if dynamic_fee.initialized != 0 {
+        let square_vfa_bin = dynamic_fee
+            .volatility_accumulator
+            .saturating_mul(dynamic_fee.bin_step as u128)
+            .saturating_pow(2);
+        let v_fee = square_vfa_bin.saturating_mul(dynamic_fee.variable_fee_control as u128);
+        (v_fee.saturating_add(99_999_999_999) / 100_000_000_000).min(u64::MAX as u128) as u64
+    } else {
+        0
+    };
+    pool.pool_fees.base_fee.cliff_fee_numerator.saturating_add(variable_fee).min(MAX_FEE_NUMERATOR)
+}
+
+/// Virtual reserves `(a, b)` of the pool at its current price: `L / sqrt_price` and
+/// `L * sqrt_price / 2^128` (sqrt prices are Q64.64).
+pub fn virtual_reserves(pool: &Pool) -> (u128, u128) {
+    if pool.sqrt_price == 0 {
+        return (0, 0);
+    }
+    let reserve_a = pool.liquidity / pool.sqrt_price;
+    let reserve_b = mul_div(pool.liquidity, pool.sqrt_price, 1 << 64).unwrap_or(u128::MAX) >> 64;
+    (reserve_a, reserve_b)
+}
+
+/// Token amounts tradable before the price leaves `[sqrt_min_price, sqrt_max_price]`: `(a, b)`.
+fn range_reserves(pool: &Pool, reserve_a: u128, reserve_b: u128) -> (u128, u128) {
+    let a_at_max = pool.liquidity.checked_div(pool.sqrt_max_price).unwrap_or(0);
+    let b_at_min = mul_div(pool.liquidity, pool.sqrt_min_price, 1 << 64).unwrap_or(u128::MAX) >> 64;
+    (reserve_a.saturating_sub(a_at_max), reserve_b.saturating_sub(b_at_min))
+}
+
+/// Fee on `amount`, rounded up.
+#[inline]
+fn fee_on_amount(amount: u64, fee_numerator: u64) -> u64 {
+    let fee = (amount as u128 * fee_numerator as u128).div_ceil(FEE_DENOMINATOR as u128);
+    fee.min(amount as u128) as u64
+}
+
+/// Computes the output of swapping `amount_in` on a DAMM v2 pool (exact input).
+///
+/// Uses the pool's virtual reserves, so the result matches the program up to rounding. Fees
+/// are charged on the output, except for `b -> a` swaps in `COLLECT_FEE_MODE_ONLY_B` pools
+/// where they are charged on the input.
+///
+/// # Arguments
+/// * `pool` - Decoded pool state
+/// * `a_to_b` - Whether token A is the input
+/// * `amount_in` - The amount of input tokens to swap
+/// * `slippage_basis_points` - The acceptable slippage in basis points (e.g., 100 for 1%)
+pub fn compute_swap_amount(
+    pool: &Pool,
+    a_to_b: bool,
+    amount_in: u64,
+    slippage_basis_points: u64,
+) -> Result<ComputeSwapParams, String> {
+    compute_swap_amount_with_transfer_fees(
+        pool,
+        a_to_b,
+        amount_in,
+        slippage_basis_points,
+        None,
+        None,
+    )
+}
+
+/// `compute_swap_amount` for Token-2022 mints with a transfer fee: the pool swaps `amount_in`
+/// net of the input mint's fee, and `amount_out` / `min_amount_out` are net of the output
+/// mint's fee, as the program's `minimum_amount_out` check sees them.
+pub fn compute_swap_amount_with_transfer_fees(
+    pool: &Pool,
+    a_to_b: bool,
+    amount_in: u64,
+    slippage_basis_points: u64,
+    input_transfer_fee: Option<&TransferFee>,
+    output_transfer_fee: Option<&TransferFee>,
+) -> Result<ComputeSwapParams, String> {
+    let swapped_in = transfer_fee_excluded_amount(amount_in, input_transfer_fee);
+    let (reserve_a, reserve_b) = virtual_reserves(pool);
+    if reserve_a == 0 || reserve_b == 0 {
+        return Err("Pool has no liquidity".to_string());
+    }
+    let fee_numerator = fee_numerator(pool);
+    let fee_on_input = !a_to_b && pool.collect_fee_mode == COLLECT_FEE_MODE_ONLY_B;
+
+    let (input_fee, net_in) = if fee_on_input {
+        let fee = fee_on_amount(swapped_in, fee_numerator);
+        (fee, swapped_in - fee)
+    } else {
+        (0, swapped_in)
+    };
+    let (input_reserve, output_reserve) =
+        if a_to_b { (reserve_a, reserve_b) } else { (reserve_b, reserve_a) };
+    let gross_out = mul_div(output_reserve, net_in as u128, input_reserve + net_in as u128)
+        .ok_or_else(|| "Swap output overflow".to_string())?;
+
+    let (available_a, available_b) = range_reserves(pool, reserve_a, reserve_b);
+    let available = if a_to_b { available_b } else { available_a };
+    if gross_out > available {
+        return Err("Swap would move the price outside the pool's price range".to_string());
+    }
+    let gross_out = gross_out as u64;
+
+    let (fee, amount_out) = if fee_on_input {
+        (input_fee, gross_out)
+    } else {
+        let fee = fee_on_amount(gross_out, fee_numerator);
+        (fee, gross_out - fee)
+    };
+    let amount_out = transfer_fee_excluded_amount(amount_out, output_transfer_fee);
+    let min_amount_out =
+        ((amount_out as f64) * (1.0 - (slippage_basis_points as f64) / 10000.0)) as u64;
+
+    Ok(ComputeSwapParams { amount_in, amount_out, min_amount_out, fee })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_div_handles_wide_products() {
+        assert_eq!(mul_div(u128::MAX, 4, 8), Some(u128::MAX / 2));
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90), Some(1 << 110));
+        assert_eq!(mul_div(u128::MAX, u128::MAX, 1), None);
+    }
+
+    #[test]
+    fn swap_matches_constant_product_on_virtual_reserves() {
+        // sqrt_price = 1.0 (Q64.64), L = 1e9 << 64 -> 1e9 virtual reserves on each side.
+        let mut pool = Pool {
+            liquidity: 1_000_000_000u128 << 64,
+            sqrt_price: 1u128 << 64,
+            sqrt_min_price: 1u128 << 32,
+            sqrt_max_price: 1u128 << 96,
+            ..Default::default()
+        };
+        pool.pool_fees.base_fee.cliff_fee_numerator = 2_500_000; // 0.25%
+        let result = compute_swap_amount(&pool, true, 1_000_000, 0).unwrap();
+        // 1e9 * 1e6 / (1e9 + 1e6) = 999_000 (floor), fee 0.25% on output rounded up.
+        assert_eq!(result.amount_out, 999_000 - 2_498);
+        assert_eq!(result.fee, 2_498);
+    }
+
+    #[test]
+    fn transfer_fees_reduce_input_and_output() {
+        let pool = Pool {
+            liquidity: 1_000_000_000u128 << 64,
+            sqrt_price: 1u128 << 64,
+            sqrt_min_price: 1u128 << 32,
+            sqrt_max_price: 1u128 << 96,
+            ..Default::default()
+        };
+        // 1% on the input, capped at 5_000; 2% on the output.
+        let input_fee =
+            TransferFee { epoch: 0, maximum_fee: 5_000, transfer_fee_basis_points: 100 };
+        let output_fee =
+            TransferFee { epoch: 0, maximum_fee: u64::MAX, transfer_fee_basis_points: 200 };
+        assert_eq!(input_fee.calculate_fee(1_000_000), 5_000);
+        assert_eq!(input_fee.calculate_fee(101), 2);
+
+        let plain = compute_swap_amount(&pool, true, 995_000, 0).unwrap();
+        let result = compute_swap_amount_with_transfer_fees(
+            &pool,
+            true,
+            1_000_000,
+            0,
+            Some(&input_fee),
+            Some(&output_fee),
+        )
+        .unwrap();
+        assert_eq!(result.amount_in, 1_000_000);
+        assert_eq!(result.amount_out, output_fee.calculate_post_fee_amount(plain.amount_out));
+    }
+}
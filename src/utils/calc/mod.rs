@@ -1,5 +1,6 @@
 pub mod bonk;
 pub mod common;
+pub mod meteora_damm_v2;
 pub mod pumpfun;
 pub mod pumpswap;
 pub mod raydium_amm_v4;
@@ -1,6 +1,8 @@
 use super::common::{
     calculate_with_slippage_buy, calculate_with_slippage_sell, ceil_div, compute_fee,
+    lp_deposit_amounts, lp_withdraw_amounts,
 };
+pub use super::common::{DepositLiquidityResult, WithdrawLiquidityResult};
 use crate::instruction::utils::pumpswap::accounts::{
     COIN_CREATOR_FEE_BASIS_POINTS, LP_FEE_BASIS_POINTS, PROTOCOL_FEE_BASIS_POINTS,
 };
@@ -273,3 +275,127 @@ pub fn sell_quote_input_internal(
 
     Ok(SellQuoteInputResult { internal_raw_quote: raw_quote, base: base_amount_in, min_quote })
 }
+
+/// Calculate the base and quote amounts needed to mint `lp_token` LP tokens
+///
+/// Amounts are rounded up (the pool never under-collects), proportional to the current reserves.
+///
+/// # Arguments
+/// * `lp_token` - LP tokens to mint
+/// * `slippage_basis_points` - Slippage tolerance in basis points (100 = 1%)
+/// * `base_reserve` - Base token reserves in the pool
+/// * `quote_reserve` - Quote token reserves in the pool
+/// * `lp_supply` - Current LP supply of the pool (`Pool::lp_supply`)
+pub fn deposit_lp_token_internal(
+    lp_token: u64,
+    slippage_basis_points: u64,
+    base_reserve: u64,
+    quote_reserve: u64,
+    lp_supply: u64,
+) -> Result<DepositLiquidityResult, String> {
+    lp_deposit_amounts(lp_token, slippage_basis_points, base_reserve, quote_reserve, lp_supply)
+}
+
+/// Calculate the LP tokens minted and quote tokens needed when depositing `base` base tokens
+///
+/// # Arguments
+/// * `base` - Base tokens to deposit
+/// * `slippage_basis_points` - Slippage tolerance in basis points (100 = 1%)
+/// * `base_reserve` - Base token reserves in the pool
+/// * `quote_reserve` - Quote token reserves in the pool
+/// * `lp_supply` - Current LP supply of the pool
+pub fn deposit_base_internal(
+    base: u64,
+    slippage_basis_points: u64,
+    base_reserve: u64,
+    quote_reserve: u64,
+    lp_supply: u64,
+) -> Result<DepositLiquidityResult, String> {
+    if base_reserve == 0 || quote_reserve == 0 {
+        return Err("Invalid input: 'baseReserve' or 'quoteReserve' cannot be zero.".to_string());
+    }
+    let lp_token = ((base as u128) * (lp_supply as u128) / (base_reserve as u128)) as u64;
+    deposit_lp_token_internal(
+        lp_token,
+        slippage_basis_points,
+        base_reserve,
+        quote_reserve,
+        lp_supply,
+    )
+}
+
+/// Calculate the LP tokens minted and base tokens needed when depositing `quote` quote tokens
+pub fn deposit_quote_internal(
+    quote: u64,
+    slippage_basis_points: u64,
+    base_reserve: u64,
+    quote_reserve: u64,
+    lp_supply: u64,
+) -> Result<DepositLiquidityResult, String> {
+    if base_reserve == 0 || quote_reserve == 0 {
+        return Err("Invalid input: 'baseReserve' or 'quoteReserve' cannot be zero.".to_string());
+    }
+    let lp_token = ((quote as u128) * (lp_supply as u128) / (quote_reserve as u128)) as u64;
+    deposit_lp_token_internal(
+        lp_token,
+        slippage_basis_points,
+        base_reserve,
+        quote_reserve,
+        lp_supply,
+    )
+}
+
+/// Calculate the base and quote amounts returned for burning `lp_token` LP tokens
+///
+/// Amounts are rounded down, proportional to the current reserves.
+pub fn withdraw_internal(
+    lp_token: u64,
+    slippage_basis_points: u64,
+    base_reserve: u64,
+    quote_reserve: u64,
+    lp_supply: u64,
+) -> Result<WithdrawLiquidityResult, String> {
+    lp_withdraw_amounts(lp_token, slippage_basis_points, base_reserve, quote_reserve, lp_supply)
+}
+
+/// LP supply minted by `create_pool` for the initial deposit: `floor(sqrt(base * quote))`
+/// (part of it may be locked by the program and never credited to the creator).
+pub fn initial_lp_supply(base: u64, quote: u64) -> u64 {
+    let product = (base as u128) * (quote as u128);
+    if product == 0 {
+        return 0;
+    }
+    // Newton's method on u128
+    let mut x = product;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + product / x) / 2;
+    }
+    x as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deposit_and_withdraw_are_proportional() {
+        let deposit = deposit_base_internal(1_000, 100, 10_000, 50_000, 20_000).unwrap();
+        assert_eq!(deposit.lp_token, 2_000);
+        assert_eq!(deposit.quote, 5_000);
+        assert_eq!(deposit.max_quote, 5_050);
+
+        let withdraw = withdraw_internal(2_000, 100, 11_000, 55_000, 22_000).unwrap();
+        assert_eq!((withdraw.base, withdraw.quote), (1_000, 5_000));
+        assert_eq!(withdraw.min_base, 990);
+    }
+
+    #[test]
+    fn initial_lp_supply_is_integer_sqrt() {
+        assert_eq!(initial_lp_supply(4, 9), 6);
+        assert_eq!(initial_lp_supply(10, 10), 10);
+        assert_eq!(initial_lp_supply(2, 3), 2);
+        assert_eq!(initial_lp_supply(0, 5), 0);
+    }
+}
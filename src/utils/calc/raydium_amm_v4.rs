@@ -1,6 +1,7 @@
 use crate::instruction::utils::raydium_amm_v4::accounts::{
     SWAP_FEE_DENOMINATOR, SWAP_FEE_NUMERATOR, TRADE_FEE_DENOMINATOR, TRADE_FEE_NUMERATOR,
 };
+use crate::instruction::utils::raydium_amm_v4_types::AmmInfo;
 
 /// Computes trading fee using ceiling division.
 ///
@@ -148,3 +149,35 @@ pub fn compute_swap_amount(
         fee: swap_result.trade_fee,
     }
 }
+
+/// Vault balances minus the PnL the AMM has not taken yet.
+///
+/// These are the reserves the program prices deposits and withdrawals against, with
+/// `AmmInfo::lp_amount` as the LP supply.
+///
+/// # Returns
+/// Returns (coin_reserve, pc_reserve)
+pub fn lp_reserves(coin_vault_amount: u64, pc_vault_amount: u64, amm_info: &AmmInfo) -> (u64, u64) {
+    (
+        coin_vault_amount.saturating_sub(amm_info.out_put.need_take_pnl_coin),
+        pc_vault_amount.saturating_sub(amm_info.out_put.need_take_pnl_pc),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::utils::raydium_amm_v4_types::{amm_info_decode, AMM_INFO_SIZE};
+
+    #[test]
+    fn lp_reserves_exclude_pnl_not_yet_taken() {
+        // 16 u64 state fields + 8 u64 fees, then `OutPutData::need_take_pnl_{coin,pc}`.
+        let mut data = vec![0u8; AMM_INFO_SIZE];
+        data[192..200].copy_from_slice(&1_500u64.to_le_bytes());
+        data[200..208].copy_from_slice(&700u64.to_le_bytes());
+        let amm_info = amm_info_decode(&data).unwrap();
+
+        assert_eq!(lp_reserves(1_000_000, 2_000_000, &amm_info), (998_500, 1_999_300));
+        assert_eq!(lp_reserves(1_000, 500, &amm_info), (0, 0));
+    }
+}
@@ -1,6 +1,9 @@
+use crate::common::spl_token_2022::TransferFee;
 use crate::instruction::utils::raydium_cpmm::accounts::{
     CREATOR_FEE_RATE, FEE_RATE_DENOMINATOR_VALUE, FUND_FEE_RATE, PROTOCOL_FEE_RATE, TRADE_FEE_RATE,
 };
+use crate::instruction::utils::raydium_cpmm_types::PoolState;
+use crate::utils::calc::common::transfer_fee_excluded_amount;
 
 /// Computes trading fee using ceiling division.
 ///
@@ -166,12 +169,43 @@ pub fn compute_swap_amount(
     is_base_in: bool,
     amount_in: u64,
     slippage_basis_points: u64,
+) -> ComputeSwapParams {
+    compute_swap_amount_with_transfer_fees(
+        base_reserve,
+        quote_reserve,
+        is_base_in,
+        amount_in,
+        slippage_basis_points,
+        None,
+        None,
+    )
+}
+
+/// `compute_swap_amount` for pools holding Token-2022 mints with a transfer fee.
+///
+/// The pool receives `amount_in` net of the input mint's fee, and `amount_out` /
+/// `min_amount_out` are what the payer receives net of the output mint's fee, which is the
+/// amount the program checks against `minimum_amount_out`.
+///
+/// # Arguments
+/// * `input_transfer_fee` - Transfer fee in force for the input mint, if any
+/// * `output_transfer_fee` - Transfer fee in force for the output mint, if any
+#[inline]
+pub fn compute_swap_amount_with_transfer_fees(
+    base_reserve: u64,
+    quote_reserve: u64,
+    is_base_in: bool,
+    amount_in: u64,
+    slippage_basis_points: u64,
+    input_transfer_fee: Option<&TransferFee>,
+    output_transfer_fee: Option<&TransferFee>,
 ) -> ComputeSwapParams {
     let (input_reserve, output_reserve) =
         if is_base_in { (base_reserve, quote_reserve) } else { (quote_reserve, base_reserve) };
 
+    let net_amount_in = transfer_fee_excluded_amount(amount_in, input_transfer_fee);
     let swap_result = swap_base_input(
-        amount_in,
+        net_amount_in,
         input_reserve,
         output_reserve,
         TRADE_FEE_RATE,
@@ -180,17 +214,96 @@ pub fn compute_swap_amount(
         FUND_FEE_RATE,
         true,
     );
+    let amount_out = transfer_fee_excluded_amount(swap_result.output_amount, output_transfer_fee);
 
-    let min_amount_out = ((swap_result.output_amount as f64)
-        * (1.0 - (slippage_basis_points as f64) / 10000.0)) as u64;
+    let min_amount_out =
+        ((amount_out as f64) * (1.0 - (slippage_basis_points as f64) / 10000.0)) as u64;
 
-    let all_trade = swap_result.input_amount == amount_in;
+    let all_trade = swap_result.input_amount == net_amount_in;
 
     ComputeSwapParams {
         all_trade,
         amount_in,
-        amount_out: swap_result.output_amount,
+        amount_out,
         min_amount_out,
         fee: swap_result.trade_fee,
     }
 }
+
+/// Vault balances net of the protocol and fund fees still held by the pool.
+///
+/// LP tokens are only redeemable against these amounts; pass them as reserves to
+/// `common::lp_deposit_amounts` / `common::lp_withdraw_amounts` together with `PoolState::lp_supply`.
+///
+/// # Returns
+/// Returns (token0_reserve, token1_reserve)
+pub fn lp_reserves(
+    token0_vault_amount: u64,
+    token1_vault_amount: u64,
+    pool_state: &PoolState,
+) -> (u64, u64) {
+    (
+        token0_vault_amount
+            .saturating_sub(pool_state.protocol_fees_token0)
+            .saturating_sub(pool_state.fund_fees_token0),
+        token1_vault_amount
+            .saturating_sub(pool_state.protocol_fees_token1)
+            .saturating_sub(pool_state.fund_fees_token1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::utils::raydium_cpmm_types::{pool_state_decode, POOL_STATE_SIZE};
+
+    #[test]
+    fn lp_reserves_exclude_protocol_and_fund_fees() {
+        // 10 pubkeys + 5 u8 + lp_supply, then protocol fees 0/1 and fund fees 0/1.
+        let mut data = vec![0u8; POOL_STATE_SIZE];
+        for (offset, fee) in [(333usize, 100u64), (341, 200), (349, 30), (357, 40)] {
+            data[offset..offset + 8].copy_from_slice(&fee.to_le_bytes());
+        }
+        let pool_state = pool_state_decode(&data).unwrap();
+
+        assert_eq!(lp_reserves(10_000, 20_000, &pool_state), (9_870, 19_760));
+        assert_eq!(lp_reserves(50, 50, &pool_state), (0, 0));
+    }
+
+    #[test]
+    fn transfer_fees_net_the_input_and_the_output() {
+        let fee = TransferFee { epoch: 0, maximum_fee: u64::MAX, transfer_fee_basis_points: 100 };
+        let plain = compute_swap_amount(1_000_000_000, 2_000_000_000, true, 1_000_000, 100);
+        let netted_in = compute_swap_amount(1_000_000_000, 2_000_000_000, true, 990_000, 100);
+
+        let with_input_fee = compute_swap_amount_with_transfer_fees(
+            1_000_000_000,
+            2_000_000_000,
+            true,
+            1_000_000,
+            100,
+            Some(&fee),
+            None,
+        );
+        assert!(with_input_fee.all_trade);
+        assert_eq!(with_input_fee.amount_in, 1_000_000);
+        assert_eq!(with_input_fee.amount_out, netted_in.amount_out);
+        assert_eq!(with_input_fee.fee, netted_in.fee);
+
+        let with_output_fee = compute_swap_amount_with_transfer_fees(
+            1_000_000_000,
+            2_000_000_000,
+            true,
+            1_000_000,
+            100,
+            None,
+            Some(&fee),
+        );
+        assert!(with_output_fee.all_trade);
+        assert_eq!(with_output_fee.amount_out, fee.calculate_post_fee_amount(plain.amount_out));
+        assert_eq!(
+            with_output_fee.min_amount_out,
+            (with_output_fee.amount_out as f64 * 0.99) as u64
+        );
+    }
+}